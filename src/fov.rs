@@ -0,0 +1,229 @@
+//! Field-of-view and line-of-sight queries.
+
+use crate::{Coord, Direction4, LineIter, Scalar, SignedScalar, UnsignedScalar};
+
+/// Check whether there is an unobstructed line of sight from `start` to `end`.
+///
+/// Every coordinate strictly between the two endpoints is tested with `opaque`;
+/// the endpoints themselves are never checked, so you can see the wall you're looking at.
+pub fn los<T: UnsignedScalar>(
+    start: Coord<T>,
+    end: Coord<T>,
+    opaque: impl Fn(Coord<T>) -> bool,
+) -> bool {
+    los_blocker(start, end, opaque).is_none()
+}
+
+/// Like [`los`], but return the first coordinate blocking the line of sight,
+/// or `None` if `end` is visible from `start`.
+pub fn los_blocker<T: UnsignedScalar>(
+    start: Coord<T>,
+    end: Coord<T>,
+    opaque: impl Fn(Coord<T>) -> bool,
+) -> Option<Coord<T>> {
+    LineIter::new(start, end).skip(1).find(|&c| opaque(c))
+}
+
+/// Like [`los`], but only for sight along a row or column, like a rook in chess.
+///
+/// If `start` and `end` don't share a row or column this is always false.
+/// Otherwise the cells between them are scanned directly, which is quicker than
+/// walking a [`LineIter`].
+pub fn los_orthogonal<T: UnsignedScalar>(
+    start: Coord<T>,
+    end: Coord<T>,
+    opaque: impl Fn(Coord<T>) -> bool,
+) -> bool {
+    let (from, to, cell): (T, T, &dyn Fn(T) -> Coord<T>) = if start.y == end.y {
+        (start.x.min(end.x), start.x.max(end.x), &|x| Coord {
+            x,
+            y: start.y,
+        })
+    } else if start.x == end.x {
+        (start.y.min(end.y), start.y.max(end.y), &|y| Coord {
+            x: start.x,
+            y,
+        })
+    } else {
+        return false;
+    };
+
+    let mut cursor = from + T::ONE;
+    while cursor < to {
+        if opaque(cell(cursor)) {
+            return false;
+        }
+        cursor += T::ONE;
+    }
+    true
+}
+
+/// How well protected something is, for [`cover_value`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoverLevel {
+    #[default]
+    None,
+    Half,
+    Full,
+}
+
+/// Figure out how much cover `defender` has against a shot from `attacker`,
+/// XCOM-style.
+///
+/// Only cells right next to the defender give cover, and only on the sides
+/// facing the attacker. A side faces the attacker if the attacker is at least
+/// as far out in that direction as it is off to the side, so a shot from
+/// exactly diagonal can hit either of two sides, and an attacker further
+/// around than that is flanking the cover. The best cover found wins.
+///
+/// `cover` says how much cover each cell gives. This doesn't check whether the
+/// attacker can see the defender at all; use [`los`] for that.
+pub fn cover_value<T: UnsignedScalar>(
+    defender: Coord<T>,
+    attacker: Coord<T>,
+    cover: impl Fn(Coord<T>) -> CoverLevel,
+) -> CoverLevel {
+    let delta = attacker.to_icoord() - defender.to_icoord();
+    Direction4::DIRECTIONS
+        .into_iter()
+        .filter(|side| {
+            let along = delta.dot(side.deltas_as());
+            let across = delta.dot(side.rotate_by(1).deltas_as()).saturating_abs();
+            along > T::Signed::ZERO && along >= across
+        })
+        .filter_map(|side| defender.offset4(side))
+        .filter(|cell| *cell != attacker)
+        .map(cover)
+        .max()
+        .unwrap_or(CoverLevel::None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Area, BitGrid};
+
+    #[test]
+    fn test_los() {
+        let wall = Coord::new(3, 2);
+        let opaque = |c: Coord| c == wall;
+
+        assert!(los(Coord::new(0, 1), Coord::new(6, 4), |_| false));
+        assert!(!los(Coord::new(0, 1), Coord::new(6, 4), opaque));
+        assert_eq!(
+            los_blocker(Coord::new(0, 1), Coord::new(6, 4), opaque),
+            Some(wall)
+        );
+        // Endpoints don't block
+        assert!(los(Coord::new(0, 1), wall, opaque));
+        assert!(los(wall, Coord::new(6, 4), opaque));
+        assert!(los(wall, wall, opaque));
+    }
+
+    #[test]
+    fn test_los_bit_grid() {
+        // The same bits a pathfinder walks through: flip them to get what
+        // blocks sight
+        let mut floor = BitGrid::new(7, 5).invert();
+        floor.set(Coord::new(3, 2), false);
+        let walls = floor.invert();
+
+        assert!(!los(Coord::new(0, 1), Coord::new(6, 4), walls.as_fn()));
+        assert!(los(Coord::new(0, 0), Coord::new(6, 0), walls.as_fn()));
+        assert!(!los_orthogonal(
+            Coord::new(0, 2),
+            Coord::new(6, 2),
+            walls.as_fn()
+        ));
+        // Off the grid is clear
+        assert!(los(Coord::new(0, 5), Coord::new(6, 5), walls.as_fn()));
+    }
+
+    #[test]
+    fn test_cover() {
+        // ......
+        // ..h...
+        // ..DF..
+        // ......
+        let defender = Coord::new(2, 2);
+        let cover = |c: Coord| match (c.x, c.y) {
+            (2, 1) => CoverLevel::Half,
+            (3, 2) => CoverLevel::Full,
+            _ => CoverLevel::None,
+        };
+
+        // Straight on
+        assert_eq!(
+            cover_value(defender, Coord::new(5, 2), cover),
+            CoverLevel::Full
+        );
+        assert_eq!(
+            cover_value(defender, Coord::new(2, 0), cover),
+            CoverLevel::Half
+        );
+        assert_eq!(
+            cover_value(defender, Coord::new(0, 2), cover),
+            CoverLevel::None
+        );
+        // Diagonal shots use both sides
+        assert_eq!(
+            cover_value(defender, Coord::new(4, 0), cover),
+            CoverLevel::Full
+        );
+        assert_eq!(
+            cover_value(defender, Coord::new(0, 0), cover),
+            CoverLevel::Half
+        );
+        assert_eq!(
+            cover_value(defender, Coord::new(5, 1), cover),
+            CoverLevel::Full
+        );
+        // Mostly from below, so the wall to the east is flanked
+        assert_eq!(
+            cover_value(defender, Coord::new(3, 5), cover),
+            CoverLevel::None
+        );
+        assert_eq!(
+            cover_value(defender, Coord::new(4, 3), cover),
+            CoverLevel::Full
+        );
+        assert_eq!(
+            cover_value(defender, Coord::new(3, 4), cover),
+            CoverLevel::None
+        );
+        // Point blank
+        assert_eq!(
+            cover_value(defender, Coord::new(2, 3), cover),
+            CoverLevel::None
+        );
+        assert_eq!(cover_value(defender, defender, cover), CoverLevel::None);
+    }
+
+    #[test]
+    fn test_los_orthogonal() {
+        let wall = Coord::new(3, 2);
+        let opaque = |c: Coord| c == wall;
+
+        assert!(!los_orthogonal(Coord::new(0, 1), Coord::new(6, 4), |_| {
+            false
+        }));
+        assert!(los_orthogonal(Coord::new(0, 1), Coord::new(6, 1), opaque));
+        assert!(!los_orthogonal(Coord::new(0, 2), Coord::new(6, 2), opaque));
+        assert!(!los_orthogonal(Coord::new(6, 2), Coord::new(0, 2), opaque));
+        assert!(!los_orthogonal(Coord::new(3, 0), Coord::new(3, 5), opaque));
+        assert!(los_orthogonal(Coord::new(3, 0), Coord::new(3, 1), opaque));
+        // Endpoints don't block
+        assert!(los_orthogonal(wall, Coord::new(3, 5), opaque));
+        assert!(los_orthogonal(wall, wall, opaque));
+
+        // Agrees with the general version
+        for start in [Coord::new(0, 2), Coord::new(3, 4), Coord::new(1, 1)] {
+            for end in Area::new(Coord::new(0, 0), 7, 5) {
+                if start.x == end.x || start.y == end.y {
+                    assert_eq!(los_orthogonal(start, end, opaque), los(start, end, opaque));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,746 @@
+use super::{Coord, UnsignedScalar};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Area<T = u32> {
+    pub corner: Coord<T>,
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: UnsignedScalar> Area<T> {
+    pub fn new(corner: Coord<T>, width: T, height: T) -> Self {
+        Self {
+            corner,
+            width,
+            height,
+        }
+    }
+
+    /// Does this area have no cells in it?
+    pub fn is_empty(&self) -> bool {
+        self.width == T::ZERO || self.height == T::ZERO
+    }
+
+    pub fn contains(&self, pos: Coord<T>) -> bool {
+        self.x1() <= pos.x && pos.x < self.x2() && self.y1() <= pos.y && pos.y < self.y2()
+    }
+
+    /// Do the two areas share any cells?
+    pub fn overlaps(&self, other: &Area<T>) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// Get the area of cells that are in both areas,
+    /// or `None` if they don't overlap.
+    pub fn intersect(&self, other: &Area<T>) -> Option<Area<T>> {
+        let x1 = self.x1().max(other.x1());
+        let y1 = self.y1().max(other.y1());
+        let x2 = self.x2().min(other.x2());
+        let y2 = self.y2().min(other.y2());
+        if x1 < x2 && y1 < y2 {
+            Some(Area::new(Coord { x: x1, y: y1 }, x2 - x1, y2 - y1))
+        } else {
+            None
+        }
+    }
+
+    /// Get the smallest area containing both areas.
+    pub fn union_bounds(&self, other: &Area<T>) -> Area<T> {
+        let x1 = self.x1().min(other.x1());
+        let y1 = self.y1().min(other.y1());
+        let x2 = self.x2().max(other.x2());
+        let y2 = self.y2().max(other.y2());
+        Area::new(Coord { x: x1, y: y1 }, x2 - x1, y2 - y1)
+    }
+
+    /// Grow this area by `n` cells on every side.
+    ///
+    /// The corner can't go negative, so if it's less than `n` from zero
+    /// it only grows as far as zero on that side.
+    pub fn expand(&self, n: T) -> Area<T> {
+        let x1 = self.x1().saturating_sub(n);
+        let y1 = self.y1().saturating_sub(n);
+        Area::new(
+            Coord { x: x1, y: y1 },
+            self.x2() + n - x1,
+            self.y2() + n - y1,
+        )
+    }
+
+    /// Shrink this area by `n` cells on every side.
+    ///
+    /// Returns `None` if there would be nothing left.
+    pub fn shrink(&self, n: T) -> Option<Area<T>> {
+        let two_n = n + n;
+        if self.width <= two_n || self.height <= two_n {
+            return None;
+        }
+        Some(Area::new(
+            Coord {
+                x: self.x1() + n,
+                y: self.y1() + n,
+            },
+            self.width - two_n,
+            self.height - two_n,
+        ))
+    }
+
+    pub fn center(&self) -> Coord<T> {
+        let two = T::ONE + T::ONE;
+        Coord {
+            x: self.corner.x + self.width / two,
+            y: self.corner.y + self.height / two,
+        }
+    }
+
+    /// Get the four corner cells of this area, clockwise starting from the top-left.
+    ///
+    /// If the area is empty, this is meaningless.
+    pub fn corners(&self) -> [Coord<T>; 4] {
+        let right = (self.x2().saturating_sub(T::ONE)).max(self.x1());
+        let bottom = (self.y2().saturating_sub(T::ONE)).max(self.y1());
+        [
+            Coord {
+                x: self.x1(),
+                y: self.y1(),
+            },
+            Coord {
+                x: right,
+                y: self.y1(),
+            },
+            Coord {
+                x: right,
+                y: bottom,
+            },
+            Coord {
+                x: self.x1(),
+                y: bottom,
+            },
+        ]
+    }
+
+    /// Cut this area with a horizontal line into a top and bottom half.
+    ///
+    /// The top half is `at` cells tall; if `at` is larger than the height it's
+    /// clamped, so the bottom half will be empty.
+    pub fn split_horizontal(&self, at: T) -> (Area<T>, Area<T>) {
+        let at = at.min(self.height);
+        let top = Area::new(self.corner, self.width, at);
+        let bottom = Area::new(
+            Coord {
+                x: self.x1(),
+                y: self.y1() + at,
+            },
+            self.width,
+            self.height - at,
+        );
+        (top, bottom)
+    }
+
+    /// Cut this area with a vertical line into a left and right half.
+    ///
+    /// The left half is `at` cells wide; if `at` is larger than the width it's
+    /// clamped, so the right half will be empty.
+    pub fn split_vertical(&self, at: T) -> (Area<T>, Area<T>) {
+        let at = at.min(self.width);
+        let left = Area::new(self.corner, at, self.height);
+        let right = Area::new(
+            Coord {
+                x: self.x1() + at,
+                y: self.y1(),
+            },
+            self.width - at,
+            self.height,
+        );
+        (left, right)
+    }
+
+    /// Iterate over each row of this area, top to bottom.
+    ///
+    /// Each row is itself an iterator over the cells in it, left to right.
+    pub fn rows(&self) -> AreaRows<T> {
+        AreaRows {
+            area: *self,
+            cursor: T::ZERO,
+        }
+    }
+
+    /// Iterate over each column of this area, left to right.
+    ///
+    /// Each column is itself an iterator over the cells in it, top to bottom.
+    pub fn columns(&self) -> AreaColumns<T> {
+        AreaColumns {
+            area: *self,
+            cursor: T::ZERO,
+        }
+    }
+
+    /// Iterate over every `step_x`th cell across and every `step_y`th cell
+    /// down, in row-major order, starting at the corner.
+    ///
+    /// Panics if either step is zero.
+    ///
+    /// ```
+    /// # use aglet::{Area, Coord};
+    /// let dots: Vec<_> = Area::new(Coord::new(1, 1), 5, 3).iter_step(2, 2).collect();
+    /// assert_eq!(dots, [(1, 1), (3, 1), (5, 1), (1, 3), (3, 3), (5, 3)].map(Coord::from));
+    /// ```
+    pub fn iter_step(&self, step_x: T, step_y: T) -> AreaStepIter<T> {
+        assert!(
+            step_x != T::ZERO && step_y != T::ZERO,
+            "steps must be positive"
+        );
+        let steps = |len: T, step: T| {
+            let len = len.to_usize().unwrap_or(usize::MAX);
+            len.div_ceil(step.to_usize().unwrap_or(usize::MAX))
+        };
+        let columns = steps(self.width, step_x);
+        AreaStepIter {
+            corner: self.corner,
+            step: Coord {
+                x: step_x,
+                y: step_y,
+            },
+            columns,
+            total: columns.saturating_mul(steps(self.height, step_y)),
+            cursor: 0,
+        }
+    }
+
+    /// Get the cells along the edge of this area.
+    pub fn edges(&self) -> Edges<T> {
+        Edges::new(self.corner, self.width, self.height)
+    }
+
+    /// Split this area into the ring of cells around its edge and everything
+    /// inside that.
+    ///
+    /// If the area is 2 or fewer cells wide or tall, it's all border and the
+    /// interior is `None`.
+    pub fn border_and_interior(&self) -> (Edges<T>, Option<Area<T>>) {
+        (self.edges(), self.shrink(T::ONE))
+    }
+
+    /// Left-side X coordinate
+    pub fn x1(&self) -> T {
+        self.corner.x
+    }
+    /// Right-side X coordinate
+    pub fn x2(&self) -> T {
+        self.corner.x + self.width
+    }
+    /// Top Y coordinate
+    pub fn y1(&self) -> T {
+        self.corner.y
+    }
+    /// Bottom Y coordinate
+    pub fn y2(&self) -> T {
+        self.corner.y + self.height
+    }
+}
+
+impl<T: UnsignedScalar> IntoIterator for Area<T> {
+    type Item = Coord<T>;
+
+    type IntoIter = AreaIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let width = self.width.to_usize().unwrap_or(usize::MAX);
+        let height = self.height.to_usize().unwrap_or(usize::MAX);
+        AreaIter {
+            corner: self.corner,
+            width,
+            total: width.saturating_mul(height),
+            cursor: 0,
+        }
+    }
+}
+
+/// Iterator over the cells of an [`Area`] in row-major order.
+///
+/// The count is kept in a `usize`, so small scalars like `u8` can still
+/// iterate areas with more cells than they can count to.
+pub struct AreaIter<T = u32> {
+    corner: Coord<T>,
+    width: usize,
+    total: usize,
+    cursor: usize,
+}
+
+impl<T: UnsignedScalar> Iterator for AreaIter<T> {
+    type Item = Coord<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.total {
+            return None;
+        }
+
+        // Both fit, since they're less than the area's width and height
+        let x = T::from_usize(self.cursor % self.width).unwrap();
+        let y = T::from_usize(self.cursor / self.width).unwrap();
+        self.cursor += 1;
+        Some(self.corner + Coord { x, y })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: UnsignedScalar> ExactSizeIterator for AreaIter<T> {
+    fn len(&self) -> usize {
+        self.total - self.cursor
+    }
+}
+
+/// Iterator over the rows of an [`Area`], made with [`Area::rows`].
+pub struct AreaRows<T = u32> {
+    area: Area<T>,
+    cursor: T,
+}
+
+impl<T: UnsignedScalar> Iterator for AreaRows<T> {
+    type Item = AreaIter<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.area.height {
+            return None;
+        }
+        let corner = Coord {
+            x: self.area.x1(),
+            y: self.area.y1() + self.cursor,
+        };
+        self.cursor += T::ONE;
+        Some(Area::new(corner, self.area.width, T::ONE).into_iter())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: UnsignedScalar> ExactSizeIterator for AreaRows<T> {
+    fn len(&self) -> usize {
+        (self.area.height - self.cursor)
+            .to_usize()
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// Iterator over the columns of an [`Area`], made with [`Area::columns`].
+pub struct AreaColumns<T = u32> {
+    area: Area<T>,
+    cursor: T,
+}
+
+impl<T: UnsignedScalar> Iterator for AreaColumns<T> {
+    type Item = AreaIter<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.area.width {
+            return None;
+        }
+        let corner = Coord {
+            x: self.area.x1() + self.cursor,
+            y: self.area.y1(),
+        };
+        self.cursor += T::ONE;
+        Some(Area::new(corner, T::ONE, self.area.height).into_iter())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: UnsignedScalar> ExactSizeIterator for AreaColumns<T> {
+    fn len(&self) -> usize {
+        (self.area.width - self.cursor)
+            .to_usize()
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// Iterator over evenly spaced cells in an [`Area`], made with
+/// [`Area::iter_step`].
+#[derive(Clone, Debug)]
+pub struct AreaStepIter<T = u32> {
+    corner: Coord<T>,
+    step: Coord<T>,
+    columns: usize,
+    total: usize,
+    cursor: usize,
+}
+
+impl<T: UnsignedScalar> Iterator for AreaStepIter<T> {
+    type Item = Coord<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.total {
+            return None;
+        }
+        // The column and row times the step are inside the area, so they fit
+        let x = T::from_usize(self.cursor % self.columns).unwrap() * self.step.x;
+        let y = T::from_usize(self.cursor / self.columns).unwrap() * self.step.y;
+        self.cursor += 1;
+        Some(self.corner + Coord { x, y })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: UnsignedScalar> ExactSizeIterator for AreaStepIter<T> {
+    fn len(&self) -> usize {
+        self.total - self.cursor
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Edges<T = u32> {
+    pub corner: Coord<T>,
+    pub width: T,
+    pub height: T,
+}
+
+impl<T: UnsignedScalar> Edges<T> {
+    pub fn new(corner: Coord<T>, width: T, height: T) -> Self {
+        Self {
+            corner,
+            width,
+            height,
+        }
+    }
+
+    /// How many cells are on the edge.
+    fn perimeter(&self) -> T {
+        let two = T::ONE + T::ONE;
+        if self.width == T::ZERO || self.height == T::ZERO {
+            T::ZERO
+        } else if self.width <= two || self.height <= two {
+            // Every cell is on the edge
+            self.width * self.height
+        } else {
+            two * self.width + two * self.height - two - two
+        }
+    }
+}
+
+impl<T: UnsignedScalar> IntoIterator for Edges<T> {
+    type Item = Coord<T>;
+
+    type IntoIter = EdgesIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        EdgesIter {
+            edges: self,
+            cursor: T::ZERO,
+        }
+    }
+}
+
+pub struct EdgesIter<T = u32> {
+    edges: Edges<T>,
+    cursor: T,
+}
+
+impl<T: UnsignedScalar> Iterator for EdgesIter<T> {
+    type Item = Coord<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (zero, one) = (T::ZERO, T::ONE);
+        let (two, three, four) = (one + one, one + one + one, one + one + one + one);
+        let Edges {
+            corner,
+            width,
+            height,
+        } = self.edges;
+        let cursor = self.cursor;
+        if cursor >= self.edges.perimeter() {
+            return None;
+        }
+
+        let (x, y) = if cursor < width {
+            (cursor, zero)
+        } else if cursor + one < width + height {
+            (width - one, cursor + one - width)
+        } else if cursor + two < width * two + height {
+            (width - (cursor + three - width - height), height - one)
+        } else if cursor + four < width * two + height * two {
+            (zero, height - (cursor + four - height - width * two))
+        } else {
+            return None;
+        };
+        self.cursor += one;
+        Some(corner + Coord { x, y })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: UnsignedScalar> ExactSizeIterator for EdgesIter<T> {
+    fn len(&self) -> usize {
+        (self.edges.perimeter() - self.cursor)
+            .to_usize()
+            .unwrap_or(usize::MAX)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use crate::{Area, Coord, Edges};
+
+    #[test]
+    fn areas() {
+        let area = Area::new(Coord::new(0, 0), 5, 5);
+        let area_vec: Vec<_> = area.into_iter().collect();
+        let area_set: HashSet<_> = area.into_iter().collect();
+        assert_eq!(area_vec.len(), 25);
+        assert_eq!(area_set.len(), 25);
+
+        let area = Area::new(Coord::new(3, 7), 2, 2);
+        assert_eq!(
+            area.into_iter().collect::<Vec<_>>(),
+            [
+                Coord::new(3, 7),
+                Coord::new(4, 7),
+                Coord::new(3, 8),
+                Coord::new(4, 8)
+            ]
+        );
+    }
+
+    #[test]
+    fn small_scalars() {
+        let area = Area::<u8>::new(Coord { x: 0, y: 0 }, 20, 20);
+        assert_eq!(area.into_iter().len(), 400);
+        assert_eq!(area.into_iter().last(), Some(Coord { x: 19, y: 19 }));
+        let wide = Area::<u16>::new(Coord { x: 1, y: 2 }, 1000, 100);
+        assert_eq!(wide.into_iter().count(), 100_000);
+    }
+
+    #[test]
+    fn stepping() {
+        let area = Area::new(Coord::new(2, 0), 4, 5);
+        let cells: Vec<_> = area.iter_step(3, 2).collect();
+        assert_eq!(
+            cells,
+            [(2, 0), (5, 0), (2, 2), (5, 2), (2, 4), (5, 4)].map(Coord::from)
+        );
+        assert_eq!(area.iter_step(1, 1).len(), 20);
+        assert!(area.iter_step(1, 1).eq(area));
+        assert_eq!(area.iter_step(10, 10).collect::<Vec<_>>(), [area.corner]);
+        assert_eq!(Area::new(Coord::ZERO, 0, 3).iter_step(2, 2).len(), 0);
+    }
+
+    #[test]
+    fn stepping_extremes() {
+        let area = Area::new(Coord::new(5, 5), 3, 2);
+        let far: Vec<_> = area.iter_step(u32::MAX, 1).collect();
+        assert_eq!(far, [Coord::new(5, 5), Coord::new(5, 6)]);
+        assert_eq!(area.iter_step(u32::MAX, u32::MAX).len(), 1);
+
+        let full = Area::new(Coord::ZERO, u32::MAX, 1);
+        assert_eq!(full.iter_step(u32::MAX, 1).len(), 1);
+        assert_eq!(full.iter_step(u32::MAX - 1, 1).len(), 2);
+
+        // Small scalars can step over more cells than they can count
+        let small = Area::<u8>::new(Coord { x: 0, y: 0 }, 20, 20);
+        assert_eq!(small.iter_step(1, 1).len(), 400);
+        assert_eq!(small.iter_step(3, 3).len(), 49);
+        assert_eq!(small.iter_step(3, 3).last(), Some(Coord { x: 18, y: 18 }));
+        let edge = Area::<u8>::new(Coord { x: 0, y: 0 }, 255, 255);
+        assert_eq!(edge.iter_step(2, 255).last(), Some(Coord { x: 254, y: 0 }));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let area = Area::new(Coord::new(1, 2), 3, 2);
+        let rows: Vec<Vec<_>> = area
+            .rows()
+            .map(|row| row.map(|c| (c.x, c.y)).collect())
+            .collect();
+        assert_eq!(rows, [[(1, 2), (2, 2), (3, 2)], [(1, 3), (2, 3), (3, 3)]]);
+        let columns: Vec<Vec<_>> = area
+            .columns()
+            .map(|col| col.map(|c| (c.x, c.y)).collect())
+            .collect();
+        assert_eq!(
+            columns,
+            [[(1, 2), (1, 3)], [(2, 2), (2, 3)], [(3, 2), (3, 3)]]
+        );
+        assert_eq!(area.rows().len(), 2);
+        assert_eq!(area.columns().len(), 3);
+    }
+
+    #[test]
+    fn border_and_interior() {
+        let area = Area::new(Coord::new(2, 2), 4, 3);
+        let (border, interior) = area.border_and_interior();
+        assert_eq!(interior, Some(Area::new(Coord::new(3, 3), 2, 1)));
+        let border: HashSet<_> = border.into_iter().collect();
+        assert_eq!(border.len(), 10);
+        for coord in area {
+            assert_ne!(border.contains(&coord), interior.unwrap().contains(coord));
+        }
+
+        // Thin areas are all border, with no repeats
+        for (w, h) in [(1, 1), (1, 4), (4, 1), (2, 3), (0, 3)] {
+            let area = Area::new(Coord::new(5, 5), w, h);
+            let (border, interior) = area.border_and_interior();
+            assert_eq!(interior, None);
+            let iter = border.into_iter();
+            assert_eq!(iter.len(), (w * h) as usize);
+            let border: Vec<_> = iter.collect();
+            let mut all: Vec<_> = area.into_iter().collect();
+            let mut sorted = border.clone();
+            sorted.sort_by_key(|c| (c.y, c.x));
+            all.sort_by_key(|c| (c.y, c.x));
+            assert_eq!(sorted, all);
+        }
+    }
+
+    #[test]
+    fn contains() {
+        let area = Area::new(Coord::new(2, 3), 4, 2);
+        assert!(area.contains(Coord::new(2, 3)));
+        assert!(area.contains(Coord::new(5, 4)));
+        assert!(!area.contains(Coord::new(6, 4)));
+        assert!(!area.contains(Coord::new(5, 5)));
+        assert!(!area.contains(Coord::new(1, 3)));
+        assert!(!Area::new(Coord::ZERO, 0, 0).contains(Coord::ZERO));
+    }
+
+    #[test]
+    fn overlaps() {
+        let a = Area::new(Coord::new(0, 0), 3, 3);
+        let b = Area::new(Coord::new(2, 2), 3, 3);
+        let c = Area::new(Coord::new(3, 0), 3, 3);
+        assert!(a.overlaps(&b));
+        // Touching isn't overlapping
+        assert!(!a.overlaps(&c));
+        assert_eq!(a.intersect(&b), Some(Area::new(Coord::new(2, 2), 1, 1)));
+        assert_eq!(a.intersect(&c), None);
+        assert_eq!(a.union_bounds(&b), Area::new(Coord::new(0, 0), 5, 5));
+        assert_eq!(a.union_bounds(&c), Area::new(Coord::new(0, 0), 6, 3));
+    }
+
+    #[test]
+    fn resize() {
+        let a = Area::new(Coord::new(1, 4), 5, 3);
+        assert_eq!(a.expand(2), Area::new(Coord::new(0, 2), 8, 7));
+        assert_eq!(a.shrink(1), Some(Area::new(Coord::new(2, 5), 3, 1)));
+        assert_eq!(a.shrink(2), None);
+        assert_eq!(
+            a.corners(),
+            [
+                Coord::new(1, 4),
+                Coord::new(5, 4),
+                Coord::new(5, 6),
+                Coord::new(1, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn split() {
+        let a = Area::new(Coord::new(1, 4), 5, 3);
+        assert_eq!(
+            a.split_horizontal(1),
+            (
+                Area::new(Coord::new(1, 4), 5, 1),
+                Area::new(Coord::new(1, 5), 5, 2)
+            )
+        );
+        assert_eq!(
+            a.split_vertical(2),
+            (
+                Area::new(Coord::new(1, 4), 2, 3),
+                Area::new(Coord::new(3, 4), 3, 3)
+            )
+        );
+        let (left, right) = a.split_vertical(10);
+        assert_eq!(left, a);
+        assert!(right.is_empty());
+    }
+
+    #[test]
+    fn edges() {
+        let edges: Vec<_> = Edges::new(Coord::new(0, 0), 5, 4)
+            .into_iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                (0, 0),
+                (1, 0),
+                (2, 0),
+                (3, 0),
+                (4, 0),
+                (4, 1),
+                (4, 2),
+                (4, 3),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (0, 3),
+                (0, 2),
+                (0, 1)
+            ]
+        );
+
+        let edges: Vec<_> = Edges::new(Coord::new(7, 11), 3, 4)
+            .into_iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                (7, 11),
+                (8, 11),
+                (9, 11),
+                (9, 12),
+                (9, 13),
+                (9, 14),
+                (8, 14),
+                (7, 14),
+                (7, 13),
+                (7, 12)
+            ]
+        );
+
+        let edges: Vec<_> = Edges::new(Coord::new(0, 0), 2, 6)
+            .into_iter()
+            .map(|c| (c.x, c.y))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                (0, 0),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (1, 4),
+                (1, 5),
+                (0, 5),
+                (0, 4),
+                (0, 3),
+                (0, 2),
+                (0, 1)
+            ]
+        );
+    }
+}
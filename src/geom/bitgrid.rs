@@ -0,0 +1,256 @@
+use super::{Coord, UnsignedScalar};
+
+/// A grid of bits, packed 64 to a word.
+///
+/// Like a `Grid<bool>`, but an eighth of the size or less, and checking
+/// whether anything at all is set looks at whole words at a time.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawBitGrid"))]
+pub struct BitGrid {
+  width: u32,
+  height: u32,
+  /// Row-major. Bits past the end of the grid in the last word are always
+  /// clear.
+  words: Vec<u64>,
+}
+
+impl BitGrid {
+  /// Make a grid with every bit clear.
+  pub fn new(width: u32, height: u32) -> Self {
+    let bits = width as usize * height as usize;
+    Self {
+      width,
+      height,
+      words: vec![0; bits.div_ceil(64)],
+    }
+  }
+
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  /// Is the bit at `coord` set? Out of bounds bits are never set.
+  pub fn get<S: UnsignedScalar>(&self, coord: Coord<S>) -> bool {
+    match self.idx(coord) {
+      Some(idx) => self.words[idx / 64] & (1 << (idx % 64)) != 0,
+      None => false,
+    }
+  }
+
+  /// Set or clear the bit at `coord`, returning what it was before.
+  ///
+  /// Does nothing if `coord` is out of bounds.
+  pub fn set<S: UnsignedScalar>(&mut self, coord: Coord<S>, on: bool) -> bool {
+    let Some(idx) = self.idx(coord) else {
+      return false;
+    };
+    let word = &mut self.words[idx / 64];
+    let mask = 1 << (idx % 64);
+    let old = *word & mask != 0;
+    if on {
+      *word |= mask;
+    } else {
+      *word &= !mask;
+    }
+    old
+  }
+
+  /// Flip the bit at `coord`, returning what it is now.
+  ///
+  /// Does nothing and returns `false` if `coord` is out of bounds.
+  pub fn toggle<S: UnsignedScalar>(&mut self, coord: Coord<S>) -> bool {
+    let Some(idx) = self.idx(coord) else {
+      return false;
+    };
+    let word = &mut self.words[idx / 64];
+    *word ^= 1 << (idx % 64);
+    *word & (1 << (idx % 64)) != 0
+  }
+
+  /// Get the bits set in either grid.
+  ///
+  /// Panics if the grids aren't the same size.
+  pub fn union(&self, other: &BitGrid) -> BitGrid {
+    self.zip_words(other, |a, b| a | b)
+  }
+
+  /// Get the bits set in both grids.
+  ///
+  /// Panics if the grids aren't the same size.
+  pub fn intersect(&self, other: &BitGrid) -> BitGrid {
+    self.zip_words(other, |a, b| a & b)
+  }
+
+  /// Get the bits set in this grid but not `other`.
+  ///
+  /// Panics if the grids aren't the same size.
+  pub fn difference(&self, other: &BitGrid) -> BitGrid {
+    self.zip_words(other, |a, b| a & !b)
+  }
+
+  /// Get a grid with every bit flipped.
+  pub fn invert(&self) -> BitGrid {
+    let mut out = BitGrid {
+      width: self.width,
+      height: self.height,
+      words: self.words.iter().map(|word| !word).collect(),
+    };
+    out.clear_padding();
+    out
+  }
+
+  /// Borrow this as a function from coordinates to bits, for passing to
+  /// things that want a closure, like the line-of-sight functions.
+  ///
+  /// ```
+  /// # use aglet::{BitGrid, Coord};
+  /// let mut walls = BitGrid::new(5, 5);
+  /// walls.set(Coord::new(2, 2), true);
+  /// let opaque = walls.as_fn();
+  /// assert!(opaque(Coord::new(2, 2)));
+  /// assert!(!opaque(Coord::new(4, 4)));
+  /// assert!(!opaque(Coord::new(9, 9)));
+  /// ```
+  pub fn as_fn(&self) -> impl Fn(Coord) -> bool + Copy + '_ {
+    move |coord| self.get(coord)
+  }
+
+  /// Is any bit set at all?
+  pub fn any(&self) -> bool {
+    self.words.iter().any(|word| *word != 0)
+  }
+
+  /// The number of set bits.
+  pub fn count_ones(&self) -> usize {
+    self
+      .words
+      .iter()
+      .map(|word| word.count_ones() as usize)
+      .sum()
+  }
+
+  /// Iterate over the coordinates of every set bit, in row-major order.
+  ///
+  /// Runs of clear bits are skipped a word at a time.
+  pub fn iter_ones(&self) -> BitGridOnes<'_> {
+    BitGridOnes {
+      grid: self,
+      word_idx: 0,
+      word: self.words.first().copied().unwrap_or(0),
+    }
+  }
+
+  fn zip_words(
+    &self,
+    other: &BitGrid,
+    op: impl Fn(u64, u64) -> u64,
+  ) -> BitGrid {
+    assert_eq!(
+      (self.width, self.height),
+      (other.width, other.height),
+      "bit grids must be the same size"
+    );
+    BitGrid {
+      width: self.width,
+      height: self.height,
+      words: self
+        .words
+        .iter()
+        .zip(&other.words)
+        .map(|(a, b)| op(*a, *b))
+        .collect(),
+    }
+  }
+
+  /// Clear the bits past the end of the grid in the last word.
+  fn clear_padding(&mut self) {
+    let bits = self.width as usize * self.height as usize;
+    if let Some(last) = self.words.last_mut() {
+      if !bits.is_multiple_of(64) {
+        *last &= (1 << (bits % 64)) - 1;
+      }
+    }
+  }
+
+  fn idx<S: UnsignedScalar>(&self, coord: Coord<S>) -> Option<usize> {
+    let x = coord.x.to_usize()?;
+    let y = coord.y.to_usize()?;
+    if x >= self.width as usize || y >= self.height as usize {
+      None
+    } else {
+      Some(y * self.width as usize + x)
+    }
+  }
+}
+
+/// What a [`BitGrid`] deserializes from, before checking the words fit the
+/// size.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "BitGrid")]
+struct RawBitGrid {
+  width: u32,
+  height: u32,
+  words: Vec<u64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawBitGrid> for BitGrid {
+  type Error = String;
+
+  fn try_from(raw: RawBitGrid) -> Result<Self, Self::Error> {
+    let bits = (raw.width as usize)
+      .checked_mul(raw.height as usize)
+      .ok_or("bit grid is too big")?;
+    if raw.words.len() != bits.div_ceil(64) {
+      return Err(format!(
+        "expected {} words for a {}x{} bit grid, got {}",
+        bits.div_ceil(64),
+        raw.width,
+        raw.height,
+        raw.words.len()
+      ));
+    }
+    let padding = match (raw.words.last(), bits % 64) {
+      (Some(last), tail) if tail != 0 => last >> tail,
+      _ => 0,
+    };
+    if padding != 0 {
+      return Err("bits are set past the end of the bit grid".to_string());
+    }
+    Ok(BitGrid {
+      width: raw.width,
+      height: raw.height,
+      words: raw.words,
+    })
+  }
+}
+
+/// Iterator over the set bits in a [`BitGrid`].
+#[derive(Debug, Clone)]
+pub struct BitGridOnes<'a> {
+  grid: &'a BitGrid,
+  word_idx: usize,
+  /// What's left of the current word
+  word: u64,
+}
+
+impl Iterator for BitGridOnes<'_> {
+  type Item = Coord;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while self.word == 0 {
+      self.word_idx += 1;
+      self.word = *self.grid.words.get(self.word_idx)?;
+    }
+    let bit = self.word.trailing_zeros() as usize;
+    self.word &= self.word - 1;
+    let idx = (self.word_idx * 64 + bit) as u32;
+    Some(Coord::new(idx % self.grid.width, idx / self.grid.width))
+  }
+}
@@ -0,0 +1,751 @@
+use super::{
+    Area, AreaIter, Direction4, Direction8, Direction9, Neighbors, Rotation, Scalar, SignedScalar,
+    UnsignedScalar,
+};
+
+use std::{
+    convert::{TryFrom, TryInto},
+    fmt::Display,
+    num::{ParseIntError, TryFromIntError},
+    ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Unsigned-int coordinates.
+///
+/// These are generic over the [`UnsignedScalar`] used, defaulting to `u32`.
+/// Just like `HashMap::new` only works with the default hasher,
+/// [`Coord::new`] and [`Coord::ZERO`] only make `Coord<u32>`s;
+/// for other scalars, use a struct literal or convert from a tuple.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coord<T = u32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl Coord {
+    pub const ZERO: Coord = Coord::new(0, 0);
+
+    /// Make a new coord.
+    pub const fn new(x: u32, y: u32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: UnsignedScalar> Coord<T> {
+    /// Get this as an index into an array representing a 2d array.
+    ///
+    /// (AKA, `y * width + x`.)
+    pub fn to_2d_idx(self, width: T) -> T {
+        // what did you think i was kidding or something
+        self.y * width + self.x
+    }
+
+    /// Convert this into a [`CoordVec`].
+    pub fn to_icoord(self) -> CoordVec<T::Signed> {
+        self.into()
+    }
+
+    /// Get a list of this coordinate's orthagonal neighbors.
+    /// They are given in clockwise order starting with the neighbor to the north,
+    /// as if each of [`Direction4::DIRECTIONS`] had been added to them.
+    ///
+    /// If a neighbor is out of bounds, it is skipped in the output.
+    ///
+    /// There may be 2, 3, or 4 neighbors:
+    /// - 2 if this is at `(0, 0)`
+    /// - 3 if this is on an edge (`x` or `y` are 0)
+    /// - 4 otherwise.
+    ///
+    /// [`Direction4::DIRECTIONS`]: super::Direction4::DIRECTIONS
+    ///
+    /// This allocates; see [`Self::neighbors4_iter`] for a version that doesn't.
+    pub fn neighbors4(self) -> Vec<Coord<T>> {
+        self.neighbors4_iter().collect()
+    }
+
+    /// Get a list of this coordinate's orthagonal and diagonal neighbors.
+    /// They are given in clockwise order starting with the neighbor to the north,
+    /// as if each of [`Direction8::DIRECTIONS`] had been added to them.
+    ///
+    /// If a neighbor is out of bounds, it is skipped in the output.
+    ///
+    /// There may be 3, 5, or 8 neighbors:
+    /// - 3 if this is at `(0, 0)`
+    /// - 5 if this is on an edge (`x` or `y` are 0)
+    /// - 8 otherwise.
+    ///
+    /// [`Direction8::DIRECTIONS`]: super::Direction8::DIRECTIONS
+    ///
+    /// This allocates; see [`Self::neighbors8_iter`] for a version that doesn't.
+    pub fn neighbors8(self) -> Vec<Coord<T>> {
+        self.neighbors8_iter().collect()
+    }
+
+    /// Iterate over this coordinate's orthagonal neighbors without allocating,
+    /// in the same order as [`Self::neighbors4`].
+    pub fn neighbors4_iter(self) -> Neighbors<T> {
+        Neighbors::new4(self, None)
+    }
+
+    /// Iterate over this coordinate's orthagonal and diagonal neighbors without
+    /// allocating, in the same order as [`Self::neighbors8`].
+    pub fn neighbors8_iter(self) -> Neighbors<T> {
+        Neighbors::new8(self, None)
+    }
+
+    /// Like [`Self::neighbors4_iter`], but skips neighbors outside of `area`.
+    pub fn neighbors4_within(self, area: Area<T>) -> Neighbors<T> {
+        Neighbors::new4(self, Some(area))
+    }
+
+    /// Like [`Self::neighbors8_iter`], but skips neighbors outside of `area`.
+    pub fn neighbors8_within(self, area: Area<T>) -> Neighbors<T> {
+        Neighbors::new8(self, Some(area))
+    }
+
+    /// Like [`Self::neighbors4_iter`], but skips neighbors with `x >= width`
+    /// or `y >= height`.
+    pub fn neighbors4_bounded(self, width: T, height: T) -> Neighbors<T> {
+        self.neighbors4_within(Area::new(
+            Coord {
+                x: T::ZERO,
+                y: T::ZERO,
+            },
+            width,
+            height,
+        ))
+    }
+
+    /// Like [`Self::neighbors8_iter`], but skips neighbors with `x >= width`
+    /// or `y >= height`.
+    pub fn neighbors8_bounded(self, width: T, height: T) -> Neighbors<T> {
+        self.neighbors8_within(Area::new(
+            Coord {
+                x: T::ZERO,
+                y: T::ZERO,
+            },
+            width,
+            height,
+        ))
+    }
+
+    pub fn area(self, width: T, height: T) -> Area<T> {
+        Area::new(self, width, height)
+    }
+
+    /// Iterate over the rectangle with this and `other` as opposite corners,
+    /// including both of them.
+    ///
+    /// The corners can be in either order; either way, this goes in row-major
+    /// order from the top-left.
+    ///
+    /// ```
+    /// # use aglet::Coord;
+    /// let cells: Vec<_> = Coord::new(2, 1).to(Coord::new(1, 2)).collect();
+    /// assert_eq!(cells, [(1, 1), (2, 1), (1, 2), (2, 2)].map(Coord::from));
+    /// assert_eq!(Coord::new(3, 3).to(Coord::new(3, 3)).len(), 1);
+    /// ```
+    pub fn to(self, other: Self) -> AreaIter<T> {
+        let corner = Coord {
+            x: self.x.min(other.x),
+            y: self.y.min(other.y),
+        };
+        let diff = self.abs_diff(other);
+        Area::new(corner, diff.x + T::ONE, diff.y + T::ONE).into_iter()
+    }
+
+    pub fn offset4(self, offset: Direction4) -> Option<Self> {
+        (self.to_icoord() + offset.deltas_as()).to_coord()
+    }
+
+    pub fn offset8(self, offset: Direction8) -> Option<Self> {
+        (self.to_icoord() + offset.deltas_as()).to_coord()
+    }
+
+    pub fn offset9(self, offset: Direction9) -> Option<Self> {
+        (self.to_icoord() + offset.deltas_as()).to_coord()
+    }
+
+    /// Get the component-wise absolute difference between this and `other`.
+    ///
+    /// This is what `abs(self - other)` would be, if that didn't underflow.
+    pub fn abs_diff(self, other: Self) -> Self {
+        Self {
+            x: self.x.abs_diff(other.x),
+            y: self.y.abs_diff(other.y),
+        }
+    }
+
+    /// Get the taxicab distance between this and `other`, `|dx| + |dy|`.
+    ///
+    /// Saturates at the max value of `T` instead of overflowing.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x.saturating_add(d.y)
+    }
+
+    /// Get the chessboard distance between this and `other`, `max(|dx|, |dy|)`.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x.max(d.y)
+    }
+
+    /// Get the square of the straight-line distance between this and `other`.
+    ///
+    /// Saturates at the max value of `T` instead of overflowing.
+    pub fn euclidean_distance_sq(self, other: Self) -> T {
+        let d = self.abs_diff(other);
+        d.x.saturating_mul(d.x)
+            .saturating_add(d.y.saturating_mul(d.y))
+    }
+
+    /// Get the straight-line distance of this from the origin.
+    pub fn magnitude(self) -> f64 {
+        self.x.to_f64().hypot(self.y.to_f64())
+    }
+
+    /// Get the dot product of this and `other`.
+    ///
+    /// Saturates at the max value of `T` instead of overflowing.
+    pub fn dot(self, other: Self) -> T {
+        self.x
+            .saturating_mul(other.x)
+            .saturating_add(self.y.saturating_mul(other.y))
+    }
+}
+
+impl<T: UnsignedScalar> From<(T, T)> for Coord<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: UnsignedScalar> From<Coord<T>> for (T, T) {
+    fn from(value: Coord<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T: UnsignedScalar> Add for Coord<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> AddAssign for Coord<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: UnsignedScalar> Sub for Coord<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> SubAssign for Coord<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: UnsignedScalar> Mul<T> for Coord<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> MulAssign<T> for Coord<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: UnsignedScalar> Mul<Coord<T>> for Coord<T> {
+    type Output = Self;
+    fn mul(self, rhs: Coord<T>) -> Self::Output {
+        Self {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> MulAssign<Coord<T>> for Coord<T> {
+    fn mul_assign(&mut self, rhs: Coord<T>) {
+        self.x *= rhs.x;
+        self.y *= rhs.y;
+    }
+}
+
+/// Try to convert a [`CoordVec`] to a [`Coord`].
+/// Will return Error if the [`CoordVec`] has any negatives in it.
+impl<S: SignedScalar> TryFrom<CoordVec<S>> for Coord<S::Unsigned> {
+    type Error = TryFromIntError;
+    fn try_from(value: CoordVec<S>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            x: value.x.to_unsigned()?,
+            y: value.y.to_unsigned()?,
+        })
+    }
+}
+
+/// Prints as `(x, y)`.
+///
+/// Any width, fill, or alignment flags are applied to each component, so
+/// `format!("{:>3}", coord)` gives `(  3,   4)`.
+/// The alternate flag (`{:#}`) prints the compact form `x,y` instead.
+impl<T: Display> Display for Coord<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_components(&self.x, &self.y, f)
+    }
+}
+
+/// Parses `(x, y)`, `x,y`, or `x y`.
+impl<T: UnsignedScalar> FromStr for Coord<T> {
+    type Err = ParseCoordError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = parse_components(s)?;
+        Ok(Self { x, y })
+    }
+}
+
+/// Error returned when parsing a [`Coord`] or [`CoordVec`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCoordError {
+    /// The string didn't have exactly two components.
+    WrongComponentCount,
+    /// The string had an opening paren and no closing paren, or vice versa.
+    UnbalancedParens,
+    /// One of the components wasn't a valid integer.
+    BadComponent(ParseIntError),
+}
+
+impl Display for ParseCoordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseCoordError::WrongComponentCount => {
+                write!(f, "expected exactly two components")
+            }
+            ParseCoordError::UnbalancedParens => write!(f, "unbalanced parentheses"),
+            ParseCoordError::BadComponent(err) => write!(f, "bad component: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ParseCoordError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseCoordError::BadComponent(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for ParseCoordError {
+    fn from(err: ParseIntError) -> Self {
+        ParseCoordError::BadComponent(err)
+    }
+}
+
+fn fmt_components<T: Display>(x: &T, y: &T, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if f.alternate() {
+        x.fmt(f)?;
+        f.write_str(",")?;
+        y.fmt(f)
+    } else {
+        f.write_str("(")?;
+        x.fmt(f)?;
+        f.write_str(", ")?;
+        y.fmt(f)?;
+        f.write_str(")")
+    }
+}
+
+fn parse_components<T: Scalar>(s: &str) -> Result<(T, T), ParseCoordError> {
+    let s = s.trim();
+    let s = match (s.strip_prefix('('), s.ends_with(')')) {
+        (Some(rest), true) => &rest[..rest.len() - 1],
+        (None, false) => s,
+        _ => return Err(ParseCoordError::UnbalancedParens),
+    };
+
+    let mut parts: Vec<&str> = if s.contains(',') {
+        s.split(',').map(str::trim).collect()
+    } else {
+        s.split_whitespace().collect()
+    };
+    if parts.len() != 2 {
+        return Err(ParseCoordError::WrongComponentCount);
+    }
+    let y = parts.pop().unwrap().parse()?;
+    let x = parts.pop().unwrap().parse()?;
+    Ok((x, y))
+}
+
+/// Signed-int coordinates.
+///
+/// These are generic over the [`SignedScalar`] used, defaulting to `i32`.
+/// Like with [`Coord`], [`CoordVec::new`] only makes `CoordVec<i32>`s.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoordVec<T = i32> {
+    pub x: T,
+    pub y: T,
+}
+
+impl CoordVec {
+    /// Create a new CoordVec.
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: SignedScalar> CoordVec<T> {
+    /// Return the quadrant this coordinate is in.
+    ///
+    /// - 1: +X, +Y
+    /// - 2: -X, +Y
+    /// - 3: -X, -Y
+    /// - 4: +X, -Y
+    ///
+    /// Zeroes are treated as positive.
+    pub fn quadrant(self) -> u32 {
+        match (self.x >= T::ZERO, self.y >= T::ZERO) {
+            (true, true) => 1,
+            (false, true) => 2,
+            (false, false) => 3,
+            (true, false) => 4,
+        }
+    }
+
+    /// Try to convert this to a Coord.
+    /// Returns `None` in case any part is negative.
+    ///
+    /// See also [`Self::to_coord_clamped`] and [`Self::to_coord_wrapping`].
+    pub fn to_coord(self) -> Option<Coord<T::Unsigned>> {
+        self.try_into().ok()
+    }
+
+    /// Convert this to a Coord, moving any negative parts up to zero.
+    ///
+    /// ```
+    /// # use aglet::{Coord, CoordVec};
+    /// assert_eq!(CoordVec::new(-3, 4).to_coord_clamped(), Coord::new(0, 4));
+    /// ```
+    pub fn to_coord_clamped(self) -> Coord<T::Unsigned> {
+        let clamp = |n: T| n.max(T::ZERO).to_unsigned().unwrap();
+        Coord {
+            x: clamp(self.x),
+            y: clamp(self.y),
+        }
+    }
+
+    /// Convert this to a Coord within `bounds`, wrapping around the edges
+    /// like on a torus. `bounds` is the width and height, so the result is
+    /// always less than it.
+    ///
+    /// Panics if either part of `bounds` is zero.
+    ///
+    /// ```
+    /// # use aglet::{Coord, CoordVec};
+    /// let bounds = Coord::new(10, 5);
+    /// assert_eq!(CoordVec::new(-1, 7).to_coord_wrapping(bounds), Coord::new(9, 2));
+    /// assert_eq!(CoordVec::new(-10, -5).to_coord_wrapping(bounds), Coord::ZERO);
+    /// ```
+    pub fn to_coord_wrapping(self, bounds: Coord<T::Unsigned>) -> Coord<T::Unsigned> {
+        let wrap = |n: T, size: T::Unsigned| match n.to_unsigned() {
+            Ok(n) => n % size,
+            // Go through -(n + 1) so that `MIN` doesn't overflow
+            Err(_) => size - T::Unsigned::ONE - (-(n + T::ONE)).to_unsigned().unwrap() % size,
+        };
+        Coord {
+            x: wrap(self.x, bounds.x),
+            y: wrap(self.y, bounds.y),
+        }
+    }
+
+    /// Get a list of this coordinate's orthagonal neighbors.
+    /// They are given in clockwise order starting with the neighbor to the north,
+    /// as if each of [`Direction4::DIRECTIONS`] had been added to them.
+    ///
+    /// [`Direction4::DIRECTIONS`]: super::Direction4::DIRECTIONS
+    pub fn neighbors4(self) -> [CoordVec<T>; 4] {
+        [
+            self + Direction4::North,
+            self + Direction4::East,
+            self + Direction4::South,
+            self + Direction4::West,
+        ]
+    }
+
+    /// Get a list of this coordinate's orthagonal and diagonal neighbors.
+    /// They are given in clockwise order starting with the neighbor to the north,
+    /// as if each of [`Direction8::DIRECTIONS`] had been added to them.
+    ///
+    /// [`Direction8::DIRECTIONS`]: super::Direction8::DIRECTIONS
+    pub fn neighbors8(self) -> [CoordVec<T>; 8] {
+        [
+            self + Direction8::North,
+            self + Direction8::NorthEast,
+            self + Direction8::East,
+            self + Direction8::SouthEast,
+            self + Direction8::South,
+            self + Direction8::SouthWest,
+            self + Direction8::West,
+            self + Direction8::NorthWest,
+        ]
+    }
+
+    /// Turn this into the closest Direction9 it is pointing in.
+    ///
+    /// This uses the convention that north is positive Y, which is flipped
+    /// from [`Direction8::deltas`] and friends, where north is `(0, -1)`.
+    pub fn point9(self) -> Direction9 {
+        if self.x == T::ZERO && self.y == T::ZERO {
+            return Direction9::Center;
+        }
+        // Flip Y to get from north-is-positive to the usual screen convention
+        let angle = (-self.y.to_f64() as f32).atan2(self.x.to_f64() as f32);
+        Direction8::from_radians(angle).into()
+    }
+
+    /// Rotate this vector around the origin by the given amount.
+    pub fn rotate(self, rot: Rotation) -> Self {
+        self.rotate_by(rot.steps_clockwise())
+    }
+
+    /// Rotate this vector around the origin by this many quarter turns clockwise.
+    /// Negative numbers go counter-clockwise.
+    pub fn rotate_by(self, steps_clockwise: i32) -> Self {
+        // Remember +Y is down, so clockwise is (x, y) -> (-y, x)
+        match steps_clockwise.rem_euclid(4) {
+            0 => self,
+            1 => Self {
+                x: -self.y,
+                y: self.x,
+            },
+            2 => Self {
+                x: -self.x,
+                y: -self.y,
+            },
+            _ => Self {
+                x: self.y,
+                y: -self.x,
+            },
+        }
+    }
+
+    /// Treat this as an offset relative to something facing the given direction,
+    /// and convert it into world space.
+    ///
+    /// The local offset is written as if facing north: `-Y` is forward and `+X`
+    /// is to the right. So "2 ahead and 1 to the right" is `CoordVec::new(1, -2)`.
+    ///
+    /// This is also available as `local * facing`.
+    pub fn in_basis(self, facing: Direction4) -> Self {
+        self.rotate_by(facing.ordinal() as i32)
+    }
+
+    /// The inverse of [`Self::in_basis`]: convert a world-space offset into one
+    /// relative to something facing the given direction.
+    pub fn from_basis(self, facing: Direction4) -> Self {
+        self.rotate_by(-(facing.ordinal() as i32))
+    }
+
+    /// Get the component-wise absolute value of this.
+    ///
+    /// Saturates at the max value of `T`, so `T::MIN` turns into `T::MAX`.
+    pub fn abs(self) -> Self {
+        Self {
+            x: self.x.saturating_abs(),
+            y: self.y.saturating_abs(),
+        }
+    }
+
+    /// Get the taxicab distance between this and `other`, `|dx| + |dy|`.
+    ///
+    /// This is unsigned, so the distance between any two points along an axis
+    /// always fits; the sum saturates instead of overflowing.
+    pub fn manhattan_distance(self, other: Self) -> T::Unsigned {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        dx.saturating_add(dy)
+    }
+
+    /// Get the chessboard distance between this and `other`, `max(|dx|, |dy|)`.
+    pub fn chebyshev_distance(self, other: Self) -> T::Unsigned {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        dx.max(dy)
+    }
+
+    /// Get the square of the straight-line distance between this and `other`.
+    ///
+    /// Saturates at the max value of `T::Unsigned` instead of overflowing.
+    pub fn euclidean_distance_sq(self, other: Self) -> T::Unsigned {
+        let dx = self.x.abs_diff(other.x);
+        let dy = self.y.abs_diff(other.y);
+        dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy))
+    }
+
+    /// Get the length of this vector.
+    pub fn magnitude(self) -> f64 {
+        self.x.to_f64().hypot(self.y.to_f64())
+    }
+
+    /// Get the dot product of this and `other`.
+    ///
+    /// Saturates instead of overflowing.
+    pub fn dot(self, other: Self) -> T {
+        self.x
+            .saturating_mul(other.x)
+            .saturating_add(self.y.saturating_mul(other.y))
+    }
+}
+
+impl<T: SignedScalar> From<(T, T)> for CoordVec<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: SignedScalar> From<CoordVec<T>> for (T, T) {
+    fn from(value: CoordVec<T>) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl<T: SignedScalar> Add for CoordVec<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl<T: SignedScalar> AddAssign for CoordVec<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SignedScalar> Sub for CoordVec<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T: SignedScalar> SubAssign for CoordVec<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: SignedScalar> Add<Direction4> for CoordVec<T> {
+    type Output = Self;
+    fn add(self, rhs: Direction4) -> Self::Output {
+        self + rhs.deltas_as()
+    }
+}
+
+impl<T: SignedScalar> AddAssign<Direction4> for CoordVec<T> {
+    fn add_assign(&mut self, rhs: Direction4) {
+        *self += rhs.deltas_as();
+    }
+}
+
+impl<T: SignedScalar> Add<Direction8> for CoordVec<T> {
+    type Output = Self;
+    fn add(self, rhs: Direction8) -> Self::Output {
+        self + rhs.deltas_as()
+    }
+}
+
+impl<T: SignedScalar> AddAssign<Direction8> for CoordVec<T> {
+    fn add_assign(&mut self, rhs: Direction8) {
+        *self += rhs.deltas_as();
+    }
+}
+
+/// Convert a local offset into world space; see [`CoordVec::in_basis`].
+impl<T: SignedScalar> Mul<Direction4> for CoordVec<T> {
+    type Output = Self;
+    fn mul(self, rhs: Direction4) -> Self::Output {
+        self.in_basis(rhs)
+    }
+}
+
+impl<T: SignedScalar> MulAssign<Direction4> for CoordVec<T> {
+    fn mul_assign(&mut self, rhs: Direction4) {
+        *self = self.in_basis(rhs);
+    }
+}
+
+impl<T: SignedScalar> Mul<T> for CoordVec<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T: SignedScalar> MulAssign<T> for CoordVec<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x *= rhs;
+        self.y *= rhs;
+    }
+}
+
+impl<T: UnsignedScalar> From<Coord<T>> for CoordVec<T::Signed> {
+    fn from(value: Coord<T>) -> Self {
+        Self {
+            x: value.x.as_signed(),
+            y: value.y.as_signed(),
+        }
+    }
+}
+
+/// Prints as `(x, y)`, with the same formatting flags as [`Coord`].
+impl<T: Display> Display for CoordVec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt_components(&self.x, &self.y, f)
+    }
+}
+
+/// Parses `(x, y)`, `x,y`, or `x y`.
+impl<T: SignedScalar> FromStr for CoordVec<T> {
+    type Err = ParseCoordError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x, y) = parse_components(s)?;
+        Ok(Self { x, y })
+    }
+}
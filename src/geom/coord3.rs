@@ -0,0 +1,340 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use super::{Coord, CoordVec, Direction4};
+
+/// Unsigned-int coordinates in 3d, for stacks of layers like z-levels.
+///
+/// `x` and `y` mean the same as in a [`Coord`], and `z` picks the layer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coord3 {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+impl Coord3 {
+    pub const ZERO: Coord3 = Coord3::new(0, 0, 0);
+
+    pub const fn new(x: u32, y: u32, z: u32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Put a 2d coordinate on layer `z`.
+    pub const fn from_xy(xy: Coord, z: u32) -> Self {
+        Self::new(xy.x, xy.y, z)
+    }
+
+    /// Get the position within this coordinate's layer.
+    pub const fn xy(self) -> Coord {
+        Coord::new(self.x, self.y)
+    }
+
+    pub fn to_icoord(self) -> CoordVec3 {
+        self.into()
+    }
+
+    /// Step one cell in the given direction, or `None` if that would go
+    /// below zero.
+    pub fn offset6(self, dir: Direction6) -> Option<Self> {
+        (self.to_icoord() + dir).to_coord()
+    }
+
+    /// Iterate over the 6 face-adjacent neighbors, in the order of
+    /// [`Direction6::DIRECTIONS`], skipping any below zero.
+    pub fn neighbors6(self) -> impl Iterator<Item = Coord3> {
+        Direction6::DIRECTIONS
+            .into_iter()
+            .filter_map(move |dir| self.offset6(dir))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> u32 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl From<(u32, u32, u32)> for Coord3 {
+    fn from((x, y, z): (u32, u32, u32)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Coord3> for (u32, u32, u32) {
+    fn from(c: Coord3) -> Self {
+        (c.x, c.y, c.z)
+    }
+}
+
+impl Add for Coord3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Coord3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Coord3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Coord3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Prints as `(x, y, z)`.
+impl Display for Coord3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// Signed-int coordinates in 3d; the 3d version of [`CoordVec`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoordVec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl CoordVec3 {
+    pub const ZERO: CoordVec3 = CoordVec3::new(0, 0, 0);
+
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Put a 2d offset on layer `z`.
+    pub const fn from_xy(xy: CoordVec, z: i32) -> Self {
+        Self::new(xy.x, xy.y, z)
+    }
+
+    pub const fn xy(self) -> CoordVec {
+        CoordVec {
+            x: self.x,
+            y: self.y,
+        }
+    }
+
+    /// Convert to unsigned coordinates, or `None` if any component is
+    /// negative.
+    pub fn to_coord(self) -> Option<Coord3> {
+        Some(Coord3::new(
+            self.x.try_into().ok()?,
+            self.y.try_into().ok()?,
+            self.z.try_into().ok()?,
+        ))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> u32 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+}
+
+impl From<(i32, i32, i32)> for CoordVec3 {
+    fn from((x, y, z): (i32, i32, i32)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<CoordVec3> for (i32, i32, i32) {
+    fn from(c: CoordVec3) -> Self {
+        (c.x, c.y, c.z)
+    }
+}
+
+impl From<Coord3> for CoordVec3 {
+    fn from(c: Coord3) -> Self {
+        Self::new(c.x as i32, c.y as i32, c.z as i32)
+    }
+}
+
+impl Add for CoordVec3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for CoordVec3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for CoordVec3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for CoordVec3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<Direction6> for CoordVec3 {
+    type Output = Self;
+
+    fn add(self, rhs: Direction6) -> Self {
+        self + rhs.deltas()
+    }
+}
+
+impl AddAssign<Direction6> for CoordVec3 {
+    fn add_assign(&mut self, rhs: Direction6) {
+        *self = *self + rhs;
+    }
+}
+
+/// Prints as `(x, y, z)`.
+impl Display for CoordVec3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// The six directions along the axes in 3d: one step to each face of a cube.
+///
+/// `MinusY` and `PlusY` line up with [`Direction4::North`] and
+/// [`Direction4::South`], like in 2d.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction6 {
+    PlusX,
+    MinusX,
+    PlusY,
+    MinusY,
+    PlusZ,
+    MinusZ,
+}
+
+impl Direction6 {
+    /// All the directions in order.
+    pub const DIRECTIONS: [Direction6; 6] = [
+        Direction6::PlusX,
+        Direction6::MinusX,
+        Direction6::PlusY,
+        Direction6::MinusY,
+        Direction6::PlusZ,
+        Direction6::MinusZ,
+    ];
+
+    /// Get the "index" of this direction, in the same index as in
+    /// [`Self::DIRECTIONS`].
+    pub fn ordinal(self) -> usize {
+        self as usize
+    }
+
+    /// Get the direction pointing the opposite way.
+    pub fn flip(self) -> Self {
+        Self::DIRECTIONS[self.ordinal() ^ 1]
+    }
+
+    /// Get the deltas a step in this direction would result in.
+    pub fn deltas(self) -> CoordVec3 {
+        let (x, y, z) = match self {
+            Direction6::PlusX => (1, 0, 0),
+            Direction6::MinusX => (-1, 0, 0),
+            Direction6::PlusY => (0, 1, 0),
+            Direction6::MinusY => (0, -1, 0),
+            Direction6::PlusZ => (0, 0, 1),
+            Direction6::MinusZ => (0, 0, -1),
+        };
+        CoordVec3::new(x, y, z)
+    }
+
+    /// Does this point up or down between layers?
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction6::PlusZ | Direction6::MinusZ)
+    }
+
+    /// Get the matching 2d direction, or `None` for the two vertical ones.
+    pub fn to_direction4(self) -> Option<Direction4> {
+        match self {
+            Direction6::PlusX => Some(Direction4::East),
+            Direction6::MinusX => Some(Direction4::West),
+            Direction6::PlusY => Some(Direction4::South),
+            Direction6::MinusY => Some(Direction4::North),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction4> for Direction6 {
+    fn from(dir: Direction4) -> Self {
+        match dir {
+            Direction4::North => Direction6::MinusY,
+            Direction4::East => Direction6::PlusX,
+            Direction4::South => Direction6::PlusY,
+            Direction4::West => Direction6::MinusX,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Coord, Coord3, CoordVec3, Direction4, Direction6};
+
+    #[test]
+    fn directions() {
+        for dir in Direction6::DIRECTIONS {
+            assert_eq!(dir.flip().flip(), dir);
+            assert_eq!(dir.deltas() + dir.flip().deltas(), CoordVec3::ZERO);
+            if let Some(d4) = dir.to_direction4() {
+                assert_eq!(Direction6::from(d4), dir);
+                assert_eq!(d4.deltas(), dir.deltas().xy());
+            }
+        }
+        assert_eq!(Direction6::PlusZ.flip(), Direction6::MinusZ);
+        assert_eq!(
+            Direction6::from(Direction4::North).deltas(),
+            CoordVec3::new(0, -1, 0)
+        );
+    }
+
+    #[test]
+    fn coords() {
+        let c = Coord3::from_xy(Coord::new(2, 0), 1);
+        assert_eq!(c.offset6(Direction6::MinusY), None);
+        assert_eq!(c.offset6(Direction6::MinusZ), Some(Coord3::new(2, 0, 0)));
+        assert_eq!(c.neighbors6().count(), 5);
+        assert_eq!(c.manhattan_distance(Coord3::new(0, 3, 5)), 9);
+        assert_eq!(c.chebyshev_distance(Coord3::new(0, 3, 5)), 4);
+        assert_eq!(CoordVec3::new(1, -2, 3).to_coord(), None);
+        assert_eq!(c.to_string(), "(2, 0, 1)");
+    }
+}
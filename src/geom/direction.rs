@@ -0,0 +1,691 @@
+use super::{CoordVec, SignedScalar, Transform};
+use enumflags2::{bitflags, BitFlag, BitFlags};
+use std::fmt::Display;
+
+/// Four-way directions.
+///
+/// These start at North and increment clockwise.
+///
+/// You can NOT convert them to numbers with just `as` anymore,
+/// use [`Self::ordinal`].
+#[bitflags]
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction4 {
+  North,
+  East,
+  South,
+  West,
+}
+
+impl Direction4 {
+  /// All the directions in order.
+  /// This is used internally for rotations and flips.
+  /// I made it public just in case it's helpful for you the programmer.
+  pub const DIRECTIONS: [Direction4; 4] = [
+    Direction4::North,
+    Direction4::East,
+    Direction4::South,
+    Direction4::West,
+  ];
+
+  /// Get the "index" of this direction,
+  /// in the same index as in [`Self::DIRECTIONS`].
+  pub fn ordinal(self) -> usize {
+    match self {
+      Direction4::North => 0,
+      Direction4::East => 1,
+      Direction4::South => 2,
+      Direction4::West => 3,
+    }
+  }
+
+  /// Rotate this by the given amount.
+  pub fn rotate(self, rot: Rotation) -> Self {
+    self.rotate_by(rot.steps_clockwise())
+  }
+
+  /// Get this direction, rotated by this many steps clockwise.
+  /// Negative numbers go counter-clockwise.
+  pub fn rotate_by(self, steps_clockwise: i32) -> Self {
+    let idx = self.ordinal() as i32;
+    let new_idx = ((idx + steps_clockwise)
+      .rem_euclid(Self::DIRECTIONS.len() as i32)) as usize;
+    Self::DIRECTIONS[new_idx]
+  }
+
+  /// Flip this direction.
+  pub fn flip(self) -> Self {
+    self.rotate_by(2)
+  }
+
+  /// Get this direction in radians.
+  ///
+  /// This uses trigonometric + graphical standard, where:
+  /// - 0 radians is to the right
+  /// - Positive radians increment *clockwise*. NOTE: this is opposite from normal trig,
+  ///   but makes sense in computer graphics where +Y is downwards.
+  ///
+  /// If you need it in degrees just call `.to_degrees` on the result.
+  pub fn radians(self) -> f32 {
+    ordinal_to_radians(self.ordinal(), 4)
+  }
+
+  /// Get the direction closest to the given angle, using the same convention
+  /// as [`Self::radians`].
+  pub fn from_radians(angle: f32) -> Self {
+    Self::DIRECTIONS[radians_to_ordinal(angle, 4)]
+  }
+
+  /// Get the deltas a step in this direction would result in, as a CoordVec.
+  pub fn deltas(self) -> CoordVec {
+    self.deltas_as()
+  }
+
+  /// Get the deltas a step in this direction would result in,
+  /// as a CoordVec of any scalar type.
+  pub fn deltas_as<T: SignedScalar>(self) -> CoordVec<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    let (x, y) = match self {
+      Direction4::North => (zero, -one),
+      Direction4::East => (one, zero),
+      Direction4::South => (zero, one),
+      Direction4::West => (-one, zero),
+    };
+    CoordVec { x, y }
+  }
+
+  /// See if this direction points horizontally (ie, is `East` or `West`).
+  pub fn is_horizontal(self) -> bool {
+    matches!(self, Direction4::East | Direction4::West)
+  }
+
+  /// See if this direction points vertically (ie, is `North` or `South`).
+  pub fn is_vertical(self) -> bool {
+    matches!(self, Direction4::North | Direction4::South)
+  }
+}
+
+/// Eight-way directions.
+///
+/// These start at North and increment counter-clockwise,
+/// so you can convert them to integers with `as` and use them
+/// in rotational calculations if you need.
+///
+/// You can NOT convert them to numbers with just `as` anymore,
+/// use [`Self::ordinal`].
+#[bitflags]
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction8 {
+  North,
+  NorthEast,
+  East,
+  SouthEast,
+  South,
+  SouthWest,
+  West,
+  NorthWest,
+}
+
+impl Direction8 {
+  /// All the directions in order.
+  /// This is used internally for rotations and flips.
+  /// I made it public just in case it's helpful for you the programmer.
+  pub const DIRECTIONS: [Direction8; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+  ];
+
+  /// Get the "index" of this direction,
+  /// in the same index as in [`Self::DIRECTIONS`].
+  pub fn ordinal(self) -> usize {
+    match self {
+      Direction8::North => 0,
+      Direction8::NorthEast => 1,
+      Direction8::East => 2,
+      Direction8::SouthEast => 3,
+      Direction8::South => 4,
+      Direction8::SouthWest => 5,
+      Direction8::West => 6,
+      Direction8::NorthWest => 7,
+    }
+  }
+
+  /// Rotate this by the given amount.
+  pub fn rotate(self, rot: Rotation) -> Self {
+    self.rotate_by(rot.steps_clockwise())
+  }
+
+  /// Get this direction, rotated by this many steps clockwise.
+  /// Negative numbers go counter-clockwise.
+  pub fn rotate_by(self, steps_clockwise: i32) -> Self {
+    let idx = self.ordinal() as i32;
+    let new_idx = ((idx + steps_clockwise)
+      .rem_euclid(Self::DIRECTIONS.len() as i32)) as usize;
+    Self::DIRECTIONS[new_idx]
+  }
+
+  /// Flip this direction.
+  pub fn flip(self) -> Self {
+    self.rotate_by(4)
+  }
+
+  /// Get this direction in radians.
+  ///
+  /// This uses trigonometric + graphical standard, where:
+  /// - 0 radians is to the right
+  /// - Positive radians increment *clockwise*. NOTE: this is opposite from normal trig,
+  ///   but makes sense in computer graphics where +Y is downwards.
+  ///
+  /// If you need it in degrees just call `.to_degrees` on the result.
+  pub fn radians(self) -> f32 {
+    ordinal_to_radians(self.ordinal(), 8)
+  }
+
+  /// Get the direction closest to the given angle, using the same convention
+  /// as [`Self::radians`].
+  ///
+  /// This is handy for snapping analog stick input to the grid.
+  pub fn from_radians(angle: f32) -> Self {
+    Self::DIRECTIONS[radians_to_ordinal(angle, 8)]
+  }
+
+  /// Get the deltas a step in this direction would result in,
+  /// as an CoordVec.
+  pub fn deltas(self) -> CoordVec {
+    self.deltas_as()
+  }
+
+  /// Get the deltas a step in this direction would result in,
+  /// as a CoordVec of any scalar type.
+  pub fn deltas_as<T: SignedScalar>(self) -> CoordVec<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    let (x, y) = match self {
+      Direction8::North => (zero, -one),
+      Direction8::NorthEast => (one, -one),
+      Direction8::East => (one, zero),
+      Direction8::SouthEast => (one, one),
+      Direction8::South => (zero, one),
+      Direction8::SouthWest => (-one, one),
+      Direction8::West => (-one, zero),
+      Direction8::NorthWest => (-one, -one),
+    };
+    CoordVec { x, y }
+  }
+}
+
+impl From<Direction4> for Direction8 {
+  fn from(d4: Direction4) -> Self {
+    match d4 {
+      Direction4::North => Direction8::North,
+      Direction4::East => Direction8::East,
+      Direction4::South => Direction8::South,
+      Direction4::West => Direction8::West,
+    }
+  }
+}
+
+/// Sixteen-way directions, with a point halfway between each of the
+/// [`Direction8`]s.
+///
+/// These start at North and increment clockwise.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction16 {
+  North,
+  NorthNorthEast,
+  NorthEast,
+  EastNorthEast,
+  East,
+  EastSouthEast,
+  SouthEast,
+  SouthSouthEast,
+  South,
+  SouthSouthWest,
+  SouthWest,
+  WestSouthWest,
+  West,
+  WestNorthWest,
+  NorthWest,
+  NorthNorthWest,
+}
+
+impl Direction16 {
+  /// All the directions in order.
+  pub const DIRECTIONS: [Direction16; 16] = [
+    Direction16::North,
+    Direction16::NorthNorthEast,
+    Direction16::NorthEast,
+    Direction16::EastNorthEast,
+    Direction16::East,
+    Direction16::EastSouthEast,
+    Direction16::SouthEast,
+    Direction16::SouthSouthEast,
+    Direction16::South,
+    Direction16::SouthSouthWest,
+    Direction16::SouthWest,
+    Direction16::WestSouthWest,
+    Direction16::West,
+    Direction16::WestNorthWest,
+    Direction16::NorthWest,
+    Direction16::NorthNorthWest,
+  ];
+
+  /// Get the "index" of this direction,
+  /// in the same index as in [`Self::DIRECTIONS`].
+  pub fn ordinal(self) -> usize {
+    self as usize
+  }
+
+  /// Rotate this by the given amount.
+  pub fn rotate(self, rot: Rotation) -> Self {
+    self.rotate_by(rot.steps_clockwise() * 2)
+  }
+
+  /// Get this direction, rotated by this many steps clockwise.
+  /// Negative numbers go counter-clockwise.
+  ///
+  /// Note each step here is only a sixteenth of a turn.
+  pub fn rotate_by(self, steps_clockwise: i32) -> Self {
+    let idx = self.ordinal() as i32;
+    let new_idx = ((idx + steps_clockwise)
+      .rem_euclid(Self::DIRECTIONS.len() as i32)) as usize;
+    Self::DIRECTIONS[new_idx]
+  }
+
+  /// Flip this direction.
+  pub fn flip(self) -> Self {
+    self.rotate_by(8)
+  }
+
+  /// Get this direction in radians, with the same convention as
+  /// [`Direction4::radians`].
+  pub fn radians(self) -> f32 {
+    ordinal_to_radians(self.ordinal(), 16)
+  }
+
+  /// Get the direction closest to the given angle, using the same convention
+  /// as [`Self::radians`].
+  pub fn from_radians(angle: f32) -> Self {
+    Self::DIRECTIONS[radians_to_ordinal(angle, 16)]
+  }
+
+  /// Get the smallest whole-number step in this direction.
+  ///
+  /// The in-between directions like `NorthNorthEast` can't be done in
+  /// one cell, so they're a knight's move, like `(1, -2)`.
+  pub fn deltas(self) -> CoordVec {
+    self.deltas_as()
+  }
+
+  /// Like [`Self::deltas`], as a CoordVec of any scalar type.
+  pub fn deltas_as<T: SignedScalar>(self) -> CoordVec<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    let two = one + one;
+    let (x, y) = match self {
+      Direction16::North => (zero, -one),
+      Direction16::NorthNorthEast => (one, -two),
+      Direction16::NorthEast => (one, -one),
+      Direction16::EastNorthEast => (two, -one),
+      Direction16::East => (one, zero),
+      Direction16::EastSouthEast => (two, one),
+      Direction16::SouthEast => (one, one),
+      Direction16::SouthSouthEast => (one, two),
+      Direction16::South => (zero, one),
+      Direction16::SouthSouthWest => (-one, two),
+      Direction16::SouthWest => (-one, one),
+      Direction16::WestSouthWest => (-two, one),
+      Direction16::West => (-one, zero),
+      Direction16::WestNorthWest => (-two, -one),
+      Direction16::NorthWest => (-one, -one),
+      Direction16::NorthNorthWest => (-one, -two),
+    };
+    CoordVec { x, y }
+  }
+}
+
+impl From<Direction4> for Direction16 {
+  fn from(d4: Direction4) -> Self {
+    Direction16::DIRECTIONS[d4.ordinal() * 4]
+  }
+}
+
+impl From<Direction8> for Direction16 {
+  fn from(d8: Direction8) -> Self {
+    Direction16::DIRECTIONS[d8.ordinal() * 2]
+  }
+}
+
+/// Turn an index clockwise from north into radians clockwise from east.
+fn ordinal_to_radians(ordinal: usize, count: usize) -> f32 {
+  let from_east = (ordinal + count * 3 / 4) % count;
+  from_east as f32 * std::f32::consts::TAU / count as f32
+}
+
+/// Inverse of [`ordinal_to_radians`], rounding to the nearest index.
+fn radians_to_ordinal(angle: f32, count: usize) -> usize {
+  let steps = (angle / std::f32::consts::TAU * count as f32).round();
+  let from_east = (steps as i64).rem_euclid(count as i64) as usize;
+  (from_east + count / 4) % count
+}
+
+/// Nine-way directions: 8 compass points plus center.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction9 {
+  NorthWest,
+  North,
+  NorthEast,
+  West,
+  Center,
+  East,
+  SouthWest,
+  South,
+  SouthEast,
+}
+
+impl Direction9 {
+  pub const DIRECTIONS: &'static [Direction9] = &[
+    Direction9::NorthWest,
+    Direction9::North,
+    Direction9::NorthEast,
+    Direction9::West,
+    Direction9::Center,
+    Direction9::East,
+    Direction9::SouthEast,
+    Direction9::South,
+    Direction9::SouthWest,
+  ];
+
+  /// Rotate this by the given amount.
+  pub fn rotate(self, rot: Rotation) -> Self {
+    self.rotate_by(rot.steps_clockwise())
+  }
+
+  /// Get this direction, rotated by this many steps clockwise.
+  /// Negative numbers go counter-clockwise.
+  pub fn rotate_by(self, steps_clockwise: i32) -> Self {
+    let dir: Result<Direction8, _> = self.try_into();
+    match dir {
+      Ok(dir) => dir.rotate_by(steps_clockwise).into(),
+      Err(()) => self,
+    }
+  }
+
+  /// Flip this direction.
+  pub fn flip(self) -> Self {
+    self.rotate_by(4)
+  }
+
+  /// Get the deltas a step in this direction would result in,
+  /// as an CoordVec.
+  pub fn deltas(self) -> CoordVec {
+    self.deltas_as()
+  }
+
+  /// Get the deltas a step in this direction would result in,
+  /// as a CoordVec of any scalar type.
+  pub fn deltas_as<T: SignedScalar>(self) -> CoordVec<T> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    let (x, y) = match self {
+      Direction9::NorthWest => (-one, -one),
+      Direction9::North => (zero, -one),
+      Direction9::NorthEast => (one, -one),
+      Direction9::West => (-one, zero),
+      Direction9::Center => (zero, zero),
+      Direction9::East => (one, zero),
+      Direction9::SouthWest => (-one, one),
+      Direction9::South => (zero, one),
+      Direction9::SouthEast => (one, one),
+    };
+    CoordVec { x, y }
+  }
+}
+
+impl TryFrom<Direction9> for Direction8 {
+  type Error = ();
+
+  fn try_from(value: Direction9) -> Result<Self, Self::Error> {
+    Ok(match value {
+      Direction9::NorthWest => Direction8::NorthWest,
+      Direction9::North => Direction8::North,
+      Direction9::NorthEast => Direction8::NorthEast,
+      Direction9::West => Direction8::West,
+      Direction9::Center => Err(())?,
+      Direction9::East => Direction8::East,
+      Direction9::SouthEast => Direction8::SouthEast,
+      Direction9::South => Direction8::South,
+      Direction9::SouthWest => Direction8::SouthWest,
+    })
+  }
+}
+
+impl From<Direction8> for Direction9 {
+  fn from(dir: Direction8) -> Self {
+    match dir {
+      Direction8::North => Direction9::North,
+      Direction8::NorthEast => Direction9::NorthEast,
+      Direction8::East => Direction9::East,
+      Direction8::SouthEast => Direction9::SouthEast,
+      Direction8::South => Direction9::South,
+      Direction8::SouthWest => Direction9::SouthWest,
+      Direction8::West => Direction9::West,
+      Direction8::NorthWest => Direction9::NorthWest,
+    }
+  }
+}
+
+/// 2-way rotations: clockwise or counterclockwise.
+/// These don't indicate any specific angle by themselves, only in relation to something.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rotation {
+  Clockwise,
+  CounterClockwise,
+}
+
+impl Rotation {
+  /// Get the number of steps clockwise this does.
+  /// - `Clockwise` is 1
+  /// - `CounterClockwise` is -1
+  pub fn steps_clockwise(&self) -> i32 {
+    match self {
+      Rotation::Clockwise => 1,
+      Rotation::CounterClockwise => -1,
+    }
+  }
+}
+
+pub type Direction4Set = BitFlags<Direction4>;
+pub type Direction8Set = BitFlags<Direction8>;
+
+/// Directions with a short compass abbreviation, like `N` or `SE`.
+pub trait CompassDirection: Copy {
+  fn abbreviation(self) -> &'static str;
+}
+
+impl CompassDirection for Direction4 {
+  fn abbreviation(self) -> &'static str {
+    match self {
+      Direction4::North => "N",
+      Direction4::East => "E",
+      Direction4::South => "S",
+      Direction4::West => "W",
+    }
+  }
+}
+
+impl CompassDirection for Direction8 {
+  fn abbreviation(self) -> &'static str {
+    match self {
+      Direction8::North => "N",
+      Direction8::NorthEast => "NE",
+      Direction8::East => "E",
+      Direction8::SouthEast => "SE",
+      Direction8::South => "S",
+      Direction8::SouthWest => "SW",
+      Direction8::West => "W",
+      Direction8::NorthWest => "NW",
+    }
+  }
+}
+
+impl CompassDirection for Direction16 {
+  fn abbreviation(self) -> &'static str {
+    match self {
+      Direction16::North => "N",
+      Direction16::NorthNorthEast => "NNE",
+      Direction16::NorthEast => "NE",
+      Direction16::EastNorthEast => "ENE",
+      Direction16::East => "E",
+      Direction16::EastSouthEast => "ESE",
+      Direction16::SouthEast => "SE",
+      Direction16::SouthSouthEast => "SSE",
+      Direction16::South => "S",
+      Direction16::SouthSouthWest => "SSW",
+      Direction16::SouthWest => "SW",
+      Direction16::WestSouthWest => "WSW",
+      Direction16::West => "W",
+      Direction16::WestNorthWest => "WNW",
+      Direction16::NorthWest => "NW",
+      Direction16::NorthNorthWest => "NNW",
+    }
+  }
+}
+
+/// Extra methods for [`Direction4Set`] and [`Direction8Set`].
+///
+/// Those are just aliases for [`BitFlags`], so they can't get their own
+/// `Display` impl. Like with `Path::display`, call [`Self::display`] instead.
+pub trait DirectionSetExt<T: CompassDirection + BitFlag> {
+  /// Get something that displays this set compactly, like `N|E|S`.
+  ///
+  /// The empty set displays as `-`.
+  fn display(&self) -> DirectionSetDisplay<T>;
+}
+
+impl<T: CompassDirection + BitFlag> DirectionSetExt<T> for BitFlags<T> {
+  fn display(&self) -> DirectionSetDisplay<T> {
+    DirectionSetDisplay(*self)
+  }
+}
+
+/// Helper for displaying a set of directions; see [`DirectionSetExt::display`].
+#[derive(Debug, Clone, Copy)]
+pub struct DirectionSetDisplay<T: CompassDirection + BitFlag>(BitFlags<T>);
+
+impl<T: CompassDirection + BitFlag> Display for DirectionSetDisplay<T> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if self.0.is_empty() {
+      return f.write_str("-");
+    }
+    for (i, dir) in self.0.iter().enumerate() {
+      if i != 0 {
+        f.write_str("|")?;
+      }
+      f.write_str(dir.abbreviation())?;
+    }
+    Ok(())
+  }
+}
+
+/// Autotiling helpers for [`Direction4Set`] and [`Direction8Set`].
+///
+/// A set of directions is how a wall or pipe tile knows which of its
+/// neighbors it connects to; see [`Grid::connectivity`](crate::Grid::connectivity).
+pub trait AutotileSet: Sized {
+  /// Get this set as a bitmask, with bit `n` set if the `n`th direction in
+  /// `DIRECTIONS` is in it.
+  ///
+  /// So a [`Direction4Set`] gives 0 to 15, and a [`Direction8Set`] gives 0 to
+  /// 255, which can index straight into a tileset laid out in that order.
+  fn to_wall_index(self) -> usize;
+
+  /// The inverse of [`Self::to_wall_index`]. Extra high bits are ignored.
+  fn from_wall_index(idx: usize) -> Self;
+
+  /// Rotate every direction in the set by this many steps clockwise, like
+  /// the directions' own `rotate_by`.
+  fn rotated(self, steps_clockwise: i32) -> Self;
+
+  /// Apply a [`Transform`] to every direction in the set.
+  fn transformed(self, transform: Transform) -> Self;
+}
+
+impl AutotileSet for Direction4Set {
+  fn to_wall_index(self) -> usize {
+    self.bits() as usize
+  }
+
+  fn from_wall_index(idx: usize) -> Self {
+    BitFlags::from_bits_truncate(idx as u8)
+  }
+
+  fn rotated(self, steps_clockwise: i32) -> Self {
+    self
+      .iter()
+      .map(|dir| dir.rotate_by(steps_clockwise))
+      .collect()
+  }
+
+  fn transformed(self, transform: Transform) -> Self {
+    self.iter().map(|dir| transform.apply_dir4(dir)).collect()
+  }
+}
+
+impl AutotileSet for Direction8Set {
+  fn to_wall_index(self) -> usize {
+    self.bits() as usize
+  }
+
+  fn from_wall_index(idx: usize) -> Self {
+    BitFlags::from_bits_truncate(idx as u8)
+  }
+
+  fn rotated(self, steps_clockwise: i32) -> Self {
+    self
+      .iter()
+      .map(|dir| dir.rotate_by(steps_clockwise))
+      .collect()
+  }
+
+  fn transformed(self, transform: Transform) -> Self {
+    self.iter().map(|dir| transform.apply_dir8(dir)).collect()
+  }
+}
+
+/// Serialize a set of directions as a list of direction names, instead of as
+/// a bitfield.
+///
+/// Use it with `#[serde(with = "aglet::direction_set_names")]`.
+#[cfg(feature = "serde")]
+pub mod direction_set_names {
+  use enumflags2::{BitFlag, BitFlags};
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub fn serialize<T, S>(set: &BitFlags<T>, ser: S) -> Result<S::Ok, S::Error>
+  where
+    T: BitFlag + Serialize,
+    S: Serializer,
+  {
+    ser.collect_seq(set.iter())
+  }
+
+  pub fn deserialize<'de, T, D>(de: D) -> Result<BitFlags<T>, D::Error>
+  where
+    T: BitFlag + Deserialize<'de>,
+    D: Deserializer<'de>,
+  {
+    let dirs = Vec::<T>::deserialize(de)?;
+    Ok(dirs.into_iter().collect())
+  }
+}
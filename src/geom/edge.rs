@@ -0,0 +1,214 @@
+use super::{Coord, Direction4};
+
+/// The border between two orthagonally adjacent cells.
+///
+/// Edges are identified by a cell and a direction, but are always stored with
+/// the direction being `North` or `West`, so the same edge described from either
+/// side compares equal. (The edge on the south side of `(3, 4)` is the same as
+/// the edge on the north side of `(3, 5)`.)
+///
+/// This means edges on the north and west borders of a grid are at `y = 0` or
+/// `x = 0`, and the ones on the south and east borders are at `y = height` or
+/// `x = width`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EdgeCoord {
+  cell: Coord,
+  dir: Direction4,
+}
+
+impl EdgeCoord {
+  /// Make the edge on the given side of the cell.
+  pub fn new(cell: Coord, side: Direction4) -> Self {
+    match side {
+      Direction4::North | Direction4::West => Self { cell, dir: side },
+      Direction4::South => Self {
+        cell: Coord::new(cell.x, cell.y + 1),
+        dir: Direction4::North,
+      },
+      Direction4::East => Self {
+        cell: Coord::new(cell.x + 1, cell.y),
+        dir: Direction4::West,
+      },
+    }
+  }
+
+  /// Get the edge between two cells, or `None` if they aren't orthagonally
+  /// adjacent.
+  pub fn between(a: Coord, b: Coord) -> Option<Self> {
+    let dir = Direction4::DIRECTIONS
+      .into_iter()
+      .find(|dir| a.offset4(*dir) == Some(b))?;
+    Some(Self::new(a, dir))
+  }
+
+  /// Get the four edges around a cell, in the order of [`Direction4::DIRECTIONS`].
+  pub fn around(cell: Coord) -> [EdgeCoord; 4] {
+    Direction4::DIRECTIONS.map(|dir| EdgeCoord::new(cell, dir))
+  }
+
+  /// The cell this edge is stored relative to. The edge is on this cell's
+  /// north or west side.
+  pub fn cell(self) -> Coord {
+    self.cell
+  }
+
+  /// Either `North` or `West`; the side of [`Self::cell`] this edge is on.
+  pub fn dir(self) -> Direction4 {
+    self.dir
+  }
+
+  /// Does this edge run left-to-right (ie, is it between two vertically
+  /// adjacent cells)?
+  pub fn is_horizontal(self) -> bool {
+    self.dir == Direction4::North
+  }
+
+  /// Does this edge run top-to-bottom (ie, is it between two horizontally
+  /// adjacent cells)?
+  pub fn is_vertical(self) -> bool {
+    self.dir == Direction4::West
+  }
+
+  /// Get the two cells on either side of this edge.
+  ///
+  /// The first is to the north or west, and is `None` if it would be negative.
+  /// The second is always [`Self::cell`].
+  pub fn cells(self) -> (Option<Coord>, Coord) {
+    (self.cell.offset4(self.dir), self.cell)
+  }
+
+  /// Get the two corners at the ends of this edge,
+  /// north/west one first.
+  pub fn corners(self) -> [CornerCoord; 2] {
+    let start = CornerCoord::new(self.cell.x, self.cell.y);
+    let end = if self.is_horizontal() {
+      CornerCoord::new(self.cell.x + 1, self.cell.y)
+    } else {
+      CornerCoord::new(self.cell.x, self.cell.y + 1)
+    };
+    [start, end]
+  }
+}
+
+/// The point where the corners of (up to) four cells meet.
+///
+/// Corner `(x, y)` is the northwest corner of cell `(x, y)`.
+/// So, a grid of cells that's `width` by `height` has corners from `(0, 0)`
+/// to `(width, height)` inclusive.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CornerCoord {
+  pub x: u32,
+  pub y: u32,
+}
+
+impl CornerCoord {
+  pub const fn new(x: u32, y: u32) -> Self {
+    Self { x, y }
+  }
+
+  /// Get the four corners of a cell, clockwise starting from the northwest.
+  pub fn around(cell: Coord) -> [CornerCoord; 4] {
+    [
+      CornerCoord::new(cell.x, cell.y),
+      CornerCoord::new(cell.x + 1, cell.y),
+      CornerCoord::new(cell.x + 1, cell.y + 1),
+      CornerCoord::new(cell.x, cell.y + 1),
+    ]
+  }
+
+  /// Get the cells touching this corner, clockwise starting from the northwest.
+  ///
+  /// Cells that would be negative are `None`.
+  pub fn cells(self) -> [Option<Coord>; 4] {
+    let se = Coord::new(self.x, self.y);
+    [
+      se.offset4(Direction4::North)
+        .and_then(|c| c.offset4(Direction4::West)),
+      se.offset4(Direction4::North),
+      Some(se),
+      se.offset4(Direction4::West),
+    ]
+  }
+
+  /// Get the edges touching this corner, in the order of
+  /// [`Direction4::DIRECTIONS`] (so the edge running north from this corner
+  /// comes first).
+  ///
+  /// Edges that would be negative are `None`.
+  pub fn edges(self) -> [Option<EdgeCoord>; 4] {
+    let se = Coord::new(self.x, self.y);
+    [
+      se.offset4(Direction4::North)
+        .map(|c| EdgeCoord::new(c, Direction4::West)),
+      Some(EdgeCoord::new(se, Direction4::North)),
+      Some(EdgeCoord::new(se, Direction4::West)),
+      se.offset4(Direction4::West)
+        .map(|c| EdgeCoord::new(c, Direction4::North)),
+    ]
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, CornerCoord, Direction4, EdgeCoord};
+
+  #[test]
+  fn normalization() {
+    let c = Coord::new(3, 4);
+    assert_eq!(
+      EdgeCoord::new(c, Direction4::South),
+      EdgeCoord::new(Coord::new(3, 5), Direction4::North)
+    );
+    assert_eq!(
+      EdgeCoord::new(c, Direction4::East),
+      EdgeCoord::new(Coord::new(4, 4), Direction4::West)
+    );
+    assert_eq!(
+      EdgeCoord::between(c, Coord::new(3, 5)),
+      Some(EdgeCoord::new(c, Direction4::South))
+    );
+    assert_eq!(
+      EdgeCoord::between(Coord::new(3, 5), c),
+      Some(EdgeCoord::new(c, Direction4::South))
+    );
+    assert_eq!(EdgeCoord::between(c, Coord::new(4, 5)), None);
+    assert_eq!(EdgeCoord::between(c, c), None);
+  }
+
+  #[test]
+  fn adjacency() {
+    let edge = EdgeCoord::new(Coord::new(3, 4), Direction4::East);
+    assert!(edge.is_vertical());
+    assert_eq!(edge.cells(), (Some(Coord::new(3, 4)), Coord::new(4, 4)));
+    assert_eq!(
+      edge.corners(),
+      [CornerCoord::new(4, 4), CornerCoord::new(4, 5)]
+    );
+
+    let edge = EdgeCoord::new(Coord::new(0, 0), Direction4::North);
+    assert_eq!(edge.cells(), (None, Coord::new(0, 0)));
+
+    let corner = CornerCoord::new(1, 0);
+    assert_eq!(
+      corner.cells(),
+      [None, None, Some(Coord::new(1, 0)), Some(Coord::new(0, 0))]
+    );
+    assert_eq!(
+      corner.edges(),
+      [
+        None,
+        Some(EdgeCoord::new(Coord::new(1, 0), Direction4::North)),
+        Some(EdgeCoord::new(Coord::new(1, 0), Direction4::West)),
+        Some(EdgeCoord::new(Coord::new(0, 0), Direction4::North)),
+      ]
+    );
+    for edge in corner.edges().into_iter().flatten() {
+      assert!(edge.corners().contains(&corner));
+    }
+    for corner in CornerCoord::around(Coord::new(2, 2)) {
+      assert!(corner.cells().contains(&Some(Coord::new(2, 2))));
+    }
+  }
+}
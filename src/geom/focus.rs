@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use super::CoordVec;
+
+/// A set of coordinates that come out nearest-first, measured to whichever
+/// of some focus points is closest.
+///
+/// This is for deciding what to do next when there's too much to do at
+/// once, like which chunks to generate or load first when the players and
+/// cameras are the focus points.
+///
+/// Moving the foci doesn't do any work right away. The queue is re-sorted
+/// the next time it's looked at, and since the foci usually move only a
+/// little at a time the order is already nearly right, which is cheap to
+/// sort.
+#[derive(Debug, Clone, Default)]
+pub struct FocusQueue {
+    /// Farthest first, so the nearest can be popped off the end.
+    queue: Vec<CoordVec>,
+    members: HashSet<CoordVec>,
+    foci: Vec<CoordVec>,
+    sorted: bool,
+}
+
+impl FocusQueue {
+    /// Make an empty queue with no foci.
+    ///
+    /// Without any foci, coordinates come out in an arbitrary order.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the focus points.
+    pub fn set_foci(&mut self, foci: impl IntoIterator<Item = CoordVec>) {
+        self.foci.clear();
+        self.foci.extend(foci);
+        self.sorted = false;
+    }
+
+    pub fn foci(&self) -> &[CoordVec] {
+        &self.foci
+    }
+
+    /// Add a coordinate to the queue.
+    ///
+    /// Returns `false` if it was already there.
+    pub fn push(&mut self, coord: CoordVec) -> bool {
+        if !self.members.insert(coord) {
+            return false;
+        }
+        if self.sorted {
+            let key = self.key(coord);
+            let idx = self.queue.partition_point(|other| self.key(*other) > key);
+            self.queue.insert(idx, coord);
+        } else {
+            self.queue.push(coord);
+        }
+        true
+    }
+
+    /// Take a coordinate out of the queue, wherever it is.
+    ///
+    /// Returns `false` if it wasn't there.
+    pub fn remove(&mut self, coord: CoordVec) -> bool {
+        if !self.members.remove(&coord) {
+            return false;
+        }
+        let idx = self.queue.iter().position(|c| *c == coord).unwrap();
+        self.queue.remove(idx);
+        true
+    }
+
+    pub fn contains(&self, coord: CoordVec) -> bool {
+        self.members.contains(&coord)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Get the coordinate nearest to any focus, without removing it.
+    pub fn peek(&mut self) -> Option<CoordVec> {
+        self.sort();
+        self.queue.last().copied()
+    }
+
+    /// Remove and return the coordinate nearest to any focus.
+    ///
+    /// Ties are broken by row, then column, so the order is always the same.
+    pub fn pop(&mut self) -> Option<CoordVec> {
+        self.sort();
+        let coord = self.queue.pop()?;
+        self.members.remove(&coord);
+        Some(coord)
+    }
+
+    /// Iterate over everything in the queue, nearest first, without removing
+    /// anything.
+    pub fn iter(&mut self) -> impl Iterator<Item = CoordVec> + '_ {
+        self.sort();
+        self.queue.iter().rev().copied()
+    }
+
+    fn sort(&mut self) {
+        if self.sorted {
+            return;
+        }
+        let mut queue = std::mem::take(&mut self.queue);
+        // The stable sort is the one that takes advantage of nearly sorted input
+        queue.sort_by_key(|coord| std::cmp::Reverse(self.key(*coord)));
+        self.queue = queue;
+        self.sorted = true;
+    }
+
+    /// Smaller keys come out first.
+    fn key(&self, coord: CoordVec) -> (i64, i32, i32) {
+        let distance = self
+            .foci
+            .iter()
+            .map(|focus| {
+                let dx = coord.x as i64 - focus.x as i64;
+                let dy = coord.y as i64 - focus.y as i64;
+                dx * dx + dy * dy
+            })
+            .min()
+            .unwrap_or(0);
+        (distance, coord.y, coord.x)
+    }
+}
+
+impl Extend<CoordVec> for FocusQueue {
+    fn extend<I: IntoIterator<Item = CoordVec>>(&mut self, iter: I) {
+        for coord in iter {
+            self.push(coord);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nearest_first() {
+        let mut queue = FocusQueue::new();
+        queue.extend((-3..=3).map(|x| CoordVec::new(x, 0)));
+        assert!(!queue.push(CoordVec::new(0, 0)));
+        queue.set_foci([CoordVec::new(-3, 0)]);
+        assert_eq!(queue.pop(), Some(CoordVec::new(-3, 0)));
+        assert_eq!(queue.peek(), Some(CoordVec::new(-2, 0)));
+
+        // Moving the focus changes the order
+        queue.set_foci([CoordVec::new(3, 1)]);
+        assert_eq!(queue.pop(), Some(CoordVec::new(3, 0)));
+        // Pushing into a sorted queue puts it in the right place
+        queue.push(CoordVec::new(3, 2));
+        assert_eq!(queue.pop(), Some(CoordVec::new(3, 2)));
+
+        // With two foci, whichever is closer counts
+        queue.set_foci([CoordVec::new(-2, 0), CoordVec::new(2, 0)]);
+        assert!(queue.remove(CoordVec::new(2, 0)));
+        assert!(!queue.contains(CoordVec::new(2, 0)));
+        let order: Vec<_> = queue.iter().collect();
+        assert_eq!(
+            order,
+            [
+                CoordVec::new(-2, 0),
+                CoordVec::new(-1, 0),
+                CoordVec::new(1, 0),
+                CoordVec::new(0, 0),
+            ]
+        );
+        assert_eq!(queue.len(), 4);
+    }
+}
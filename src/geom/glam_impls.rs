@@ -0,0 +1,106 @@
+//! Conversions to and from [`glam`]'s vectors, for handing coordinates to a
+//! renderer.
+
+use glam::{IVec2, UVec2, Vec2};
+
+use super::{Coord, CoordVec};
+
+impl From<Coord> for UVec2 {
+    fn from(coord: Coord) -> Self {
+        UVec2::new(coord.x, coord.y)
+    }
+}
+
+impl From<UVec2> for Coord {
+    fn from(v: UVec2) -> Self {
+        Coord::new(v.x, v.y)
+    }
+}
+
+impl From<CoordVec> for IVec2 {
+    fn from(v: CoordVec) -> Self {
+        IVec2::new(v.x, v.y)
+    }
+}
+
+impl From<IVec2> for CoordVec {
+    fn from(v: IVec2) -> Self {
+        CoordVec::new(v.x, v.y)
+    }
+}
+
+impl From<Coord> for Vec2 {
+    fn from(coord: Coord) -> Self {
+        Vec2::new(coord.x as f32, coord.y as f32)
+    }
+}
+
+impl From<CoordVec> for Vec2 {
+    fn from(v: CoordVec) -> Self {
+        Vec2::new(v.x as f32, v.y as f32)
+    }
+}
+
+impl Coord {
+    /// Get the position of the middle of this tile in world space, where each
+    /// tile is `tile_size` big.
+    pub fn to_world_center(self, tile_size: Vec2) -> Vec2 {
+        self.to_icoord().to_world_center(tile_size)
+    }
+}
+
+impl CoordVec {
+    /// Get the tile that a position in world space falls in, where each tile
+    /// is `tile_size` big.
+    ///
+    /// Positions exactly on a tile boundary go to the tile down and to the
+    /// right.
+    pub fn from_world(pos: Vec2, tile_size: Vec2) -> Self {
+        (pos / tile_size).floor().as_ivec2().into()
+    }
+
+    /// Get the position of the middle of this tile in world space, where each
+    /// tile is `tile_size` big.
+    pub fn to_world_center(self, tile_size: Vec2) -> Vec2 {
+        (Vec2::from(self) + Vec2::splat(0.5)) * tile_size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::{IVec2, UVec2, Vec2};
+
+    use crate::{Coord, CoordVec};
+
+    #[test]
+    fn conversions() {
+        assert_eq!(UVec2::from(Coord::new(3, 4)), UVec2::new(3, 4));
+        assert_eq!(Coord::from(UVec2::new(3, 4)), Coord::new(3, 4));
+        assert_eq!(IVec2::from(CoordVec::new(-3, 4)), IVec2::new(-3, 4));
+        assert_eq!(CoordVec::from(IVec2::new(-3, 4)), CoordVec::new(-3, 4));
+        assert_eq!(Vec2::from(CoordVec::new(-3, 4)), Vec2::new(-3.0, 4.0));
+    }
+
+    #[test]
+    fn world_space() {
+        let tile = Vec2::new(16.0, 8.0);
+        assert_eq!(
+            Coord::new(1, 2).to_world_center(tile),
+            Vec2::new(24.0, 20.0)
+        );
+        assert_eq!(
+            CoordVec::from_world(Vec2::new(24.0, 20.0), tile),
+            CoordVec::new(1, 2)
+        );
+        assert_eq!(
+            CoordVec::from_world(Vec2::new(-0.5, 8.0), tile),
+            CoordVec::new(-1, 1)
+        );
+        for coord in [CoordVec::new(-5, 7), CoordVec::new(0, -1)] {
+            assert_eq!(
+                CoordVec::from_world(coord.to_world_center(tile), tile),
+                coord
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use super::CoordVec;
+
+/// Hash a coordinate and a seed into 64 random-looking bits.
+///
+/// This is for randomness that's tied to a place instead of stored anywhere,
+/// like which decoration variant a floor tile gets or the seed for the loot
+/// in a chest. The same coordinate and seed always give the same hash, on
+/// every platform and every version of this crate.
+///
+/// For a given seed, different coordinates never collide.
+///
+/// ```
+/// # use aglet::{coord_hash, Coord};
+/// let here = coord_hash(Coord::new(3, 4), 1234);
+/// assert_eq!(here, coord_hash(Coord::new(3, 4), 1234));
+/// assert_ne!(here, coord_hash(Coord::new(4, 3), 1234));
+/// assert_ne!(here, coord_hash(Coord::new(3, 4), 1235));
+/// ```
+pub fn coord_hash(coord: impl Into<CoordVec>, seed: u64) -> u64 {
+    let coord = coord.into();
+    let packed = ((coord.x as u32 as u64) << 32) | coord.y as u32 as u64;
+    mix64(mix64(seed) ^ packed)
+}
+
+/// Get a random-looking number from 0 up to (but not including) 1 for a
+/// coordinate and seed. See [`coord_hash`].
+///
+/// This is white noise: neighboring cells have nothing to do with each other.
+pub fn coord_noise_f32(coord: impl Into<CoordVec>, seed: u64) -> f32 {
+    // f32 has 24 bits of precision, so take the top 24 bits of the hash
+    (coord_hash(coord, seed) >> 40) as f32 / (1u32 << 24) as f32
+}
+
+/// The SplitMix64 finalizer. It's a bijection, and flipping any input bit
+/// flips about half the output bits.
+pub(crate) fn mix64(z: u64) -> u64 {
+    let z = z.wrapping_add(0x9e3779b97f4a7c15);
+    let z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    let z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{coord_hash, coord_noise_f32, Coord, CoordVec};
+
+    #[test]
+    fn stable() {
+        // These must never change, or saved worlds will look different
+        assert_eq!(coord_hash(CoordVec::new(0, 0), 0), 0xa706dd2f4d197e6f);
+        assert_eq!(coord_hash(CoordVec::new(-5, 7), 42), 0x24eaeeebce44e729);
+        assert_eq!(
+            coord_hash(Coord::new(1, 2), 9),
+            coord_hash(CoordVec::new(1, 2), 9)
+        );
+    }
+
+    #[test]
+    fn noise_spread() {
+        let mut buckets = [0; 10];
+        for x in -50..50 {
+            for y in -50..50 {
+                let n = coord_noise_f32(CoordVec::new(x, y), 7);
+                assert!((0.0..1.0).contains(&n));
+                buckets[(n * 10.0) as usize] += 1;
+            }
+        }
+        for count in buckets {
+            assert!((900..1100).contains(&count), "{:?}", buckets);
+        }
+    }
+}
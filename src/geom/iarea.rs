@@ -0,0 +1,254 @@
+use std::num::TryFromIntError;
+
+use super::{Area, CoordVec, Scalar, SignedScalar, UnsignedScalar};
+
+/// Like an [`Area`], but in signed space, so the corner can be negative.
+///
+/// This is handy for things like camera rectangles and world-space bounding
+/// boxes, which don't care about the edge of the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IArea<T: SignedScalar = i32> {
+    pub corner: CoordVec<T>,
+    pub width: T::Unsigned,
+    pub height: T::Unsigned,
+}
+
+impl<T: SignedScalar> IArea<T> {
+    pub fn new(corner: CoordVec<T>, width: T::Unsigned, height: T::Unsigned) -> Self {
+        Self {
+            corner,
+            width,
+            height,
+        }
+    }
+
+    /// Does this area have no cells in it?
+    pub fn is_empty(&self) -> bool {
+        self.width == T::Unsigned::ZERO || self.height == T::Unsigned::ZERO
+    }
+
+    pub fn contains(&self, pos: CoordVec<T>) -> bool {
+        self.x1() <= pos.x && pos.x < self.x2() && self.y1() <= pos.y && pos.y < self.y2()
+    }
+
+    /// Do the two areas share any cells?
+    pub fn overlaps(&self, other: &IArea<T>) -> bool {
+        self.intersect(other).is_some()
+    }
+
+    /// Get the area of cells that are in both areas,
+    /// or `None` if they don't overlap.
+    pub fn intersect(&self, other: &IArea<T>) -> Option<IArea<T>> {
+        let x1 = self.x1().max(other.x1());
+        let y1 = self.y1().max(other.y1());
+        let x2 = self.x2().min(other.x2());
+        let y2 = self.y2().min(other.y2());
+        if x1 < x2 && y1 < y2 {
+            Some(IArea::from_bounds(x1, y1, x2, y2))
+        } else {
+            None
+        }
+    }
+
+    /// Get the smallest area containing both areas.
+    pub fn union_bounds(&self, other: &IArea<T>) -> IArea<T> {
+        let x1 = self.x1().min(other.x1());
+        let y1 = self.y1().min(other.y1());
+        let x2 = self.x2().max(other.x2());
+        let y2 = self.y2().max(other.y2());
+        IArea::from_bounds(x1, y1, x2, y2)
+    }
+
+    /// Grow this area by `n` cells on every side.
+    pub fn expand(&self, n: T::Unsigned) -> IArea<T> {
+        let n = n.as_signed();
+        IArea::from_bounds(self.x1() - n, self.y1() - n, self.x2() + n, self.y2() + n)
+    }
+
+    /// Shrink this area by `n` cells on every side.
+    ///
+    /// Returns `None` if there would be nothing left.
+    pub fn shrink(&self, n: T::Unsigned) -> Option<IArea<T>> {
+        let two_n = n + n;
+        if self.width <= two_n || self.height <= two_n {
+            return None;
+        }
+        Some(IArea::new(
+            CoordVec {
+                x: self.x1() + n.as_signed(),
+                y: self.y1() + n.as_signed(),
+            },
+            self.width - two_n,
+            self.height - two_n,
+        ))
+    }
+
+    pub fn center(&self) -> CoordVec<T> {
+        let two = T::Unsigned::ONE + T::Unsigned::ONE;
+        CoordVec {
+            x: self.corner.x + (self.width / two).as_signed(),
+            y: self.corner.y + (self.height / two).as_signed(),
+        }
+    }
+
+    /// Get the four corner cells of this area, clockwise starting from the top-left.
+    ///
+    /// If the area is empty, this is meaningless.
+    pub fn corners(&self) -> [CoordVec<T>; 4] {
+        let right = (self.x2() - T::ONE).max(self.x1());
+        let bottom = (self.y2() - T::ONE).max(self.y1());
+        [
+            CoordVec {
+                x: self.x1(),
+                y: self.y1(),
+            },
+            CoordVec {
+                x: right,
+                y: self.y1(),
+            },
+            CoordVec {
+                x: right,
+                y: bottom,
+            },
+            CoordVec {
+                x: self.x1(),
+                y: bottom,
+            },
+        ]
+    }
+
+    /// Left-side X coordinate
+    pub fn x1(&self) -> T {
+        self.corner.x
+    }
+    /// Right-side X coordinate
+    pub fn x2(&self) -> T {
+        self.corner.x + self.width.as_signed()
+    }
+    /// Top Y coordinate
+    pub fn y1(&self) -> T {
+        self.corner.y
+    }
+    /// Bottom Y coordinate
+    pub fn y2(&self) -> T {
+        self.corner.y + self.height.as_signed()
+    }
+
+    fn from_bounds(x1: T, y1: T, x2: T, y2: T) -> Self {
+        IArea::new(CoordVec { x: x1, y: y1 }, x2.abs_diff(x1), y2.abs_diff(y1))
+    }
+}
+
+/// Fails if the area's right or bottom edge doesn't fit in the signed type.
+impl<U: UnsignedScalar> TryFrom<Area<U>> for IArea<U::Signed> {
+    type Error = TryFromIntError;
+
+    fn try_from(area: Area<U>) -> Result<Self, Self::Error> {
+        // Round-tripping through the signed type catches anything too big
+        area.x2().as_signed().to_unsigned()?;
+        area.y2().as_signed().to_unsigned()?;
+        Ok(IArea::new(area.corner.to_icoord(), area.width, area.height))
+    }
+}
+
+/// Fails if the area's corner is negative.
+impl<T: SignedScalar> TryFrom<IArea<T>> for Area<T::Unsigned> {
+    type Error = TryFromIntError;
+
+    fn try_from(area: IArea<T>) -> Result<Self, Self::Error> {
+        Ok(Area::new(area.corner.try_into()?, area.width, area.height))
+    }
+}
+
+impl<T: SignedScalar> IntoIterator for IArea<T> {
+    type Item = CoordVec<T>;
+
+    type IntoIter = IAreaIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IAreaIter {
+            area: self,
+            cursor: T::Unsigned::ZERO,
+        }
+    }
+}
+
+pub struct IAreaIter<T: SignedScalar = i32> {
+    area: IArea<T>,
+    cursor: T::Unsigned,
+}
+
+impl<T: SignedScalar> Iterator for IAreaIter<T> {
+    type Item = CoordVec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.area.width * self.area.height {
+            return None;
+        }
+
+        let x = (self.cursor % self.area.width).as_signed();
+        let y = (self.cursor / self.area.width).as_signed();
+        self.cursor += T::Unsigned::ONE;
+        Some(self.area.corner + CoordVec { x, y })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: SignedScalar> ExactSizeIterator for IAreaIter<T> {
+    fn len(&self) -> usize {
+        (self.area.width * self.area.height - self.cursor)
+            .to_usize()
+            .unwrap_or(usize::MAX)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Area, Coord, CoordVec, IArea};
+
+    #[test]
+    fn negative() {
+        let area = IArea::new(CoordVec::new(-2, -1), 3, 2);
+        assert!(area.contains(CoordVec::new(-2, -1)));
+        assert!(area.contains(CoordVec::new(0, 0)));
+        assert!(!area.contains(CoordVec::new(1, 0)));
+        assert_eq!(
+            area.into_iter().collect::<Vec<_>>(),
+            [
+                CoordVec::new(-2, -1),
+                CoordVec::new(-1, -1),
+                CoordVec::new(0, -1),
+                CoordVec::new(-2, 0),
+                CoordVec::new(-1, 0),
+                CoordVec::new(0, 0),
+            ]
+        );
+        assert_eq!(area.center(), CoordVec::new(-1, 0));
+        assert_eq!(area.expand(1), IArea::new(CoordVec::new(-3, -2), 5, 4));
+    }
+
+    #[test]
+    fn set_ops() {
+        let a = IArea::new(CoordVec::new(-5, -5), 6, 6);
+        let b = IArea::new(CoordVec::new(0, 0), 3, 3);
+        assert_eq!(a.intersect(&b), Some(IArea::new(CoordVec::new(0, 0), 1, 1)));
+        assert_eq!(a.union_bounds(&b), IArea::new(CoordVec::new(-5, -5), 8, 8));
+        assert!(!a.overlaps(&IArea::new(CoordVec::new(1, -5), 3, 3)));
+    }
+
+    #[test]
+    fn conversions() {
+        let area = Area::new(Coord::new(3, 4), 5, 6);
+        let iarea = IArea::try_from(area).unwrap();
+        assert_eq!(iarea, IArea::new(CoordVec::new(3, 4), 5, 6));
+        assert_eq!(Area::try_from(iarea), Ok(area));
+
+        assert!(Area::try_from(IArea::new(CoordVec::new(-1, 0), 5, 5)).is_err());
+        assert!(IArea::try_from(Area::new(Coord::new(u32::MAX - 2, 0), 1, 1)).is_err());
+    }
+}
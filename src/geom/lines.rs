@@ -1,38 +1,42 @@
-use crate::{Coord, CoordVec};
+use crate::{Area, Coord, CoordVec, Region, Scalar, SignedScalar, UnsignedScalar};
 
 /// Iterates over coordinates on a line using Bresenham's algorithm.
 ///
 /// Implementation taken mostly from https://crates.io/crates/bresenham,
 /// with some new features.
 #[derive(Debug)]
-pub struct LineIter {
-    cursor: CoordVec,
-    deltas: CoordVec,
-    x1: i32,
-    diff: i32,
+pub struct LineIter<T: UnsignedScalar = u32> {
+    cursor: CoordVec<T::Signed>,
+    deltas: CoordVec<T::Signed>,
+    x1: T::Signed,
+    diff: T::Signed,
     octant: Octant,
     end_mode: LineEndMode,
 }
 
-impl LineIter {
+impl<T: UnsignedScalar> LineIter<T> {
     /// Creates a new iterator. Yields intermediate points between `start`
     /// and `end`. Does include `start` but not `end`.
-    pub fn new(start: Coord, end: Coord) -> LineIter {
+    pub fn new(start: Coord<T>, end: Coord<T>) -> LineIter<T> {
         Self::new_with_end_mode(start, end, LineEndMode::StopBefore)
     }
 
-    pub fn new_with_end_mode(start: Coord, end: Coord, end_mode: LineEndMode) -> LineIter {
-        let octant = Octant::from_points(start, end);
+    pub fn new_with_end_mode(
+        start: Coord<T>,
+        end: Coord<T>,
+        end_mode: LineEndMode,
+    ) -> LineIter<T> {
+        let octant = Octant::from_points(start.to_icoord(), end.to_icoord());
 
         let start = octant.to_octant0(start.into());
         let end = octant.to_octant0(end.into());
 
-        let dx = end.x as i32 - start.x as i32;
-        let dy = end.y as i32 - start.y as i32;
+        let dx = end.x - start.x;
+        let dy = end.y - start.y;
 
         LineIter {
             cursor: start,
-            deltas: CoordVec::new(dx, dy),
+            deltas: CoordVec { x: dx, y: dy },
             x1: end.x,
             diff: dy - dx,
             octant,
@@ -41,8 +45,8 @@ impl LineIter {
     }
 }
 
-impl Iterator for LineIter {
-    type Item = Coord;
+impl<T: UnsignedScalar> Iterator for LineIter<T> {
+    type Item = Coord<T>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let stop = match self.end_mode {
@@ -54,25 +58,64 @@ impl Iterator for LineIter {
             return None;
         }
 
-        let out = match self.octant.from_octant0(self.cursor.into()).try_into() {
+        let out = match self.octant.from_octant0(self.cursor).try_into() {
             Ok(it) => it,
             Err(_) => return None,
         };
 
-        if self.diff >= 0 {
-            self.cursor.y += 1;
+        if self.diff >= T::Signed::ZERO {
+            self.cursor.y += T::Signed::ONE;
             self.diff -= self.deltas.x;
         }
 
         self.diff += self.deltas.y;
 
         // loop inc
-        self.cursor.x += 1;
+        self.cursor.x += T::Signed::ONE;
 
         Some(out)
     }
 }
 
+/// A straight line between two cells, including both ends.
+///
+/// This is handy for planning corridors and doors: check what a line would
+/// run into before carving it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment<T = u32> {
+    pub start: Coord<T>,
+    pub end: Coord<T>,
+}
+
+impl<T: UnsignedScalar> Segment<T> {
+    pub fn new(start: Coord<T>, end: Coord<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Iterate over every cell on the line, from `start` to `end` inclusive.
+    pub fn cells(&self) -> LineIter<T> {
+        LineIter::new_with_end_mode(self.start, self.end, LineEndMode::StopAt)
+    }
+
+    /// Iterate over the cells on the line that are inside `area`.
+    pub fn cells_intersecting(&self, area: Area<T>) -> impl Iterator<Item = Coord<T>> {
+        self.cells().filter(move |coord| area.contains(*coord))
+    }
+
+    /// Does the line go through any cell of `area`?
+    pub fn intersects_area(&self, area: Area<T>) -> bool {
+        self.cells_intersecting(area).next().is_some()
+    }
+}
+
+impl Segment {
+    /// Does the line go through any cell of `region`?
+    pub fn crosses_region(&self, region: &Region) -> bool {
+        self.cells().any(|coord| region.contains(coord))
+    }
+}
+
 /// Where to stop the iteration of the line.
 #[derive(Debug, Clone, Copy, Default)]
 pub enum LineEndMode {
@@ -92,19 +135,19 @@ struct Octant(u8);
 impl Octant {
     /// adapted from http://codereview.stackexchange.com/a/95551
     #[inline]
-    fn from_points(start: Coord, end: Coord) -> Octant {
-        let mut dx = end.x as i32 - start.x as i32;
-        let mut dy = end.y as i32 - start.y as i32;
+    fn from_points<S: SignedScalar>(start: CoordVec<S>, end: CoordVec<S>) -> Octant {
+        let mut dx = end.x - start.x;
+        let mut dy = end.y - start.y;
 
         let mut octant = 0;
 
-        if dy < 0 {
+        if dy < S::ZERO {
             dx = -dx;
             dy = -dy;
             octant += 4;
         }
 
-        if dx < 0 {
+        if dx < S::ZERO {
             let tmp = dx;
             dx = dy;
             dy = -tmp;
@@ -119,7 +162,7 @@ impl Octant {
     }
 
     #[inline]
-    fn to_octant0(&self, p: CoordVec) -> CoordVec {
+    fn to_octant0<S: SignedScalar>(&self, p: CoordVec<S>) -> CoordVec<S> {
         let (x, y) = match self.0 {
             0 => (p.x, p.y),
             1 => (p.y, p.x),
@@ -131,11 +174,12 @@ impl Octant {
             7 => (p.x, -p.y),
             _ => unreachable!(),
         };
-        CoordVec::new(x, y)
+        CoordVec { x, y }
     }
 
     #[inline]
-    fn from_octant0(&self, p: CoordVec) -> CoordVec {
+    #[allow(clippy::wrong_self_convention)]
+    fn from_octant0<S: SignedScalar>(&self, p: CoordVec<S>) -> CoordVec<S> {
         let (x, y) = match self.0 {
             0 => (p.x, p.y),
             1 => (p.y, p.x),
@@ -147,7 +191,7 @@ impl Octant {
             7 => (p.x, -p.y),
             _ => unreachable!(),
         };
-        CoordVec::new(x, y)
+        CoordVec { x, y }
     }
 }
 
@@ -240,4 +284,25 @@ mod tests {
         let res: Vec<_> = li.collect();
         println!("{:?}", res);
     }
+
+    #[test]
+    fn test_segment() {
+        let corridor = Segment::new(Coord::new(0, 2), Coord::new(9, 2));
+        let room = Area::new(Coord::new(3, 1), 3, 3);
+        let hit: Vec<_> = corridor
+            .cells_intersecting(room)
+            .map(|c| (c.x, c.y))
+            .collect();
+        assert_eq!(hit, [(3, 2), (4, 2), (5, 2)]);
+        assert!(corridor.intersects_area(room));
+        assert!(!corridor.intersects_area(Area::new(Coord::new(0, 3), 10, 2)));
+        // Both ends count
+        assert!(corridor.intersects_area(Area::new(Coord::new(9, 2), 1, 1)));
+
+        let mut region = Region::new();
+        region.insert(Coord::new(7, 3));
+        assert!(!corridor.crosses_region(&region));
+        region.insert(Coord::new(7, 2));
+        assert!(corridor.crosses_region(&region));
+    }
 }
@@ -0,0 +1,77 @@
+//! Conversions to and from [`mint`]'s vectors and points, for talking to any
+//! math library that supports it.
+
+use mint::{Point2, Vector2};
+
+use super::{Coord, CoordVec, SignedScalar, UnsignedScalar};
+
+impl<T: UnsignedScalar> From<Coord<T>> for Vector2<T> {
+    fn from(coord: Coord<T>) -> Self {
+        Vector2 {
+            x: coord.x,
+            y: coord.y,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> From<Vector2<T>> for Coord<T> {
+    fn from(v: Vector2<T>) -> Self {
+        Coord { x: v.x, y: v.y }
+    }
+}
+
+impl<T: UnsignedScalar> From<Coord<T>> for Point2<T> {
+    fn from(coord: Coord<T>) -> Self {
+        Point2 {
+            x: coord.x,
+            y: coord.y,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> From<Point2<T>> for Coord<T> {
+    fn from(p: Point2<T>) -> Self {
+        Coord { x: p.x, y: p.y }
+    }
+}
+
+impl<T: SignedScalar> From<CoordVec<T>> for Vector2<T> {
+    fn from(v: CoordVec<T>) -> Self {
+        Vector2 { x: v.x, y: v.y }
+    }
+}
+
+impl<T: SignedScalar> From<Vector2<T>> for CoordVec<T> {
+    fn from(v: Vector2<T>) -> Self {
+        CoordVec { x: v.x, y: v.y }
+    }
+}
+
+impl<T: SignedScalar> From<CoordVec<T>> for Point2<T> {
+    fn from(v: CoordVec<T>) -> Self {
+        Point2 { x: v.x, y: v.y }
+    }
+}
+
+impl<T: SignedScalar> From<Point2<T>> for CoordVec<T> {
+    fn from(p: Point2<T>) -> Self {
+        CoordVec { x: p.x, y: p.y }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use mint::{Point2, Vector2};
+
+    use crate::{Coord, CoordVec};
+
+    #[test]
+    fn conversions() {
+        let v: Vector2<u32> = Coord::new(3, 4).into();
+        assert_eq!((v.x, v.y), (3, 4));
+        assert_eq!(Coord::from(v), Coord::new(3, 4));
+        let p: Point2<i16> = CoordVec { x: -3i16, y: 4 }.into();
+        assert_eq!((p.x, p.y), (-3, 4));
+        assert_eq!(CoordVec::from(p), CoordVec { x: -3i16, y: 4 });
+    }
+}
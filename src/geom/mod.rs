@@ -0,0 +1,49 @@
+//! Coordinates, directions, and shapes made out of them.
+
+mod area;
+mod bitgrid;
+mod coord;
+mod coord3;
+mod direction;
+mod edge;
+mod focus;
+#[cfg(feature = "glam")]
+mod glam_impls;
+mod hash;
+mod iarea;
+mod lines;
+#[cfg(feature = "mint")]
+mod mint_impls;
+mod neighbors;
+mod per_direction;
+mod polygon;
+#[cfg(feature = "rand")]
+mod random;
+mod ray;
+mod region;
+mod rings;
+mod scalar;
+mod space;
+mod transform;
+
+pub use area::*;
+pub use bitgrid::*;
+pub use coord::*;
+pub use coord3::*;
+pub use direction::*;
+pub use edge::*;
+pub use focus::*;
+pub use hash::*;
+pub use iarea::*;
+pub use lines::*;
+pub use neighbors::*;
+pub use per_direction::*;
+pub use polygon::*;
+#[cfg(feature = "rand")]
+pub use random::*;
+pub use ray::*;
+pub use region::*;
+pub use rings::*;
+pub use scalar::*;
+pub use space::*;
+pub use transform::*;
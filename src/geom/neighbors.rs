@@ -0,0 +1,125 @@
+use crate::{Area, Coord, Direction8, UnsignedScalar};
+
+const ORTHAGONALS: &[Direction8] = &[
+    Direction8::North,
+    Direction8::East,
+    Direction8::South,
+    Direction8::West,
+];
+
+/// Which cells count as adjacent to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Connectivity {
+    /// Only orthagonal neighbors.
+    #[default]
+    Four,
+    /// Orthagonal and diagonal neighbors.
+    Eight,
+}
+
+impl Connectivity {
+    /// Iterate over the neighbors of `coord` that are inside `area`.
+    pub fn neighbors_within<T: UnsignedScalar>(
+        self,
+        coord: Coord<T>,
+        area: Area<T>,
+    ) -> Neighbors<T> {
+        match self {
+            Connectivity::Four => coord.neighbors4_within(area),
+            Connectivity::Eight => coord.neighbors8_within(area),
+        }
+    }
+}
+
+/// Iterator over the neighbors of a [`Coord`], without allocating.
+///
+/// Neighbors are given in clockwise order starting with the one to the north.
+/// Neighbors that would be negative, or outside the bounding area if there is one,
+/// are skipped.
+///
+/// Make one with [`Coord::neighbors4_iter`], [`Coord::neighbors8_iter`],
+/// or their bounded variants.
+#[derive(Debug, Clone)]
+pub struct Neighbors<T: UnsignedScalar = u32> {
+    center: Coord<T>,
+    dirs: &'static [Direction8],
+    bounds: Option<Area<T>>,
+}
+
+impl<T: UnsignedScalar> Neighbors<T> {
+    pub(crate) fn new4(center: Coord<T>, bounds: Option<Area<T>>) -> Self {
+        Self {
+            center,
+            dirs: ORTHAGONALS,
+            bounds,
+        }
+    }
+
+    pub(crate) fn new8(center: Coord<T>, bounds: Option<Area<T>>) -> Self {
+        Self {
+            center,
+            dirs: &Direction8::DIRECTIONS,
+            bounds,
+        }
+    }
+}
+
+impl<T: UnsignedScalar> Iterator for Neighbors<T> {
+    type Item = Coord<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((dir, rest)) = self.dirs.split_first() {
+            self.dirs = rest;
+            let neighbor = match self.center.offset8(*dir) {
+                Some(it) => it,
+                None => continue,
+            };
+            if let Some(bounds) = self.bounds {
+                if !bounds.contains(neighbor) {
+                    continue;
+                }
+            }
+            return Some(neighbor);
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.dirs.len()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Area, Coord};
+
+    #[test]
+    fn matches_vec_versions() {
+        for coord in Area::new(Coord::ZERO, 3, 3) {
+            assert_eq!(
+                coord.neighbors4_iter().collect::<Vec<_>>(),
+                coord.neighbors4()
+            );
+            assert_eq!(
+                coord.neighbors8_iter().collect::<Vec<_>>(),
+                coord.neighbors8()
+            );
+        }
+    }
+
+    #[test]
+    fn bounded() {
+        let c = Coord::new(2, 1);
+        let n: Vec<_> = c.neighbors4_bounded(3, 2).collect();
+        assert_eq!(n, [Coord::new(2, 0), Coord::new(1, 1)]);
+        let n: Vec<_> = c.neighbors8_bounded(3, 2).collect();
+        assert_eq!(n, [Coord::new(2, 0), Coord::new(1, 1), Coord::new(1, 0)]);
+
+        let area = Area::new(Coord::new(2, 1), 2, 2);
+        let n: Vec<_> = c.neighbors8_within(area).collect();
+        assert_eq!(n, [Coord::new(3, 1), Coord::new(3, 2), Coord::new(2, 2)]);
+        let n: Vec<_> = c.neighbors4_within(area).collect();
+        assert_eq!(n, [Coord::new(3, 1), Coord::new(2, 2)]);
+    }
+}
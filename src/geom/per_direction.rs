@@ -0,0 +1,146 @@
+use std::ops::{Index, IndexMut};
+
+use super::{Direction4, Direction8};
+
+macro_rules! per_direction {
+    ($(#[$meta:meta])* $name:ident, $dir:ident, $n:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name<T>(
+            #[doc = concat!("The values, in the order of [`", stringify!($dir), "::DIRECTIONS`].")]
+            pub [T; $n],
+        );
+
+        impl<T> $name<T> {
+            /// Wrap up an array that's in the order of the directions.
+            pub const fn new(values: [T; $n]) -> Self {
+                Self(values)
+            }
+
+            /// Work out the value for each direction.
+            pub fn from_fn(f: impl FnMut($dir) -> T) -> Self {
+                Self($dir::DIRECTIONS.map(f))
+            }
+
+            pub fn get(&self, dir: $dir) -> &T {
+                &self.0[dir.ordinal()]
+            }
+
+            pub fn get_mut(&mut self, dir: $dir) -> &mut T {
+                &mut self.0[dir.ordinal()]
+            }
+
+            /// Returns the old value.
+            pub fn set(&mut self, dir: $dir, val: T) -> T {
+                std::mem::replace(self.get_mut(dir), val)
+            }
+
+            pub fn map<U>(self, f: impl FnMut(T) -> U) -> $name<U> {
+                $name(self.0.map(f))
+            }
+
+            /// Iterate over the values in the order of the directions.
+            pub fn iter(&self) -> std::slice::Iter<'_, T> {
+                self.0.iter()
+            }
+
+            /// Iterate over each direction and its value.
+            pub fn iter_with_dir(&self) -> impl Iterator<Item = ($dir, &T)> {
+                $dir::DIRECTIONS.into_iter().zip(self.0.iter())
+            }
+
+            pub fn iter_mut_with_dir(&mut self) -> impl Iterator<Item = ($dir, &mut T)> {
+                $dir::DIRECTIONS.into_iter().zip(self.0.iter_mut())
+            }
+
+            pub fn into_inner(self) -> [T; $n] {
+                self.0
+            }
+        }
+
+        impl<T> Index<$dir> for $name<T> {
+            type Output = T;
+
+            fn index(&self, dir: $dir) -> &T {
+                self.get(dir)
+            }
+        }
+
+        impl<T> IndexMut<$dir> for $name<T> {
+            fn index_mut(&mut self, dir: $dir) -> &mut T {
+                self.get_mut(dir)
+            }
+        }
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from(values: [T; $n]) -> Self {
+                Self(values)
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $n] {
+            fn from(per: $name<T>) -> Self {
+                per.0
+            }
+        }
+    };
+}
+
+per_direction!(
+    /// One value for each [`Direction4`], like a wall sprite for each side.
+    ///
+    /// ```
+    /// # use aglet::{Direction4, PerDirection4};
+    /// let mut costs = PerDirection4::from_fn(|dir| if dir.is_vertical() { 2 } else { 1 });
+    /// costs[Direction4::North] = 5;
+    /// assert_eq!(costs.into_inner(), [5, 1, 2, 1]);
+    /// ```
+    PerDirection4,
+    Direction4,
+    4
+);
+
+per_direction!(
+    /// One value for each [`Direction8`], like the cost of stepping each way.
+    PerDirection8,
+    Direction8,
+    8
+);
+
+impl<T: Clone> From<PerDirection8<T>> for PerDirection4<T> {
+    /// Keep just the values for the four orthogonal directions.
+    fn from(per: PerDirection8<T>) -> Self {
+        Self::from_fn(|dir| per[Direction8::from(dir)].clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Direction4, Direction8, PerDirection4, PerDirection8};
+
+    #[test]
+    fn indexing() {
+        let mut walls = PerDirection8::new(['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h']);
+        assert_eq!(walls[Direction8::SouthEast], 'd');
+        assert_eq!(walls.set(Direction8::West, 'x'), 'g');
+        *walls.get_mut(Direction8::North) = 'y';
+        assert_eq!(walls.iter().collect::<String>(), "ybcdefxh");
+
+        let orthogonal = PerDirection4::from(walls);
+        assert_eq!(orthogonal[Direction4::West], 'x');
+        assert_eq!(orthogonal.into_inner(), ['y', 'c', 'e', 'x']);
+    }
+
+    #[test]
+    fn iterating() {
+        let mut per = PerDirection4::from_fn(Direction4::ordinal);
+        for (dir, val) in per.iter_mut_with_dir() {
+            *val += dir.flip().ordinal() * 10;
+        }
+        let pairs: Vec<_> = per.iter_with_dir().map(|(d, v)| (d, *v)).collect();
+        assert_eq!(pairs[0], (Direction4::North, 20));
+        assert_eq!(pairs[3], (Direction4::West, 13));
+        assert_eq!(per.map(|v| v % 10), PerDirection4::new([0, 1, 2, 3]));
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+
+use super::{Coord, CoordVec, LineIter};
+
+/// Whether [`PolygonIter`] and [`TriangleIter`] fill their shape in or just
+/// trace around it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolygonMode {
+    /// Every cell inside the shape, plus its outline.
+    #[default]
+    Filled,
+    /// Just the cells along the edges, drawn with [`LineIter`].
+    Outline,
+}
+
+/// Iterates over the cells of a polygon.
+///
+/// The vertices are cells, joined in order with the last one joined back to
+/// the first. When filled, cells inside the polygon are found by the
+/// even-odd rule, so self-intersecting shapes get holes where they overlap,
+/// and the outline is always included. Filled polygons come out in
+/// row-major order with no repeats. Outlines come out in order walking
+/// around the edges, starting at the first vertex.
+///
+/// ```
+/// # use aglet::{CoordVec, PolygonIter};
+/// // An L-shaped room
+/// let room = [(0, 0), (2, 0), (2, 2), (4, 2), (4, 3), (0, 3)].map(CoordVec::from);
+/// assert_eq!(PolygonIter::new(room).count(), 3 * 4 + 2 * 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PolygonIter {
+    cells: Cells,
+}
+
+#[derive(Debug, Clone)]
+enum Cells {
+    /// Each is `(y, first x, last x)`, inclusive.
+    Spans {
+        spans: std::vec::IntoIter<(i32, i32, i32)>,
+        current: Option<(i32, i32, i32)>,
+    },
+    Outline(std::vec::IntoIter<CoordVec>),
+}
+
+impl PolygonIter {
+    /// Fill in the polygon with these vertices.
+    pub fn new(vertices: impl IntoIterator<Item = CoordVec>) -> Self {
+        Self::new_with_mode(vertices, PolygonMode::Filled)
+    }
+
+    pub fn new_with_mode(vertices: impl IntoIterator<Item = CoordVec>, mode: PolygonMode) -> Self {
+        let vertices: Vec<_> = vertices.into_iter().collect();
+        let outline = outline(&vertices);
+        let cells = match mode {
+            PolygonMode::Outline => Cells::Outline(outline.into_iter()),
+            PolygonMode::Filled => Cells::Spans {
+                spans: fill(&vertices, &outline).into_iter(),
+                current: None,
+            },
+        };
+        Self { cells }
+    }
+}
+
+impl Iterator for PolygonIter {
+    type Item = CoordVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.cells {
+            Cells::Outline(cells) => cells.next(),
+            Cells::Spans { spans, current } => {
+                let (y, x, end) = match current.take() {
+                    Some(span) => span,
+                    None => spans.next()?,
+                };
+                if x < end {
+                    *current = Some((y, x + 1, end));
+                }
+                Some(CoordVec::new(x, y))
+            }
+        }
+    }
+}
+
+/// Iterates over the cells of a triangle.
+///
+/// This is just a [`PolygonIter`] with three vertices.
+#[derive(Debug, Clone)]
+pub struct TriangleIter(PolygonIter);
+
+impl TriangleIter {
+    /// Fill in the triangle with these corners.
+    pub fn new(a: CoordVec, b: CoordVec, c: CoordVec) -> Self {
+        Self::new_with_mode(a, b, c, PolygonMode::Filled)
+    }
+
+    pub fn new_with_mode(a: CoordVec, b: CoordVec, c: CoordVec, mode: PolygonMode) -> Self {
+        Self(PolygonIter::new_with_mode([a, b, c], mode))
+    }
+}
+
+impl Iterator for TriangleIter {
+    type Item = CoordVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+}
+
+/// Walk around the edges. [`LineIter`] only does unsigned coordinates, so
+/// shift everything over into positive space first.
+fn outline(vertices: &[CoordVec]) -> Vec<CoordVec> {
+    let Some(first) = vertices.first() else {
+        return Vec::new();
+    };
+    let min_x = vertices.iter().map(|v| v.x).min().unwrap();
+    let min_y = vertices.iter().map(|v| v.y).min().unwrap();
+    let shift = |v: CoordVec| Coord::new((v.x - min_x) as u32, (v.y - min_y) as u32);
+
+    let mut out = Vec::new();
+    for (i, start) in vertices.iter().enumerate() {
+        let end = vertices[(i + 1) % vertices.len()];
+        out.extend(
+            LineIter::new(shift(*start), shift(end))
+                .map(|c| CoordVec::new(c.x as i32 + min_x, c.y as i32 + min_y)),
+        );
+    }
+    if out.is_empty() {
+        // Every vertex is in the same place
+        out.push(*first);
+    }
+    out
+}
+
+/// Find the spans of cells to fill on each row.
+fn fill(vertices: &[CoordVec], outline: &[CoordVec]) -> Vec<(i32, i32, i32)> {
+    let mut rows = BTreeMap::<i32, Vec<(i32, i32)>>::new();
+    for cell in outline {
+        rows.entry(cell.y).or_default().push((cell.x, cell.x));
+    }
+
+    let edges = || {
+        vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .filter(|(a, b)| a.y != b.y)
+    };
+    let (top, bottom) = (rows.keys().next(), rows.keys().next_back());
+    if let (Some(&top), Some(&bottom)) = (top, bottom) {
+        let mut crossings = Vec::new();
+        for y in top..=bottom {
+            crossings.clear();
+            // Count each edge as covering its top row but not its bottom one,
+            // so a row through a vertex crosses it exactly once or twice
+            for (a, b) in edges() {
+                if (a.y <= y) != (b.y <= y) {
+                    let t = (y - a.y) as f64 / (b.y - a.y) as f64;
+                    crossings.push(a.x as f64 + t * (b.x - a.x) as f64);
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                let (start, end) = (pair[0].ceil() as i32, pair[1].floor() as i32);
+                if start <= end {
+                    rows.entry(y).or_default().push((start, end));
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for (y, mut spans) in rows {
+        spans.sort();
+        let mut spans = spans.into_iter();
+        let Some(mut current) = spans.next() else {
+            continue;
+        };
+        for (start, end) in spans {
+            if start <= current.1 + 1 {
+                current.1 = current.1.max(end);
+            } else {
+                out.push((y, current.0, current.1));
+                current = (start, end);
+            }
+        }
+        out.push((y, current.0, current.1));
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CoordVec, PolygonIter, PolygonMode, TriangleIter};
+
+    fn verts(points: &[(i32, i32)]) -> Vec<CoordVec> {
+        points.iter().map(|p| CoordVec::from(*p)).collect()
+    }
+
+    #[test]
+    fn rectangle() {
+        let rect = verts(&[(-1, 0), (2, 0), (2, 2), (-1, 2)]);
+        let filled: Vec<_> = PolygonIter::new(rect.clone()).collect();
+        assert_eq!(filled.len(), 12);
+        assert_eq!(filled[0], CoordVec::new(-1, 0));
+        assert_eq!(filled[11], CoordVec::new(2, 2));
+        assert!(filled
+            .windows(2)
+            .all(|w| (w[0].y, w[0].x) < (w[1].y, w[1].x)));
+
+        let outline: Vec<_> = PolygonIter::new_with_mode(rect, PolygonMode::Outline).collect();
+        assert_eq!(outline.len(), 10);
+        assert_eq!(outline[0], CoordVec::new(-1, 0));
+        assert!(!outline.contains(&CoordVec::new(0, 1)));
+    }
+
+    #[test]
+    fn triangle() {
+        let (a, b, c) = (
+            CoordVec::new(0, 0),
+            CoordVec::new(4, 0),
+            CoordVec::new(0, 4),
+        );
+        let cells: Vec<_> = TriangleIter::new(a, b, c).collect();
+        assert_eq!(cells.len(), 15);
+        assert!(cells.iter().all(|p| p.x >= 0 && p.y >= 0 && p.x + p.y <= 4));
+
+        let outline = TriangleIter::new_with_mode(a, b, c, PolygonMode::Outline);
+        assert_eq!(outline.count(), 12);
+    }
+
+    #[test]
+    fn awkward_shapes() {
+        // A concave arrowhead; the notch shouldn't get filled
+        let arrow = verts(&[(0, 0), (6, 3), (0, 6), (3, 3)]);
+        let cells: Vec<_> = PolygonIter::new(arrow).collect();
+        assert!(cells.contains(&CoordVec::new(4, 3)));
+        assert!(!cells.contains(&CoordVec::new(1, 3)));
+
+        // A bowtie; each half is filled
+        let bowtie = verts(&[(0, 0), (4, 4), (4, 0), (0, 4)]);
+        let cells: Vec<_> = PolygonIter::new(bowtie).collect();
+        assert!(cells.contains(&CoordVec::new(0, 2)));
+        assert!(cells.contains(&CoordVec::new(4, 2)));
+        assert!(!cells.contains(&CoordVec::new(2, 0)));
+
+        assert_eq!(PolygonIter::new([]).count(), 0);
+        let dot = [CoordVec::new(5, -5); 3];
+        assert_eq!(
+            PolygonIter::new(dot).collect::<Vec<_>>(),
+            [CoordVec::new(5, -5)]
+        );
+        let line = verts(&[(0, 0), (3, 0)]);
+        assert_eq!(PolygonIter::new(line).count(), 4);
+    }
+}
@@ -0,0 +1,176 @@
+//! Random sampling of coordinates, with the `rand` feature.
+
+use rand::Rng;
+
+use super::{Area, Coord, UnsignedScalar};
+
+impl<T: UnsignedScalar> Area<T> {
+    /// Pick a coordinate in this area uniformly at random, or `None` if the
+    /// area is empty.
+    pub fn random_coord<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Coord<T>> {
+        if self.is_empty() {
+            return None;
+        }
+        // The offsets are less than the width and height, so they fit back in T
+        let pick = |rng: &mut R, len: T| {
+            let offset = rng.gen_range(0..len.to_usize().unwrap_or(usize::MAX));
+            T::from_usize(offset).unwrap()
+        };
+        Some(Coord {
+            x: self.x1() + pick(rng, self.width),
+            y: self.y1() + pick(rng, self.height),
+        })
+    }
+}
+
+impl Area {
+    /// Scatter random points around this area, all at least `min_distance`
+    /// apart, until there's no more room.
+    ///
+    /// This uses Bridson's Poisson-disc sampling, so the points are spread
+    /// out evenly without looking like a grid. Good for placing trees, loot,
+    /// or monsters.
+    ///
+    /// Panics if `min_distance` isn't positive.
+    pub fn scatter<R: Rng>(&self, min_distance: f64, rng: R) -> Scatter<R> {
+        assert!(min_distance > 0.0, "min_distance must be positive");
+        // Any two points in the same cell would be closer than min_distance,
+        // so each cell has at most one point
+        let cell_size = min_distance / std::f64::consts::SQRT_2;
+        let cells_wide = (self.width as f64 / cell_size).ceil() as usize;
+        let cells_high = (self.height as f64 / cell_size).ceil() as usize;
+        Scatter {
+            area: *self,
+            min_distance,
+            rng,
+            cell_size,
+            cells_wide,
+            cells: vec![None; cells_wide * cells_high],
+            active: Vec::new(),
+            started: false,
+        }
+    }
+}
+
+/// Iterator for [`Area::scatter`].
+#[derive(Debug, Clone)]
+pub struct Scatter<R> {
+    area: Area,
+    min_distance: f64,
+    rng: R,
+    cell_size: f64,
+    cells_wide: usize,
+    /// Background grid for finding nearby points quickly, relative to the
+    /// area's corner.
+    cells: Vec<Option<Coord>>,
+    /// Points that might still have room around them for more.
+    active: Vec<Coord>,
+    started: bool,
+}
+
+impl<R: Rng> Scatter<R> {
+    /// How many candidates to try around a point before giving up on it.
+    const ATTEMPTS: usize = 30;
+
+    fn cell_of(&self, coord: Coord) -> (usize, usize) {
+        let local = coord - self.area.corner;
+        (
+            (local.x as f64 / self.cell_size) as usize,
+            (local.y as f64 / self.cell_size) as usize,
+        )
+    }
+
+    fn has_room(&self, coord: Coord) -> bool {
+        let (cx, cy) = self.cell_of(coord);
+        let cells_high = self.cells.len() / self.cells_wide;
+        for y in cy.saturating_sub(2)..(cy + 3).min(cells_high) {
+            for x in cx.saturating_sub(2)..(cx + 3).min(self.cells_wide) {
+                if let Some(other) = self.cells[y * self.cells_wide + x] {
+                    let dx = coord.x as f64 - other.x as f64;
+                    let dy = coord.y as f64 - other.y as f64;
+                    if dx * dx + dy * dy < self.min_distance * self.min_distance {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn accept(&mut self, coord: Coord) -> Coord {
+        let (cx, cy) = self.cell_of(coord);
+        self.cells[cy * self.cells_wide + cx] = Some(coord);
+        self.active.push(coord);
+        coord
+    }
+}
+
+impl<R: Rng> Iterator for Scatter<R> {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            let first = self.area.random_coord(&mut self.rng)?;
+            return Some(self.accept(first));
+        }
+
+        while !self.active.is_empty() {
+            let idx = self.rng.gen_range(0..self.active.len());
+            let around = self.active[idx];
+            for _ in 0..Self::ATTEMPTS {
+                let angle = self.rng.gen_range(0.0..std::f64::consts::TAU);
+                let radius = self
+                    .rng
+                    .gen_range(self.min_distance..self.min_distance * 2.0);
+                let x = around.x as f64 + angle.cos() * radius;
+                let y = around.y as f64 + angle.sin() * radius;
+                if x < 0.0 || y < 0.0 {
+                    continue;
+                }
+                let candidate = Coord::new(x.round() as u32, y.round() as u32);
+                if self.area.contains(candidate) && self.has_room(candidate) {
+                    return Some(self.accept(candidate));
+                }
+            }
+            self.active.swap_remove(idx);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{Area, Coord};
+
+    #[test]
+    fn random_coord() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let area = Area::new(Coord::new(5, 10), 3, 2);
+        for _ in 0..100 {
+            assert!(area.contains(area.random_coord(&mut rng).unwrap()));
+        }
+        let empty = Area::new(Coord::new(5, 10), 0, 2);
+        assert_eq!(empty.random_coord(&mut rng), None);
+    }
+
+    #[test]
+    fn scatter() {
+        let area = Area::new(Coord::new(3, 7), 40, 30);
+        let points: Vec<_> = area.scatter(4.0, StdRng::seed_from_u64(2)).collect();
+        // It should pack in a decent number of points
+        assert!(points.len() > 20, "only {} points", points.len());
+        for (i, a) in points.iter().enumerate() {
+            assert!(area.contains(*a));
+            for b in &points[i + 1..] {
+                let d = a.to_icoord() - b.to_icoord();
+                assert!(d.x * d.x + d.y * d.y >= 16, "{} and {} too close", a, b);
+            }
+        }
+
+        let tiny = Area::new(Coord::new(0, 0), 1, 1);
+        assert_eq!(tiny.scatter(1.5, StdRng::seed_from_u64(3)).count(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+use super::{CoordVec, Direction4};
+
+/// Iterates over every cell a ray passes through, in order, using
+/// Amanatides and Woo's DDA traversal.
+///
+/// Unlike [`LineIter`](super::LineIter), the ray can start anywhere inside
+/// a cell and head in any direction, and it never skips a cell it clips a
+/// corner of. This is what grid raycasting renderers and bullets want.
+///
+/// Cell `(x, y)` covers the points from `x` to `x + 1` and from `y` to
+/// `y + 1`, with y going down like everywhere else. The ray goes on forever,
+/// so use something like `take_while` to stop it.
+#[derive(Debug, Clone)]
+pub struct RayIter {
+    cell: CoordVec,
+    step: CoordVec,
+    /// How far along the ray the next x and y boundaries are.
+    t_max: (f32, f32),
+    /// How far along the ray it is between x and y boundaries.
+    t_delta: (f32, f32),
+    started: bool,
+}
+
+/// One cell entered by a [`RayIter`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayStep {
+    pub coord: CoordVec,
+    /// The side of this cell the ray came in through, or `None` for the cell
+    /// it started in.
+    ///
+    /// For example, a ray going east enters cells through their west face.
+    pub face: Option<Direction4>,
+    /// How far along the ray the cell was entered, in multiples of the
+    /// direction vector. If the direction has length 1, this is the distance
+    /// from the start.
+    pub t: f32,
+}
+
+impl RayIter {
+    /// Cast a ray from `start` towards `direction`.
+    ///
+    /// If `direction` is zero, this only yields the starting cell.
+    pub fn new(start: (f32, f32), direction: (f32, f32)) -> Self {
+        let axis = |pos: f32, dir: f32| {
+            let cell = pos.floor();
+            if dir > 0.0 {
+                (1, (cell + 1.0 - pos) / dir, 1.0 / dir)
+            } else if dir < 0.0 {
+                (-1, (pos - cell) / -dir, 1.0 / -dir)
+            } else {
+                (0, f32::INFINITY, f32::INFINITY)
+            }
+        };
+        let (step_x, t_max_x, t_delta_x) = axis(start.0, direction.0);
+        let (step_y, t_max_y, t_delta_y) = axis(start.1, direction.1);
+        Self {
+            cell: CoordVec::new(start.0.floor() as i32, start.1.floor() as i32),
+            step: CoordVec::new(step_x, step_y),
+            t_max: (t_max_x, t_max_y),
+            t_delta: (t_delta_x, t_delta_y),
+            started: false,
+        }
+    }
+}
+
+impl Iterator for RayIter {
+    type Item = RayStep;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some(RayStep {
+                coord: self.cell,
+                face: None,
+                t: 0.0,
+            });
+        }
+
+        let (t, face) = if self.t_max.0.is_infinite() && self.t_max.1.is_infinite() {
+            return None;
+        } else if self.t_max.0 <= self.t_max.1 {
+            // Going exactly through a corner steps along x first
+            let t = self.t_max.0;
+            self.cell.x += self.step.x;
+            self.t_max.0 += self.t_delta.0;
+            let face = if self.step.x > 0 {
+                Direction4::West
+            } else {
+                Direction4::East
+            };
+            (t, face)
+        } else {
+            let t = self.t_max.1;
+            self.cell.y += self.step.y;
+            self.t_max.1 += self.t_delta.1;
+            let face = if self.step.y > 0 {
+                Direction4::North
+            } else {
+                Direction4::South
+            };
+            (t, face)
+        };
+        Some(RayStep {
+            coord: self.cell,
+            face: Some(face),
+            t,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CoordVec, Direction4, RayIter};
+
+    fn cells(ray: RayIter, n: usize) -> Vec<(i32, i32, Option<Direction4>)> {
+        ray.take(n)
+            .map(|step| (step.coord.x, step.coord.y, step.face))
+            .collect()
+    }
+
+    #[test]
+    fn straight() {
+        let ray = RayIter::new((0.5, 0.5), (1.0, 0.0));
+        assert_eq!(
+            cells(ray.clone(), 3),
+            [
+                (0, 0, None),
+                (1, 0, Some(Direction4::West)),
+                (2, 0, Some(Direction4::West))
+            ]
+        );
+        let ts: Vec<_> = ray.take(3).map(|step| step.t).collect();
+        assert_eq!(ts, [0.0, 0.5, 1.5]);
+
+        let up = RayIter::new((-0.5, -0.5), (0.0, -2.0));
+        assert_eq!(
+            cells(up, 2),
+            [(-1, -1, None), (-1, -2, Some(Direction4::South))]
+        );
+
+        let still = RayIter::new((3.2, 1.0), (0.0, 0.0));
+        assert_eq!(cells(still, 5), [(3, 1, None)]);
+    }
+
+    #[test]
+    fn shallow() {
+        // Bresenham would skip (1, 1); DDA catches the corner
+        let ray = RayIter::new((0.1, 0.9), (3.0, 1.0));
+        let path: Vec<_> = ray
+            .take_while(|step| step.coord.x < 3)
+            .map(|step| step.coord)
+            .collect();
+        assert_eq!(
+            path,
+            [
+                CoordVec::new(0, 0),
+                CoordVec::new(0, 1),
+                CoordVec::new(1, 1),
+                CoordVec::new(2, 1),
+            ]
+        );
+
+        let back = RayIter::new((2.5, 2.5), (-1.0, -1.0));
+        assert_eq!(
+            cells(back, 3),
+            [
+                (2, 2, None),
+                (1, 2, Some(Direction4::East)),
+                (1, 1, Some(Direction4::South))
+            ]
+        );
+    }
+}
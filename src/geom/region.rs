@@ -0,0 +1,681 @@
+use std::collections::VecDeque;
+
+use super::{
+  Area, BitGrid, BitGridOnes, Connectivity, Coord, CoordVec, Direction8,
+};
+
+/// An arbitrary set of cells.
+///
+/// This is stored as a bounding [`Area`] plus a mask saying which cells in the
+/// bounds are in the region, so it's cheap for compact blobs of cells like
+/// rooms or flood-fill results.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawRegion"))]
+pub struct Region {
+  bounds: Area,
+  /// The same size as `bounds`, relative to its corner
+  mask: BitGrid,
+}
+
+impl Region {
+  /// Make an empty region.
+  pub fn new() -> Self {
+    Self {
+      bounds: Area::new(Coord::ZERO, 0, 0),
+      mask: BitGrid::new(0, 0),
+    }
+  }
+
+  /// Make a region covering every cell in the area.
+  pub fn from_area(area: Area) -> Self {
+    Self {
+      bounds: area,
+      mask: BitGrid::new(area.width, area.height).invert(),
+    }
+  }
+
+  /// Make an empty region with room for cells inside `bounds`.
+  ///
+  /// Like `Vec::with_capacity`, inserting cells inside the bounds won't need
+  /// to reallocate.
+  pub fn with_bounds(bounds: Area) -> Self {
+    Self {
+      bounds,
+      mask: BitGrid::new(bounds.width, bounds.height),
+    }
+  }
+
+  /// Flood-fill outwards from `start` orthagonally, including every cell
+  /// inside `bounds` for which `include` returns true.
+  ///
+  /// If `start` itself isn't included, the region is empty.
+  pub fn flood_fill<F: FnMut(Coord) -> bool>(
+    start: Coord,
+    bounds: Area,
+    include: F,
+  ) -> Self {
+    Self::flood_fill_with(start, bounds, Connectivity::Four, include)
+  }
+
+  /// Like [`Self::flood_fill`], but with the given connectivity.
+  pub fn flood_fill_with<F: FnMut(Coord) -> bool>(
+    start: Coord,
+    bounds: Area,
+    connectivity: Connectivity,
+    mut include: F,
+  ) -> Self {
+    let mut out = Region::with_bounds(bounds);
+    if !bounds.contains(start) || !include(start) {
+      return Region::new();
+    }
+
+    let mut queue = VecDeque::from([start]);
+    out.set(start, true);
+    while let Some(here) = queue.pop_front() {
+      for next in connectivity.neighbors_within(here, bounds) {
+        if !out.contains(next) && include(next) {
+          out.set(next, true);
+          queue.push_back(next);
+        }
+      }
+    }
+    out.shrink_to_fit();
+    out
+  }
+
+  /// An area that every cell in this region is inside.
+  ///
+  /// This might be larger than it needs to be; call [`Self::shrink_to_fit`]
+  /// to make it as small as possible.
+  pub fn bounds(&self) -> Area {
+    self.bounds
+  }
+
+  pub fn contains(&self, coord: Coord) -> bool {
+    match self.local(coord) {
+      Some(local) => self.mask.get(local),
+      None => false,
+    }
+  }
+
+  /// The number of cells in this region.
+  pub fn len(&self) -> usize {
+    self.mask.count_ones()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    !self.mask.any()
+  }
+
+  /// Add a cell to this region, growing the bounds if needed.
+  ///
+  /// Like a `Vec`, the bounds grow by at least double at a time, so adding
+  /// lots of cells one by one doesn't copy the mask every time.
+  ///
+  /// Returns whether the cell was newly added.
+  pub fn insert(&mut self, coord: Coord) -> bool {
+    if !self.bounds.contains(coord) {
+      self.rebound(self.grown_bounds(coord));
+    }
+    let was = self.contains(coord);
+    self.set(coord, true);
+    !was
+  }
+
+  /// Remove a cell from this region.
+  ///
+  /// Returns whether the cell was in the region.
+  pub fn remove(&mut self, coord: Coord) -> bool {
+    let was = self.contains(coord);
+    if was {
+      self.set(coord, false);
+    }
+    was
+  }
+
+  /// Iterate over all the cells in this region, in row-major order.
+  pub fn iter(&self) -> RegionIter<'_> {
+    RegionIter {
+      corner: self.bounds.corner,
+      ones: self.mask.iter_ones(),
+    }
+  }
+
+  /// Get a region with every cell in either region.
+  pub fn union(&self, other: &Region) -> Region {
+    if self.is_empty() {
+      return other.clone();
+    } else if other.is_empty() {
+      return self.clone();
+    }
+    let mut out = self.clone();
+    out.rebound(self.bounds.union_bounds(&other.bounds));
+    for coord in other.iter() {
+      out.set(coord, true);
+    }
+    out
+  }
+
+  /// Get a region with only the cells in both regions.
+  pub fn intersection(&self, other: &Region) -> Region {
+    let mut out = Region::with_bounds(self.bounds);
+    for coord in self.iter().filter(|c| other.contains(*c)) {
+      out.set(coord, true);
+    }
+    out.shrink_to_fit();
+    out
+  }
+
+  /// Get a region with the cells in this region but not in `other`.
+  pub fn difference(&self, other: &Region) -> Region {
+    let mut out = self.clone();
+    for coord in other.iter() {
+      out.remove(coord);
+    }
+    out.shrink_to_fit();
+    out
+  }
+
+  /// Move every cell in this region by the given offset.
+  ///
+  /// Returns `None` if any cell would end up negative.
+  pub fn translated(&self, offset: CoordVec) -> Option<Region> {
+    // The bounds can be bigger than the cells, so only the real cells
+    // decide whether the move fits
+    let mut out = self.clone();
+    out.shrink_to_fit();
+    if !out.is_empty() {
+      let axis = |at: u32, by: i32| u32::try_from(at as i64 + by as i64).ok();
+      let corner = out.bounds.corner;
+      out.bounds.corner =
+        Coord::new(axis(corner.x, offset.x)?, axis(corner.y, offset.y)?);
+    }
+    Some(out)
+  }
+
+  /// Is every cell in this region connected to every other cell?
+  ///
+  /// Empty regions count as contiguous.
+  pub fn is_contiguous(&self, connectivity: Connectivity) -> bool {
+    match self.iter().next() {
+      Some(start) => {
+        let filled =
+          Region::flood_fill_with(start, self.bounds, connectivity, |c| {
+            self.contains(c)
+          });
+        filled.len() == self.len()
+      }
+      None => true,
+    }
+  }
+
+  /// Find the pockets of empty cells completely enclosed by this region.
+  ///
+  /// Empty cells only leak out orthagonally, so a diagonal gap in the walls
+  /// still counts as sealed. Each pocket is returned as a separate region.
+  pub fn holes(&self) -> Vec<Region> {
+    let bounds = self.bounds;
+    let mut outside = Region::with_bounds(bounds);
+    let mut queue = VecDeque::new();
+    for coord in bounds.edges() {
+      if !self.contains(coord) {
+        outside.set(coord, true);
+        queue.push_back(coord);
+      }
+    }
+    while let Some(here) = queue.pop_front() {
+      for next in here.neighbors4_within(bounds) {
+        if !self.contains(next) && !outside.contains(next) {
+          outside.set(next, true);
+          queue.push_back(next);
+        }
+      }
+    }
+
+    let mut seen = Region::with_bounds(bounds);
+    let mut holes = Vec::new();
+    for coord in bounds {
+      if self.contains(coord) || outside.contains(coord) || seen.contains(coord)
+      {
+        continue;
+      }
+      let hole = Region::flood_fill(coord, bounds, |c| !self.contains(c));
+      for c in hole.iter() {
+        seen.set(c, true);
+      }
+      holes.push(hole);
+    }
+    holes
+  }
+
+  /// Add all the [holes](Self::holes) to this region.
+  pub fn fill_holes(&mut self) {
+    for hole in self.holes() {
+      for coord in hole.iter() {
+        self.set(coord, true);
+      }
+    }
+  }
+
+  /// Walk clockwise around the outside edge of this region.
+  ///
+  /// This starts at the first cell in row-major order and follows the
+  /// outline of the 8-connected blob it's in, until it gets back to the start.
+  /// Each step is the cell along with the direction to the next cell.
+  ///
+  /// Cells can show up more than once if the region is only one cell thick
+  /// somewhere, because the outline has to go there and back again.
+  /// A lone cell has nowhere to step, so it (and an empty region) gives an
+  /// empty outline.
+  pub fn trace_outline(&self) -> Vec<(Coord, Direction8)> {
+    let mut out = Vec::new();
+    let Some(start) = self.iter().next() else {
+      return out;
+    };
+
+    // Moore neighbor tracing. `back` points from the current cell to an
+    // empty cell we came from; we sweep clockwise from there.
+    // Nothing is left of the first cell in row-major order, so start there.
+    let mut here = start;
+    let mut back = Direction8::West;
+    loop {
+      let step = (1..=8)
+        .map(|i| back.rotate_by(i))
+        .find(|dir| here.offset8(*dir).is_some_and(|next| self.contains(next)));
+      let Some(step) = step else {
+        return out;
+      };
+      if here == start && out.first().is_some_and(|(_, first)| *first == step) {
+        return out;
+      }
+      out.push((here, step));
+      here = here.offset8(step).unwrap();
+      // The last empty cell we checked, relative to the new cell
+      back = if step.ordinal() % 2 == 0 {
+        step.rotate_by(-2)
+      } else {
+        step.rotate_by(-3)
+      };
+    }
+  }
+
+  /// Shrink the bounds to be as small as possible.
+  pub fn shrink_to_fit(&mut self) {
+    let mut cells = self.iter();
+    let first = match cells.next() {
+      Some(it) => it,
+      None => {
+        *self = Region::new();
+        return;
+      }
+    };
+    let (mut x1, mut y1, mut x2, mut y2) = (first.x, first.y, first.x, first.y);
+    for c in cells {
+      x1 = x1.min(c.x);
+      y1 = y1.min(c.y);
+      x2 = x2.max(c.x);
+      y2 = y2.max(c.y);
+    }
+    self.rebound(Area::new(Coord::new(x1, y1), x2 - x1 + 1, y2 - y1 + 1));
+  }
+
+  /// Change the bounds, dropping any cells outside the new ones.
+  fn rebound(&mut self, bounds: Area) {
+    let mut out = Region::with_bounds(bounds);
+    for coord in self.iter() {
+      if bounds.contains(coord) {
+        out.set(coord, true);
+      }
+    }
+    *self = out;
+  }
+
+  /// Get bounds that fit `coord` as well as the current ones, at least
+  /// doubling along each axis that has to grow.
+  fn grown_bounds(&self, coord: Coord) -> Area {
+    if self.bounds.width == 0 || self.bounds.height == 0 {
+      return Area::new(coord, 1, 1);
+    }
+    // Returns the new start and length along one axis
+    let grow = |start: u32, len: u32, at: u32| {
+      if at < start {
+        let new_start = start.saturating_sub((start - at).max(len));
+        (new_start, len + (start - new_start))
+      } else if at - start >= len {
+        let extra = (at - start - len).saturating_add(1).max(len);
+        (start, len.saturating_add(extra))
+      } else {
+        (start, len)
+      }
+    };
+    let (x, width) = grow(self.bounds.corner.x, self.bounds.width, coord.x);
+    let (y, height) = grow(self.bounds.corner.y, self.bounds.height, coord.y);
+    Area::new(Coord::new(x, y), width, height)
+  }
+
+  /// Panics if out of bounds
+  fn set(&mut self, coord: Coord, val: bool) {
+    let local = self.local(coord).unwrap();
+    self.mask.set(local, val);
+  }
+
+  /// Get the coordinate relative to the corner of the bounds.
+  fn local(&self, coord: Coord) -> Option<Coord> {
+    self
+      .bounds
+      .contains(coord)
+      .then(|| coord - self.bounds.corner)
+  }
+}
+
+impl Default for Region {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Two regions are equal if they contain the same cells, regardless of bounds.
+impl PartialEq for Region {
+  fn eq(&self, other: &Self) -> bool {
+    self.len() == other.len() && self.iter().all(|c| other.contains(c))
+  }
+}
+
+impl Eq for Region {}
+
+impl From<Area> for Region {
+  fn from(area: Area) -> Self {
+    Region::from_area(area)
+  }
+}
+
+impl FromIterator<Coord> for Region {
+  fn from_iter<I: IntoIterator<Item = Coord>>(iter: I) -> Self {
+    let coords: Vec<Coord> = iter.into_iter().collect();
+    let mut out = Region::new();
+    if let Some(first) = coords.first() {
+      let bounds = coords.iter().fold(Area::new(*first, 1, 1), |acc, c| {
+        acc.union_bounds(&Area::new(*c, 1, 1))
+      });
+      out = Region::with_bounds(bounds);
+      for coord in coords {
+        out.set(coord, true);
+      }
+    }
+    out
+  }
+}
+
+impl Extend<Coord> for Region {
+  fn extend<I: IntoIterator<Item = Coord>>(&mut self, iter: I) {
+    let other: Region = iter.into_iter().collect();
+    *self = self.union(&other);
+  }
+}
+
+impl<'a> IntoIterator for &'a Region {
+  type Item = Coord;
+
+  type IntoIter = RegionIter<'a>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+/// Iterator over the cells in a [`Region`].
+pub struct RegionIter<'a> {
+  corner: Coord,
+  ones: BitGridOnes<'a>,
+}
+
+impl<'a> Iterator for RegionIter<'a> {
+  type Item = Coord;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    self.ones.next().map(|local| self.corner + local)
+  }
+}
+
+/// What a [`Region`] deserializes from, before checking the mask fits the
+/// bounds.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "Region")]
+struct RawRegion {
+  bounds: Area,
+  mask: BitGrid,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawRegion> for Region {
+  type Error = String;
+
+  fn try_from(raw: RawRegion) -> Result<Self, Self::Error> {
+    let RawRegion { bounds, mask } = raw;
+    if (mask.width(), mask.height()) != (bounds.width, bounds.height) {
+      return Err(format!(
+        "region mask is {}x{}, but its bounds are {}x{}",
+        mask.width(),
+        mask.height(),
+        bounds.width,
+        bounds.height
+      ));
+    }
+    Ok(Region { bounds, mask })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Area, Connectivity, Coord, CoordVec, Direction8, Region};
+
+  #[test]
+  fn set_ops() {
+    let a = Region::from_area(Area::new(Coord::new(0, 0), 3, 3));
+    let b = Region::from_area(Area::new(Coord::new(2, 2), 3, 3));
+
+    let union = a.union(&b);
+    assert_eq!(union.len(), 17);
+    assert!(union.contains(Coord::new(0, 0)));
+    assert!(union.contains(Coord::new(4, 4)));
+    assert!(!union.contains(Coord::new(4, 0)));
+
+    let inter = a.intersection(&b);
+    assert_eq!(inter.iter().collect::<Vec<_>>(), [Coord::new(2, 2)]);
+    assert_eq!(inter.bounds().width, 1);
+
+    let diff = a.difference(&b);
+    assert_eq!(diff.len(), 8);
+    assert!(!diff.contains(Coord::new(2, 2)));
+  }
+
+  #[test]
+  fn building() {
+    let mut r = Region::new();
+    assert!(r.is_empty());
+    assert!(r.insert(Coord::new(5, 5)));
+    assert!(!r.insert(Coord::new(5, 5)));
+    assert!(r.insert(Coord::new(2, 7)));
+    assert_eq!(r.len(), 2);
+    assert_eq!(
+      r.iter().collect::<Vec<_>>(),
+      [Coord::new(5, 5), Coord::new(2, 7)]
+    );
+
+    let collected: Region =
+      [Coord::new(5, 5), Coord::new(2, 7)].into_iter().collect();
+    assert_eq!(r, collected);
+
+    assert!(r.remove(Coord::new(5, 5)));
+    r.shrink_to_fit();
+    assert_eq!(r.bounds().corner, Coord::new(2, 7));
+
+    let moved = r.translated(CoordVec::new(-2, 1)).unwrap();
+    assert_eq!(moved.iter().collect::<Vec<_>>(), [Coord::new(0, 8)]);
+    assert!(r.translated(CoordVec::new(-3, 0)).is_none());
+  }
+
+  #[test]
+  fn translating_loose_bounds() {
+    // Growing leftwards leaves room in the bounds left of the cells
+    let mut r = Region::new();
+    for x in [5, 4, 3] {
+      r.insert(Coord::new(x, 0));
+    }
+    assert!(r.bounds().corner.x < 3);
+    let moved = r.translated(CoordVec::new(-3, 0)).unwrap();
+    assert_eq!(
+      moved.iter().collect::<Vec<_>>(),
+      [Coord::new(0, 0), Coord::new(1, 0), Coord::new(2, 0)]
+    );
+    assert!(r.translated(CoordVec::new(-4, 0)).is_none());
+
+    let mut wide = Region::with_bounds(Area::new(Coord::ZERO, 10, 10));
+    wide.insert(Coord::new(5, 5));
+    let moved = wide.translated(CoordVec::new(-5, -5)).unwrap();
+    assert_eq!(moved.iter().collect::<Vec<_>>(), [Coord::ZERO]);
+  }
+
+  #[test]
+  fn growing() {
+    let mut r = Region::new();
+    for x in (0..100).rev() {
+      r.insert(Coord::new(x, 3));
+    }
+    assert_eq!(r.len(), 100);
+    assert!(r.bounds().width >= 100);
+    assert_eq!(r.bounds().height, 1);
+
+    // Bounds double each way they have to grow
+    let mut r = Region::new();
+    r.insert(Coord::new(10, 10));
+    r.insert(Coord::new(11, 10));
+    assert_eq!(r.bounds(), Area::new(Coord::new(10, 10), 2, 1));
+    r.insert(Coord::new(12, 10));
+    assert_eq!(r.bounds(), Area::new(Coord::new(10, 10), 4, 1));
+    r.insert(Coord::new(11, 9));
+    assert_eq!(r.bounds(), Area::new(Coord::new(10, 9), 4, 2));
+    // It can't grow past 0
+    r.insert(Coord::new(9, 10));
+    assert_eq!(r.bounds(), Area::new(Coord::new(6, 9), 8, 2));
+    r.insert(Coord::new(0, 0));
+    assert_eq!(r.bounds(), Area::new(Coord::ZERO, 14, 11));
+    assert_eq!(r.len(), 6);
+    r.shrink_to_fit();
+    assert_eq!(r.bounds(), Area::new(Coord::ZERO, 13, 11));
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde() {
+    let r: Region = [Coord::new(5, 5), Coord::new(2, 7)].into_iter().collect();
+    let json = serde_json::to_string(&r).unwrap();
+    assert_eq!(serde_json::from_str::<Region>(&json).unwrap(), r);
+
+    let bad = json.replace(
+      r#""width":4,"height":3,"words""#,
+      r#""width":4,"height":4,"words""#,
+    );
+    // Still a valid bit grid, just the wrong size
+    assert_ne!(bad, json);
+    assert!(serde_json::from_str::<Region>(&bad).is_err());
+  }
+
+  #[test]
+  fn contiguity() {
+    let diagonal: Region =
+      [Coord::new(0, 0), Coord::new(1, 1)].into_iter().collect();
+    assert!(!diagonal.is_contiguous(Connectivity::Four));
+    assert!(diagonal.is_contiguous(Connectivity::Eight));
+    assert!(Region::new().is_contiguous(Connectivity::Four));
+    assert!(Region::from_area(Area::new(Coord::new(3, 3), 4, 2))
+      .is_contiguous(Connectivity::Four));
+  }
+
+  #[test]
+  fn holes() {
+    // A ring with one cell hole, and a 2-cell pocket
+    //  #####
+    //  #.#.#
+    //  ###.#
+    //  #####
+    let mut walls = Region::from_area(Area::new(Coord::new(1, 1), 5, 4));
+    for c in [Coord::new(2, 2), Coord::new(4, 2), Coord::new(4, 3)] {
+      walls.remove(c);
+    }
+    let mut holes = walls.holes();
+    holes.sort_by_key(|h| h.len());
+    assert_eq!(holes.len(), 2);
+    assert_eq!(holes[0].iter().collect::<Vec<_>>(), [Coord::new(2, 2)]);
+    assert_eq!(
+      holes[1].iter().collect::<Vec<_>>(),
+      [Coord::new(4, 2), Coord::new(4, 3)]
+    );
+
+    // Open up the pocket to the outside
+    walls.remove(Coord::new(5, 3));
+    assert_eq!(walls.holes().len(), 1);
+
+    walls.fill_holes();
+    assert!(walls.holes().is_empty());
+    assert!(walls.contains(Coord::new(2, 2)));
+    assert!(!walls.contains(Coord::new(4, 2)));
+  }
+
+  #[test]
+  fn outline() {
+    let square = Region::from_area(Area::new(Coord::new(1, 1), 2, 2));
+    assert_eq!(
+      square.trace_outline(),
+      [
+        (Coord::new(1, 1), Direction8::East),
+        (Coord::new(2, 1), Direction8::South),
+        (Coord::new(2, 2), Direction8::West),
+        (Coord::new(1, 2), Direction8::North),
+      ]
+    );
+
+    // A line has to double back on itself
+    let line = Region::from_area(Area::new(Coord::new(0, 0), 3, 1));
+    let coords: Vec<_> = line.trace_outline().iter().map(|s| s.0).collect();
+    assert_eq!(
+      coords,
+      [
+        Coord::new(0, 0),
+        Coord::new(1, 0),
+        Coord::new(2, 0),
+        Coord::new(1, 0),
+      ]
+    );
+
+    // Diagonal steps, and cells inside the blob aren't visited
+    let mut diamond = Region::new();
+    for (x, y) in [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)] {
+      diamond.insert(Coord::new(x, y));
+    }
+    let outline = diamond.trace_outline();
+    assert_eq!(outline.len(), 4);
+    assert!(outline.iter().all(|(c, _)| *c != Coord::new(1, 1)));
+    assert_eq!(outline[0], (Coord::new(1, 0), Direction8::SouthEast));
+
+    assert!(Region::from_area(Area::new(Coord::new(5, 5), 1, 1))
+      .trace_outline()
+      .is_empty());
+    assert!(Region::new().trace_outline().is_empty());
+  }
+
+  #[test]
+  fn flood_fill() {
+    // A wall down x = 2
+    let bounds = Area::new(Coord::ZERO, 5, 4);
+    let filled = Region::flood_fill(Coord::new(0, 0), bounds, |c| c.x != 2);
+    assert_eq!(filled.len(), 8);
+    assert!(filled.iter().all(|c| c.x < 2));
+    assert_eq!(filled.bounds(), Area::new(Coord::ZERO, 2, 4));
+
+    let empty = Region::flood_fill(Coord::new(2, 0), bounds, |c| c.x != 2);
+    assert!(empty.is_empty());
+  }
+}
@@ -0,0 +1,242 @@
+use super::CoordVec;
+
+/// Iterates over the square ring of cells at exactly Chebyshev distance
+/// `radius` from a center, clockwise starting from due north.
+///
+/// A ring of radius 0 is just the center.
+#[derive(Debug, Clone)]
+pub struct RingIter {
+    center: CoordVec,
+    radius: i32,
+    cursor: i32,
+}
+
+impl RingIter {
+    pub fn new(center: CoordVec, radius: u32) -> Self {
+        Self {
+            center,
+            radius: radius as i32,
+            cursor: 0,
+        }
+    }
+
+    fn total(&self) -> i32 {
+        (self.radius * 8).max(1)
+    }
+}
+
+impl Iterator for RingIter {
+    type Item = CoordVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.total() {
+            return None;
+        }
+        let r = self.radius;
+        let CoordVec { x, y } = self.center;
+        // Count around from the top-left corner, but start in the middle of
+        // the top side
+        let k = (self.cursor + r) % self.total();
+        self.cursor += 1;
+        if r == 0 {
+            return Some(self.center);
+        }
+        let (side, off) = (k / (2 * r), k % (2 * r));
+        Some(match side {
+            0 => CoordVec::new(x - r + off, y - r),
+            1 => CoordVec::new(x + r, y - r + off),
+            2 => CoordVec::new(x + r - off, y + r),
+            _ => CoordVec::new(x - r, y + r - off),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.total() - self.cursor).max(0) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for RingIter {}
+
+/// Iterates over the diamond of cells at exactly Manhattan distance `radius`
+/// from a center, clockwise starting from due north.
+///
+/// A diamond of radius 0 is just the center.
+#[derive(Debug, Clone)]
+pub struct DiamondIter {
+    center: CoordVec,
+    radius: i32,
+    cursor: i32,
+}
+
+impl DiamondIter {
+    pub fn new(center: CoordVec, radius: u32) -> Self {
+        Self {
+            center,
+            radius: radius as i32,
+            cursor: 0,
+        }
+    }
+
+    fn total(&self) -> i32 {
+        (self.radius * 4).max(1)
+    }
+}
+
+impl Iterator for DiamondIter {
+    type Item = CoordVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.total() {
+            return None;
+        }
+        let r = self.radius;
+        let CoordVec { x, y } = self.center;
+        let k = self.cursor;
+        self.cursor += 1;
+        if r == 0 {
+            return Some(self.center);
+        }
+        let (side, off) = (k / r, k % r);
+        Some(match side {
+            0 => CoordVec::new(x + off, y - r + off),
+            1 => CoordVec::new(x + r - off, y + off),
+            2 => CoordVec::new(x - off, y + r - off),
+            _ => CoordVec::new(x - r + off, y - off),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.total() - self.cursor).max(0) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for DiamondIter {}
+
+/// Iterates over every cell within Chebyshev distance `max_radius` of a
+/// center, spiraling outwards clockwise.
+///
+/// So the cells come out nearest first, which is just the thing for finding
+/// the closest free spot to put something down:
+///
+/// ```
+/// # use aglet::{CoordVec, SpiralIter};
+/// let blocked = |c: CoordVec| c.x.abs() + c.y.abs() < 2;
+/// let free = SpiralIter::new(CoordVec::new(0, 0), 10).find(|c| !blocked(*c));
+/// assert_eq!(free, Some(CoordVec::new(1, -1)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpiralIter {
+    ring: RingIter,
+    max_radius: u32,
+}
+
+impl SpiralIter {
+    pub fn new(center: CoordVec, max_radius: u32) -> Self {
+        Self {
+            ring: RingIter::new(center, 0),
+            max_radius,
+        }
+    }
+}
+
+impl Iterator for SpiralIter {
+    type Item = CoordVec;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(coord) = self.ring.next() {
+                return Some(coord);
+            }
+            let radius = self.ring.radius as u32 + 1;
+            if radius > self.max_radius {
+                return None;
+            }
+            self.ring = RingIter::new(self.ring.center, radius);
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let outer = self.max_radius as usize * 2 + 1;
+        let inner = self.ring.radius as usize * 2 + 1;
+        let len = outer * outer - inner * inner + self.ring.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for SpiralIter {}
+
+#[cfg(test)]
+mod test {
+    use crate::{CoordVec, DiamondIter, RingIter, SpiralIter};
+
+    fn pairs(it: impl Iterator<Item = CoordVec>) -> Vec<(i32, i32)> {
+        it.map(|c| (c.x, c.y)).collect()
+    }
+
+    #[test]
+    fn ring() {
+        let center = CoordVec::new(5, 5);
+        assert_eq!(pairs(RingIter::new(center, 0)), [(5, 5)]);
+        assert_eq!(
+            pairs(RingIter::new(center, 1)),
+            [
+                (5, 4),
+                (6, 4),
+                (6, 5),
+                (6, 6),
+                (5, 6),
+                (4, 6),
+                (4, 5),
+                (4, 4)
+            ]
+        );
+        let ring: Vec<_> = RingIter::new(center, 3).collect();
+        assert_eq!(ring.len(), 24);
+        assert_eq!(ring[0], CoordVec::new(5, 2));
+        for coord in &ring {
+            assert_eq!(coord.chebyshev_distance(center), 3);
+        }
+        let mut dedup = ring.clone();
+        dedup.sort_by_key(|c| (c.x, c.y));
+        dedup.dedup();
+        assert_eq!(dedup.len(), 24);
+    }
+
+    #[test]
+    fn diamond() {
+        let center = CoordVec::new(0, 0);
+        assert_eq!(pairs(DiamondIter::new(center, 0)), [(0, 0)]);
+        assert_eq!(
+            pairs(DiamondIter::new(center, 2)),
+            [
+                (0, -2),
+                (1, -1),
+                (2, 0),
+                (1, 1),
+                (0, 2),
+                (-1, 1),
+                (-2, 0),
+                (-1, -1)
+            ]
+        );
+        let mut diamond = DiamondIter::new(center, 5);
+        assert_eq!(diamond.len(), 20);
+        assert!(diamond.all(|c| c.manhattan_distance(center) == 5));
+    }
+
+    #[test]
+    fn spiral() {
+        let center = CoordVec::new(-1, 2);
+        let mut spiral = SpiralIter::new(center, 2);
+        assert_eq!(spiral.len(), 25);
+        assert_eq!(spiral.next(), Some(center));
+        assert_eq!(spiral.next(), Some(CoordVec::new(-1, 1)));
+        assert_eq!(spiral.len(), 23);
+        let rest: Vec<_> = spiral.collect();
+        assert_eq!(rest.len(), 23);
+        let distances: Vec<_> = rest.iter().map(|c| c.chebyshev_distance(center)).collect();
+        assert!(distances.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
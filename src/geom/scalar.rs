@@ -0,0 +1,161 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    num::{ParseIntError, TryFromIntError},
+    ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Integer types that can be used as the components of a [`Coord`] or [`CoordVec`].
+///
+/// You shouldn't need to implement this yourself; it's implemented for all the
+/// primitive integers up to 64 bits (plus `usize` and `isize`).
+///
+/// [`Coord`]: crate::Coord
+/// [`CoordVec`]: crate::CoordVec
+pub trait Scalar:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + Default
+    + FromStr<Err = ParseIntError>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Convert this to a `usize`, or `None` if it doesn't fit.
+    fn to_usize(self) -> Option<usize>;
+    /// Convert a `usize` to this, or `None` if it doesn't fit.
+    fn from_usize(n: usize) -> Option<Self>;
+    /// Convert this to a float, possibly losing precision.
+    fn to_f64(self) -> f64;
+
+    fn saturating_add(self, other: Self) -> Self;
+    fn saturating_sub(self, other: Self) -> Self;
+    fn saturating_mul(self, other: Self) -> Self;
+}
+
+/// Scalars for [`Coord`](crate::Coord), like `u32`.
+pub trait UnsignedScalar: Scalar {
+    /// The signed type of the same width, like `i32` for `u32`.
+    type Signed: SignedScalar<Unsigned = Self>;
+
+    /// Reinterpret this as its signed counterpart, like `as` would.
+    fn as_signed(self) -> Self::Signed;
+
+    /// The absolute difference between this and `other`.
+    fn abs_diff(self, other: Self) -> Self;
+}
+
+/// Scalars for [`CoordVec`](crate::CoordVec), like `i32`.
+pub trait SignedScalar: Scalar + Neg<Output = Self> {
+    /// The unsigned type of the same width, like `u32` for `i32`.
+    type Unsigned: UnsignedScalar<Signed = Self>;
+
+    /// Convert this to its unsigned counterpart, failing if it's negative.
+    fn to_unsigned(self) -> Result<Self::Unsigned, TryFromIntError>;
+
+    /// The absolute difference between this and `other`, which always fits
+    /// in the unsigned counterpart.
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+    /// The absolute value of this, saturating at `MAX` instead of overflowing.
+    fn saturating_abs(self) -> Self;
+}
+
+macro_rules! impl_scalars {
+    ($($unsigned:ty => $signed:ty),* $(,)?) => {
+        $(
+            impl Scalar for $unsigned {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn to_usize(self) -> Option<usize> {
+                    self.try_into().ok()
+                }
+                fn from_usize(n: usize) -> Option<Self> {
+                    n.try_into().ok()
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn saturating_add(self, other: Self) -> Self {
+                    <$unsigned>::saturating_add(self, other)
+                }
+                fn saturating_sub(self, other: Self) -> Self {
+                    <$unsigned>::saturating_sub(self, other)
+                }
+                fn saturating_mul(self, other: Self) -> Self {
+                    <$unsigned>::saturating_mul(self, other)
+                }
+            }
+
+            impl Scalar for $signed {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn to_usize(self) -> Option<usize> {
+                    self.try_into().ok()
+                }
+                fn from_usize(n: usize) -> Option<Self> {
+                    n.try_into().ok()
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn saturating_add(self, other: Self) -> Self {
+                    <$signed>::saturating_add(self, other)
+                }
+                fn saturating_sub(self, other: Self) -> Self {
+                    <$signed>::saturating_sub(self, other)
+                }
+                fn saturating_mul(self, other: Self) -> Self {
+                    <$signed>::saturating_mul(self, other)
+                }
+            }
+
+            impl UnsignedScalar for $unsigned {
+                type Signed = $signed;
+
+                fn as_signed(self) -> Self::Signed {
+                    self as $signed
+                }
+                fn abs_diff(self, other: Self) -> Self {
+                    <$unsigned>::abs_diff(self, other)
+                }
+            }
+
+            impl SignedScalar for $signed {
+                type Unsigned = $unsigned;
+
+                fn to_unsigned(self) -> Result<Self::Unsigned, TryFromIntError> {
+                    self.try_into()
+                }
+                fn abs_diff(self, other: Self) -> Self::Unsigned {
+                    <$signed>::abs_diff(self, other)
+                }
+                fn saturating_abs(self) -> Self {
+                    <$signed>::saturating_abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalars! {
+    u8 => i8,
+    u16 => i16,
+    u32 => i32,
+    u64 => i64,
+    usize => isize,
+}
@@ -0,0 +1,343 @@
+use super::{CoordVec, Transform};
+
+/// Where one coordinate space sits inside its parent: shifted, scaled, and
+/// turned.
+///
+/// Going from this space to the parent, a coordinate is
+/// [transformed](Transform::apply_vec) around the origin, scaled, then
+/// shifted by the offset. Going back does the reverse.
+///
+/// The scale is a fraction: each cell here is `num / den` cells of the
+/// parent. So a map drawn with 16-pixel tiles is a space with scale `16 / 1`
+/// inside screen space, and a local map where 64 cells make up one overworld
+/// tile has scale `1 / 64` inside the overworld. When scaling down, cells
+/// round towards negative infinity, so every parent cell covered by a cell
+/// here maps back to that cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawCoordSpace"))]
+pub struct CoordSpace {
+    pub offset: CoordVec,
+    pub transform: Transform,
+    scale_num: u32,
+    scale_den: u32,
+}
+
+impl CoordSpace {
+    /// Make a space with its origin at `offset` in the parent, and the same
+    /// scale and orientation.
+    pub fn new(offset: CoordVec) -> Self {
+        Self {
+            offset,
+            transform: Transform::IDENTITY,
+            scale_num: 1,
+            scale_den: 1,
+        }
+    }
+
+    pub fn with_transform(self, transform: Transform) -> Self {
+        Self { transform, ..self }
+    }
+
+    /// Set how many parent cells each cell here is, as `num / den`.
+    ///
+    /// Panics if either is zero.
+    pub fn with_scale(self, num: u32, den: u32) -> Self {
+        assert!(num > 0 && den > 0, "scale must be positive");
+        Self {
+            scale_num: num,
+            scale_den: den,
+            ..self
+        }
+    }
+
+    /// Get the scale as `(num, den)`.
+    pub fn scale(&self) -> (u32, u32) {
+        (self.scale_num, self.scale_den)
+    }
+
+    /// Convert a coordinate in this space into the parent's space.
+    pub fn to_parent(&self, coord: CoordVec) -> CoordVec {
+        let turned = self.transform.apply_vec(coord);
+        scale(turned, self.scale_num, self.scale_den) + self.offset
+    }
+
+    /// Convert a coordinate in the parent's space into this space.
+    pub fn from_parent(&self, coord: CoordVec) -> CoordVec {
+        let scaled = scale(coord - self.offset, self.scale_den, self.scale_num);
+        self.transform.inverse().apply_vec(scaled)
+    }
+}
+
+impl Default for CoordSpace {
+    fn default() -> Self {
+        Self::new(CoordVec::new(0, 0))
+    }
+}
+
+fn scale(v: CoordVec, num: u32, den: u32) -> CoordVec {
+    let axis = |x: i32| (x as i64 * num as i64).div_euclid(den as i64) as i32;
+    CoordVec::new(axis(v.x), axis(v.y))
+}
+
+/// Identifies a space in a [`CoordSpaces`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpaceId(usize);
+
+/// A tree of [`CoordSpace`]s, for converting between any two of them.
+///
+/// This keeps all the arithmetic for going between, say, overworld, local
+/// map, and screen coordinates in one place. Every space is placed inside a
+/// parent, all the way up to the root space.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawCoordSpaces"))]
+pub struct CoordSpaces {
+    /// The root has no entry; space `n` is at index `n - 1`.
+    spaces: Vec<(SpaceId, CoordSpace)>,
+}
+
+impl CoordSpaces {
+    /// The space everything else is ultimately placed in.
+    pub const ROOT: SpaceId = SpaceId(0);
+
+    /// Make a registry with only the root space.
+    pub fn new() -> Self {
+        Self { spaces: Vec::new() }
+    }
+
+    /// Add a space placed inside `parent`.
+    ///
+    /// Panics if the parent doesn't exist.
+    pub fn add(&mut self, parent: SpaceId, space: CoordSpace) -> SpaceId {
+        assert!(parent.0 <= self.spaces.len(), "parent space doesn't exist");
+        self.spaces.push((parent, space));
+        SpaceId(self.spaces.len())
+    }
+
+    /// Get a space's parent and placement, or `None` for the root.
+    pub fn get(&self, id: SpaceId) -> Option<(SpaceId, &CoordSpace)> {
+        let (parent, space) = self.spaces.get(id.0.checked_sub(1)?)?;
+        Some((*parent, space))
+    }
+
+    /// Move a space around in its parent.
+    ///
+    /// Panics if the space doesn't exist, or is the root.
+    pub fn get_mut(&mut self, id: SpaceId) -> &mut CoordSpace {
+        assert!(id != Self::ROOT, "the root space can't move");
+        &mut self.spaces[id.0 - 1].1
+    }
+
+    /// Convert a coordinate in the `from` space into the `to` space.
+    ///
+    /// This goes up from `from` to the closest space both are inside, then
+    /// down to `to`, so scaling down and back up again only happens when it
+    /// has to.
+    ///
+    /// Panics if either space doesn't exist.
+    pub fn convert(&self, coord: CoordVec, from: SpaceId, to: SpaceId) -> CoordVec {
+        let down = self.ancestors(to);
+        let mut coord = coord;
+        let mut here = from;
+        while !down.contains(&here) {
+            let (parent, space) = self.get(here).expect("root is an ancestor of everything");
+            coord = space.to_parent(coord);
+            here = parent;
+        }
+        let meet = down.iter().position(|id| *id == here).unwrap();
+        for id in down[..meet].iter().rev() {
+            coord = self.get(*id).unwrap().1.from_parent(coord);
+        }
+        coord
+    }
+
+    /// Get a space and all its parents, from it up to the root.
+    fn ancestors(&self, id: SpaceId) -> Vec<SpaceId> {
+        assert!(id.0 <= self.spaces.len(), "space doesn't exist");
+        let mut out = vec![id];
+        let mut here = id;
+        while let Some((parent, _)) = self.get(here) {
+            out.push(parent);
+            here = parent;
+        }
+        out
+    }
+}
+
+impl Default for CoordSpaces {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// What a [`CoordSpace`] deserializes from, before checking the scale.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "CoordSpace")]
+struct RawCoordSpace {
+    offset: CoordVec,
+    transform: Transform,
+    scale_num: u32,
+    scale_den: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawCoordSpace> for CoordSpace {
+    type Error = &'static str;
+
+    fn try_from(raw: RawCoordSpace) -> Result<Self, Self::Error> {
+        if raw.scale_num == 0 || raw.scale_den == 0 {
+            return Err("scale must be positive");
+        }
+        Ok(CoordSpace {
+            offset: raw.offset,
+            transform: raw.transform,
+            scale_num: raw.scale_num,
+            scale_den: raw.scale_den,
+        })
+    }
+}
+
+/// What a [`CoordSpaces`] deserializes from, before checking it's a tree.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "CoordSpaces")]
+struct RawCoordSpaces {
+    spaces: Vec<(SpaceId, CoordSpace)>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawCoordSpaces> for CoordSpaces {
+    type Error = String;
+
+    fn try_from(raw: RawCoordSpaces) -> Result<Self, Self::Error> {
+        // Parents always come before their children, which also rules out
+        // cycles
+        for (idx, (parent, _)) in raw.spaces.iter().enumerate() {
+            let id = idx + 1;
+            if parent.0 >= id {
+                return Err(format!(
+                    "space {} has parent {}, which doesn't come before it",
+                    id, parent.0
+                ));
+            }
+        }
+        Ok(CoordSpaces { spaces: raw.spaces })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CoordSpace, CoordSpaces, CoordVec, Transform};
+
+    #[test]
+    fn single_space() {
+        let space = CoordSpace::new(CoordVec::new(10, 20)).with_scale(16, 1);
+        assert_eq!(space.to_parent(CoordVec::new(1, 2)), CoordVec::new(26, 52));
+        assert_eq!(
+            space.from_parent(CoordVec::new(26, 52)),
+            CoordVec::new(1, 2)
+        );
+        assert_eq!(
+            space.from_parent(CoordVec::new(41, 67)),
+            CoordVec::new(1, 2)
+        );
+        assert_eq!(
+            space.from_parent(CoordVec::new(9, 20)),
+            CoordVec::new(-1, 0)
+        );
+
+        let turned = CoordSpace::new(CoordVec::new(5, 5)).with_transform(Transform::rotation(1));
+        // Clockwise with y down turns east into south
+        assert_eq!(turned.to_parent(CoordVec::new(2, 0)), CoordVec::new(5, 7));
+        assert_eq!(turned.from_parent(CoordVec::new(5, 7)), CoordVec::new(2, 0));
+    }
+
+    #[test]
+    fn nested() {
+        let mut spaces = CoordSpaces::new();
+        // The root is the overworld, with a 64x64 local map for tile (3, -2)
+        let local = spaces.add(
+            CoordSpaces::ROOT,
+            CoordSpace::new(CoordVec::new(3, -2)).with_scale(1, 64),
+        );
+        let overworld = CoordSpaces::ROOT;
+        // Screen pixels, 8 to a local cell, scrolled over a bit
+        let pixels = spaces.add(
+            local,
+            CoordSpace::new(CoordVec::new(-4, -4)).with_scale(1, 8),
+        );
+
+        assert_eq!(
+            spaces.convert(CoordVec::new(63, 0), local, overworld),
+            CoordVec::new(3, -2)
+        );
+        assert_eq!(
+            spaces.convert(CoordVec::new(64, 0), local, overworld),
+            CoordVec::new(4, -2)
+        );
+        assert_eq!(
+            spaces.convert(CoordVec::new(3, -2), overworld, local),
+            CoordVec::new(0, 0)
+        );
+        // Going between a space and its child doesn't round through the root
+        assert_eq!(
+            spaces.convert(CoordVec::new(10, 20), local, pixels),
+            CoordVec::new(112, 192)
+        );
+        assert_eq!(
+            spaces.convert(CoordVec::new(117, 199), pixels, local),
+            CoordVec::new(10, 20)
+        );
+        assert_eq!(
+            spaces.convert(CoordVec::new(5, 5), pixels, pixels),
+            CoordVec::new(5, 5)
+        );
+
+        spaces.get_mut(local).offset = CoordVec::new(0, 0);
+        assert_eq!(
+            spaces.convert(CoordVec::new(64, 0), local, overworld),
+            CoordVec::new(1, 0)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut spaces = CoordSpaces::new();
+        let local = spaces.add(
+            CoordSpaces::ROOT,
+            CoordSpace::new(CoordVec::new(3, -2)).with_scale(1, 64),
+        );
+        spaces.add(local, CoordSpace::new(CoordVec::new(-4, -4)));
+        let json = serde_json::to_string(&spaces).unwrap();
+        let back: CoordSpaces = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.get(local), spaces.get(local));
+
+        let space = |num, den| {
+            format!(
+                r#"{{"offset":{{"x":0,"y":0}},"transform":{},"scale_num":{},"scale_den":{}}}"#,
+                serde_json::to_string(&Transform::IDENTITY).unwrap(),
+                num,
+                den
+            )
+        };
+        assert!(serde_json::from_str::<CoordSpace>(&space(1, 2)).is_ok());
+        assert!(serde_json::from_str::<CoordSpace>(&space(1, 0)).is_err());
+        assert!(serde_json::from_str::<CoordSpace>(&space(0, 1)).is_err());
+
+        let spaces = |parents: &[usize]| {
+            let entries: Vec<_> = parents
+                .iter()
+                .map(|p| format!("[{},{}]", p, space(1, 1)))
+                .collect();
+            serde_json::from_str::<CoordSpaces>(&format!(r#"{{"spaces":[{}]}}"#, entries.join(",")))
+        };
+        assert!(spaces(&[0, 1, 1]).is_ok());
+        // Space 2 can't be its own parent, or be inside space 3
+        assert!(spaces(&[0, 2]).is_err());
+        assert!(spaces(&[0, 3, 0]).is_err());
+    }
+}
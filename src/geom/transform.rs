@@ -0,0 +1,234 @@
+use super::{
+  Coord, CoordVec, Direction16, Direction4, Direction8, Rotation, SignedScalar,
+  UnsignedScalar,
+};
+
+/// One of the 8 ways to turn and flip a square: a rotation by some number of
+/// quarter turns, optionally mirrored.
+///
+/// Mirroring happens first, left-to-right, and then the rotation clockwise.
+/// This is handy for storing the orientation of pieces and tiles, since
+/// transforms can be composed and undone without any trig.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+  /// Always in 0..4
+  quarter_turns: u8,
+  mirrored: bool,
+}
+
+impl Transform {
+  /// Leave everything alone.
+  pub const IDENTITY: Transform = Transform {
+    quarter_turns: 0,
+    mirrored: false,
+  };
+  /// Mirror left-to-right, so east and west swap.
+  pub const MIRROR: Transform = Transform {
+    quarter_turns: 0,
+    mirrored: true,
+  };
+
+  /// Make a transform that turns this many quarter turns clockwise, mirrored
+  /// first if `mirrored` is set.
+  pub fn new(steps_clockwise: i32, mirrored: bool) -> Self {
+    Self {
+      quarter_turns: steps_clockwise.rem_euclid(4) as u8,
+      mirrored,
+    }
+  }
+
+  /// Make a transform that turns this many quarter turns clockwise.
+  /// Negative numbers go counter-clockwise.
+  pub fn rotation(steps_clockwise: i32) -> Self {
+    Self::new(steps_clockwise, false)
+  }
+
+  /// All 8 transforms: the 4 rotations, then the 4 mirrored rotations.
+  pub fn all() -> [Transform; 8] {
+    std::array::from_fn(|i| Transform::new(i as i32 % 4, i >= 4))
+  }
+
+  /// How many quarter turns clockwise this rotates, from 0 to 3.
+  pub fn quarter_turns(self) -> u8 {
+    self.quarter_turns
+  }
+
+  /// Does this mirror things?
+  pub fn is_mirrored(self) -> bool {
+    self.mirrored
+  }
+
+  /// Does this swap width and height?
+  pub fn swaps_axes(self) -> bool {
+    self.quarter_turns % 2 == 1
+  }
+
+  /// Get the transform that does `self`, and then `next`.
+  pub fn then(self, next: Transform) -> Transform {
+    // Mirroring turns clockwise into counter-clockwise
+    let turns = if next.mirrored {
+      next.quarter_turns as i32 - self.quarter_turns as i32
+    } else {
+      next.quarter_turns as i32 + self.quarter_turns as i32
+    };
+    Transform::new(turns, self.mirrored != next.mirrored)
+  }
+
+  /// Get the transform that undoes this one.
+  pub fn inverse(self) -> Transform {
+    if self.mirrored {
+      // Mirrored transforms are all reflections, so they undo themselves
+      self
+    } else {
+      Transform::rotation(-(self.quarter_turns as i32))
+    }
+  }
+
+  /// Transform an offset around the origin.
+  pub fn apply_vec<T: SignedScalar>(self, v: CoordVec<T>) -> CoordVec<T> {
+    let v = if self.mirrored {
+      CoordVec { x: -v.x, y: v.y }
+    } else {
+      v
+    };
+    v.rotate_by(self.quarter_turns as i32)
+  }
+
+  /// Transform a cell inside a `width` by `height` box into the matching cell
+  /// of the transformed box.
+  ///
+  /// The transformed box has its width and height swapped if
+  /// [`Self::swaps_axes`]; see [`Self::apply_size`].
+  pub fn apply_coord<T: UnsignedScalar>(
+    self,
+    coord: Coord<T>,
+    width: T,
+    height: T,
+  ) -> Coord<T> {
+    let one = T::ONE;
+    let Coord { mut x, mut y } = coord;
+    let (mut w, mut h) = (width, height);
+    if self.mirrored {
+      x = w - one - x;
+    }
+    for _ in 0..self.quarter_turns {
+      (x, y) = (h - one - y, x);
+      (w, h) = (h, w);
+    }
+    Coord { x, y }
+  }
+
+  /// Get the size of a `width` by `height` box after transforming it.
+  pub fn apply_size<T>(self, width: T, height: T) -> (T, T) {
+    if self.swaps_axes() {
+      (height, width)
+    } else {
+      (width, height)
+    }
+  }
+
+  pub fn apply_dir4(self, dir: Direction4) -> Direction4 {
+    let dir = if self.mirrored && dir.is_horizontal() {
+      dir.flip()
+    } else {
+      dir
+    };
+    dir.rotate_by(self.quarter_turns as i32)
+  }
+
+  pub fn apply_dir8(self, dir: Direction8) -> Direction8 {
+    let dir = if self.mirrored {
+      Direction8::DIRECTIONS[(8 - dir.ordinal()) % 8]
+    } else {
+      dir
+    };
+    dir.rotate_by(self.quarter_turns as i32 * 2)
+  }
+
+  pub fn apply_dir16(self, dir: Direction16) -> Direction16 {
+    let dir = if self.mirrored {
+      Direction16::DIRECTIONS[(16 - dir.ordinal()) % 16]
+    } else {
+      dir
+    };
+    dir.rotate_by(self.quarter_turns as i32 * 4)
+  }
+}
+
+impl From<Rotation> for Transform {
+  fn from(rot: Rotation) -> Self {
+    Transform::rotation(rot.steps_clockwise())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    Coord, CoordVec, Direction16, Direction4, Direction8, Rotation, Transform,
+  };
+
+  #[test]
+  fn composition() {
+    let v = CoordVec::new(2, -1);
+    for a in Transform::all() {
+      assert_eq!(a.then(a.inverse()), Transform::IDENTITY);
+      assert_eq!(a.inverse().then(a), Transform::IDENTITY);
+      for b in Transform::all() {
+        let ab = a.then(b);
+        assert_eq!(ab.apply_vec(v), b.apply_vec(a.apply_vec(v)));
+        for dir in Direction8::DIRECTIONS {
+          assert_eq!(ab.apply_dir8(dir), b.apply_dir8(a.apply_dir8(dir)));
+        }
+      }
+    }
+    assert_eq!(
+      Transform::from(Rotation::Clockwise).then(Transform::rotation(-1)),
+      Transform::IDENTITY
+    );
+  }
+
+  #[test]
+  fn directions_agree_with_vecs() {
+    for t in Transform::all() {
+      for dir in Direction4::DIRECTIONS {
+        assert_eq!(t.apply_dir4(dir).deltas(), t.apply_vec(dir.deltas()));
+      }
+      for dir in Direction8::DIRECTIONS {
+        assert_eq!(t.apply_dir8(dir).deltas(), t.apply_vec(dir.deltas()));
+      }
+      for dir in Direction16::DIRECTIONS {
+        assert_eq!(t.apply_dir16(dir).deltas(), t.apply_vec(dir.deltas()));
+      }
+    }
+    assert_eq!(
+      Transform::MIRROR.apply_dir8(Direction8::NorthEast),
+      Direction8::NorthWest
+    );
+  }
+
+  #[test]
+  fn coords() {
+    // a b .
+    // . . c
+    let (w, h) = (3, 2);
+    let cw = Transform::rotation(1);
+    assert_eq!(cw.apply_size(w, h), (2, 3));
+    assert_eq!(cw.apply_coord(Coord::new(0, 0), w, h), Coord::new(1, 0));
+    assert_eq!(cw.apply_coord(Coord::new(2, 1), w, h), Coord::new(0, 2));
+    assert_eq!(
+      Transform::MIRROR.apply_coord(Coord::new(0, 1), w, h),
+      Coord::new(2, 1)
+    );
+
+    for a in Transform::all() {
+      for b in Transform::all() {
+        let (w2, h2) = a.apply_size(w, h);
+        for coord in crate::Area::new(Coord::new(0, 0), w, h) {
+          let step = b.apply_coord(a.apply_coord(coord, w, h), w2, h2);
+          assert_eq!(a.then(b).apply_coord(coord, w, h), step);
+        }
+      }
+    }
+  }
+}
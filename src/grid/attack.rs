@@ -0,0 +1,277 @@
+use std::collections::VecDeque;
+
+use crate::{
+  BitGrid, Connectivity, Coord, CoordVec, Direction8, Direction8Set,
+};
+
+use super::Grid;
+
+/// How a piece moves and attacks, for [`Grid::danger_map`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mobility {
+  /// Slides in a straight line in any of these directions, like a rook.
+  /// The range is how far each ray goes.
+  Slider(Direction8Set),
+  /// Jumps by any of these offsets, like a knight.
+  /// The range is how many jumps it can chain together; it can only land
+  /// on empty cells between jumps.
+  Leaper(Vec<CoordVec>),
+  /// Walks through empty cells, then attacks a neighbor.
+  /// The range is how many steps it can take first.
+  Walker(Connectivity),
+}
+
+/// Attack masks, treating every filled cell in the grid as a blocker.
+///
+/// The masks are [`BitGrid`]s the same size as the grid.
+///
+/// These are handy for chess-like movement, or showing the player which cells
+/// an enemy could hit.
+impl<T> Grid<T> {
+  /// Get every cell a sliding piece at `from` could reach by moving in a
+  /// straight line in any of `dirs`, like a rook or bishop.
+  ///
+  /// Each ray stops at the first filled cell, which is included (so it can be
+  /// captured), or at the edge of the grid. `from` itself isn't included.
+  pub fn slider_attacks<D: Into<Direction8>>(
+    &self,
+    from: Coord,
+    dirs: impl IntoIterator<Item = D>,
+  ) -> BitGrid {
+    self.slider_attacks_limited(from, dirs, u32::MAX)
+  }
+
+  /// Like [`Self::slider_attacks`], but each ray goes at most `range` cells.
+  pub fn slider_attacks_limited<D: Into<Direction8>>(
+    &self,
+    from: Coord,
+    dirs: impl IntoIterator<Item = D>,
+    range: u32,
+  ) -> BitGrid {
+    let mut out = BitGrid::new(self.width, self.height);
+    for dir in dirs {
+      let dir = dir.into();
+      let mut here = from;
+      for _ in 0..range {
+        match here.offset8(dir) {
+          Some(next) if self.is_coord_valid(next) => here = next,
+          _ => break,
+        }
+        out.set(here, true);
+        if self.contains(here) {
+          break;
+        }
+      }
+    }
+    out
+  }
+
+  /// Get every cell a leaping piece at `from` could land on by jumping by
+  /// any of `offsets`, like a knight.
+  ///
+  /// Leapers jump over everything, so the only cells left out are the ones
+  /// off the grid.
+  pub fn leaper_attacks(
+    &self,
+    from: Coord,
+    offsets: impl IntoIterator<Item = CoordVec>,
+  ) -> BitGrid {
+    let mut out = BitGrid::new(self.width, self.height);
+    for offset in offsets {
+      if let Some(target) = (from.to_icoord() + offset).to_coord() {
+        if self.is_coord_valid(target) {
+          out.set(target, true);
+        }
+      }
+    }
+    out
+  }
+}
+
+impl<T> Grid<T> {
+  /// Get every cell reachable from `from` in at most `steps` steps through
+  /// empty cells, including `from` itself.
+  pub fn reachable(
+    &self,
+    from: Coord,
+    connectivity: Connectivity,
+    steps: u32,
+  ) -> BitGrid {
+    self.chain_moves(from, steps, |here| {
+      connectivity
+        .neighbors_within(here, self.area())
+        .filter(|next| !self.contains(*next))
+        .collect()
+    })
+  }
+
+  /// Count how many pieces could attack each cell next turn.
+  ///
+  /// Each piece is given as its position, how it moves, and its range; see
+  /// [`Mobility`] for what the range means for each kind. Filled cells in this
+  /// grid block movement. Cells nobody threatens are left empty, and pieces
+  /// don't threaten their own cell.
+  pub fn danger_map(&self, enemies: &[(Coord, Mobility, u32)]) -> Grid<u32> {
+    let mut out = Grid::new(self.width, self.height);
+    for (from, mobility, range) in enemies {
+      let threatened = match mobility {
+        Mobility::Slider(dirs) => {
+          self.slider_attacks_limited(*from, dirs.iter(), *range)
+        }
+        Mobility::Leaper(offsets) => self.chain_moves(*from, *range, |here| {
+          self
+            .leaper_attacks(here, offsets.iter().copied())
+            .iter_ones()
+            .collect()
+        }),
+        Mobility::Walker(connectivity) => {
+          let mut hit = BitGrid::new(self.width, self.height);
+          let walked = self.reachable(*from, *connectivity, *range);
+          for here in walked.iter_ones() {
+            for next in connectivity.neighbors_within(here, self.area()) {
+              hit.set(next, true);
+            }
+          }
+          hit
+        }
+      };
+      for coord in threatened.iter_ones().filter(|c| c != from) {
+        *out.get_or_insert(coord, 0) += 1;
+      }
+    }
+    out
+  }
+
+  /// Breadth-first search from `from`, taking up to `steps` moves.
+  ///
+  /// Every cell landed on is included, but only empty cells can be moved on
+  /// from.
+  fn chain_moves<F: FnMut(Coord) -> Vec<Coord>>(
+    &self,
+    from: Coord,
+    steps: u32,
+    mut moves: F,
+  ) -> BitGrid {
+    let mut out = BitGrid::new(self.width, self.height);
+    out.set(from, true);
+    let mut queue = VecDeque::from([(from, 0)]);
+    while let Some((here, dist)) = queue.pop_front() {
+      if dist >= steps || (here != from && self.contains(here)) {
+        continue;
+      }
+      for next in moves(here) {
+        if !out.set(next, true) {
+          queue.push_back((next, dist + 1));
+        }
+      }
+    }
+    out
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    Connectivity, Coord, CoordVec, Direction4, Direction8, Grid, Mobility,
+  };
+
+  #[test]
+  fn sliders() {
+    // .....
+    // .#...
+    // ..R.#
+    // .....
+    let mut grid = Grid::new(5, 4);
+    grid.insert(Coord::new(1, 1), ());
+    grid.insert(Coord::new(4, 2), ());
+    let from = Coord::new(2, 2);
+
+    let rook = grid.slider_attacks(from, Direction4::DIRECTIONS);
+    let mut cells: Vec<_> = rook.iter_ones().map(|c| (c.x, c.y)).collect();
+    cells.sort();
+    assert_eq!(
+      cells,
+      [(0, 2), (1, 2), (2, 0), (2, 1), (2, 3), (3, 2), (4, 2)]
+    );
+
+    let bishop = grid.slider_attacks(
+      from,
+      [
+        Direction8::NorthEast,
+        Direction8::SouthEast,
+        Direction8::SouthWest,
+        Direction8::NorthWest,
+      ],
+    );
+    let mut cells: Vec<_> = bishop.iter_ones().map(|c| (c.x, c.y)).collect();
+    cells.sort();
+    assert_eq!(cells, [(1, 1), (1, 3), (3, 1), (3, 3), (4, 0)]);
+
+    let king = grid.slider_attacks_limited(from, Direction8::DIRECTIONS, 1);
+    assert_eq!(king.count_ones(), 8);
+    assert!(!king.get(from));
+    assert_eq!((king.width(), king.height()), (5, 4));
+  }
+
+  #[test]
+  fn leapers() {
+    let grid: Grid<()> = Grid::new(8, 8);
+    let knight = [
+      (1, 2),
+      (2, 1),
+      (2, -1),
+      (1, -2),
+      (-1, -2),
+      (-2, -1),
+      (-2, 1),
+      (-1, 2),
+    ]
+    .map(|(x, y)| CoordVec::new(x, y));
+    assert_eq!(
+      grid.leaper_attacks(Coord::new(4, 4), knight).count_ones(),
+      8
+    );
+    let corner = grid.leaper_attacks(Coord::new(0, 0), knight);
+    let mut cells: Vec<_> = corner.iter_ones().map(|c| (c.x, c.y)).collect();
+    cells.sort();
+    assert_eq!(cells, [(1, 2), (2, 1)]);
+  }
+
+  #[test]
+  fn danger() {
+    // R...
+    // .##.
+    // ....
+    let mut grid = Grid::new(4, 3);
+    grid.insert(Coord::new(1, 1), ());
+    grid.insert(Coord::new(2, 1), ());
+
+    let reach = grid.reachable(Coord::new(0, 0), Connectivity::Four, 2);
+    let mut cells: Vec<_> = reach.iter_ones().map(|c| (c.x, c.y)).collect();
+    cells.sort();
+    assert_eq!(cells, [(0, 0), (0, 1), (0, 2), (1, 0), (2, 0)]);
+
+    let rook = (
+      Coord::new(0, 0),
+      Mobility::Slider(Direction8::East | Direction8::South),
+      3,
+    );
+    let king = (Coord::new(3, 2), Mobility::Walker(Connectivity::Eight), 0);
+    let knight = (
+      Coord::new(3, 0),
+      Mobility::Leaper(vec![CoordVec::new(-2, 1), CoordVec::new(-1, 2)]),
+      1,
+    );
+    let danger = grid.danger_map(&[rook, king, knight]);
+
+    assert_eq!(danger.get(Coord::new(0, 0)), None);
+    assert_eq!(danger.get(Coord::new(3, 0)), Some(&1));
+    assert_eq!(danger.get(Coord::new(0, 2)), Some(&1));
+    assert_eq!(danger.get(Coord::new(2, 1)), Some(&1));
+    assert_eq!(danger.get(Coord::new(1, 1)), Some(&1));
+    // Both the king and the knight
+    assert_eq!(danger.get(Coord::new(2, 2)), Some(&2));
+    assert_eq!(danger.get(Coord::new(1, 2)), None);
+    assert_eq!(danger.iter().count(), 9);
+  }
+}
@@ -0,0 +1,156 @@
+use crate::{Coord, Direction8};
+
+use super::{torus::wrap, Grid};
+
+/// A cell in a [`Grid`], along with its eight neighbors.
+///
+/// This is what the rule gets in [`Grid::step_automaton`].
+#[derive(Debug)]
+pub struct Neighborhood<'a, T> {
+  grid: &'a Grid<T>,
+  coord: Coord,
+  wrapping: bool,
+}
+
+// Derive would require T: Clone
+impl<'a, T> Clone for Neighborhood<'a, T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<'a, T> Copy for Neighborhood<'a, T> {}
+
+impl<'a, T> Neighborhood<'a, T> {
+  pub fn new(grid: &'a Grid<T>, coord: Coord) -> Self {
+    Self {
+      grid,
+      coord,
+      wrapping: false,
+    }
+  }
+
+  /// Make a neighborhood whose neighbors wrap around the edges of the grid,
+  /// like in a [`TorusGrid`](super::TorusGrid).
+  pub fn new_wrapping(grid: &'a Grid<T>, coord: Coord) -> Self {
+    Self {
+      grid,
+      coord,
+      wrapping: true,
+    }
+  }
+
+  /// The coordinate of the center cell.
+  pub fn coord(&self) -> Coord {
+    self.coord
+  }
+
+  /// The value in the center cell.
+  pub fn value(&self) -> Option<&'a T> {
+    self.grid.get(self.coord)
+  }
+
+  /// The value in the neighbor in the given direction.
+  ///
+  /// Neighbors out of bounds of the grid are treated as empty, unless this
+  /// neighborhood wraps.
+  pub fn get(&self, dir: Direction8) -> Option<&'a T> {
+    if self.wrapping {
+      let (width, height) = (self.grid.width(), self.grid.height());
+      self
+        .grid
+        .get(wrap(self.coord.to_icoord() + dir, width, height))
+    } else {
+      self.grid.get(self.coord.offset8(dir)?)
+    }
+  }
+
+  /// Iterate over all eight neighbors, in the order of
+  /// [`Direction8::DIRECTIONS`].
+  pub fn iter(&self) -> impl Iterator<Item = (Direction8, Option<&'a T>)> {
+    let this = *self;
+    Direction8::DIRECTIONS
+      .into_iter()
+      .map(move |dir| (dir, this.get(dir)))
+  }
+
+  /// Count the filled neighbors.
+  pub fn count_filled(&self) -> usize {
+    self.iter().filter(|(_, val)| val.is_some()).count()
+  }
+
+  /// Count the filled neighbors matching the predicate.
+  pub fn count<F: Fn(&T) -> bool>(&self, pred: F) -> usize {
+    self
+      .iter()
+      .filter(|(_, val)| val.is_some_and(&pred))
+      .count()
+  }
+}
+
+impl<T> Grid<T> {
+  /// Run one generation of a cellular automaton over the grid.
+  ///
+  /// `rule` is called once for every slot in the grid, filled or not, and
+  /// returns what that slot should hold in the next generation. It always sees
+  /// the *previous* generation, so you don't need to clone the grid yourself
+  /// to avoid reading cells you've already updated.
+  pub fn step_automaton<F>(&mut self, mut rule: F)
+  where
+    F: FnMut(&Neighborhood<'_, T>) -> Option<T>,
+  {
+    let next = (0..self.spots.len())
+      .map(|idx| rule(&Neighborhood::new(self, self.coord_of(idx))))
+      .collect();
+    self.spots = next;
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, Grid};
+
+  #[test]
+  fn blinker() {
+    let mut grid = Grid::new(5, 5);
+    for x in 1..4 {
+      grid.insert(Coord::new(x, 2), ());
+    }
+
+    let life = |nb: &super::Neighborhood<'_, ()>| match (
+      nb.value(),
+      nb.count_filled(),
+    ) {
+      (Some(()), 2 | 3) | (None, 3) => Some(()),
+      _ => None,
+    };
+
+    grid.step_automaton(life);
+    let mut alive: Vec<_> = grid.iter().map(|(c, _)| c).collect();
+    alive.sort_by_key(|c| (c.y, c.x));
+    assert_eq!(
+      alive,
+      [Coord::new(2, 1), Coord::new(2, 2), Coord::new(2, 3)]
+    );
+
+    grid.step_automaton(life);
+    let mut alive: Vec<_> = grid.iter().map(|(c, _)| c).collect();
+    alive.sort_by_key(|c| (c.y, c.x));
+    assert_eq!(
+      alive,
+      [Coord::new(1, 2), Coord::new(2, 2), Coord::new(3, 2)]
+    );
+  }
+
+  #[test]
+  fn counting() {
+    let mut grid = Grid::new(3, 3);
+    grid.insert(Coord::new(0, 0), 1);
+    grid.insert(Coord::new(1, 0), 2);
+    grid.insert(Coord::new(2, 2), 3);
+    grid.step_automaton(|nb| Some(nb.count(|v| *v >= 2)));
+    assert_eq!(grid.get(Coord::new(1, 1)), Some(&2));
+    assert_eq!(grid.get(Coord::new(0, 0)), Some(&1));
+    assert_eq!(grid.get(Coord::new(2, 0)), Some(&1));
+  }
+}
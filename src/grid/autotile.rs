@@ -0,0 +1,97 @@
+use crate::{
+  Coord, CoordVec, Direction4, Direction4Set, Direction8, Direction8Set,
+};
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Find which of the eight neighbors of `coord` match `pred`.
+  ///
+  /// `pred` gets `None` for empty cells and cells off the edge of the grid,
+  /// so it can decide whether the edge counts as a wall. Turn the result
+  /// into a tile index with
+  /// [`AutotileSet::to_wall_index`](crate::AutotileSet::to_wall_index).
+  pub fn connectivity<F: FnMut(Option<&T>) -> bool>(
+    &self,
+    coord: Coord,
+    mut pred: F,
+  ) -> Direction8Set {
+    Direction8::DIRECTIONS
+      .into_iter()
+      .filter(|dir| pred(self.get_offset(coord, dir.deltas())))
+      .collect()
+  }
+
+  /// Like [`Self::connectivity`], but only the four orthagonal neighbors.
+  pub fn connectivity4<F: FnMut(Option<&T>) -> bool>(
+    &self,
+    coord: Coord,
+    mut pred: F,
+  ) -> Direction4Set {
+    Direction4::DIRECTIONS
+      .into_iter()
+      .filter(|dir| pred(self.get_offset(coord, dir.deltas())))
+      .collect()
+  }
+
+  fn get_offset(&self, coord: Coord, offset: CoordVec) -> Option<&T> {
+    self.get((coord.to_icoord() + offset).to_coord()?)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    AutotileSet, Coord, Direction4, Direction4Set, Direction8, Direction8Set,
+    Grid, Transform,
+  };
+
+  #[test]
+  fn connectivity() {
+    let map =
+      Grid::from_str_map("#.#\n##.\n..#", |c, _| (c == '#').then_some(()));
+    let center = Coord::new(1, 1);
+    let walls = map.connectivity(center, |c| c.is_some());
+    assert_eq!(
+      walls,
+      Direction8::NorthEast
+        | Direction8::SouthEast
+        | Direction8::West
+        | Direction8::NorthWest
+    );
+    let walls4 = map.connectivity4(center, |c| c.is_some());
+    assert_eq!(walls4, Direction4::West);
+    assert_eq!(walls4.to_wall_index(), 8);
+
+    // Off the edge counts as empty
+    let corner = map.connectivity4(Coord::new(0, 0), |c| c.is_none());
+    assert_eq!(
+      corner,
+      Direction4::North | Direction4::East | Direction4::West
+    );
+  }
+
+  #[test]
+  fn wall_indices() {
+    for idx in 0..16 {
+      assert_eq!(Direction4Set::from_wall_index(idx).to_wall_index(), idx);
+    }
+    for idx in 0..256 {
+      assert_eq!(Direction8Set::from_wall_index(idx).to_wall_index(), idx);
+    }
+    assert_eq!(Direction4Set::from_wall_index(0x11), Direction4::North);
+
+    let elbow = Direction4::North | Direction4::East;
+    assert_eq!(elbow.rotated(1), Direction4::East | Direction4::South);
+    assert_eq!(
+      elbow.transformed(Transform::MIRROR),
+      Direction4::North | Direction4::West
+    );
+    let diagonal: Direction8Set = Direction8::NorthEast.into();
+    assert_eq!(diagonal.rotated(-2), Direction8::NorthWest);
+    assert_eq!(
+      diagonal.transformed(Transform::rotation(2)),
+      Direction8::SouthWest
+    );
+  }
+}
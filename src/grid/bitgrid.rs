@@ -0,0 +1,110 @@
+use crate::BitGrid;
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Get a [`BitGrid`] with a bit set for every filled cell.
+  pub fn occupancy(&self) -> BitGrid {
+    let mut out = BitGrid::new(self.width, self.height);
+    for (coord, _) in self.iter() {
+      out.set(coord, true);
+    }
+    out
+  }
+}
+
+/// Cells holding `true` become set bits; `false` and empty cells are clear.
+impl From<&Grid<bool>> for BitGrid {
+  fn from(grid: &Grid<bool>) -> Self {
+    let mut out = BitGrid::new(grid.width, grid.height);
+    for (coord, on) in grid.iter() {
+      out.set(coord, *on);
+    }
+    out
+  }
+}
+
+/// Every cell of the grid is filled, with whether its bit is set.
+impl From<&BitGrid> for Grid<bool> {
+  fn from(bits: &BitGrid) -> Self {
+    Grid::from_fn(bits.width(), bits.height(), |coord| Some(bits.get(coord)))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{BitGrid, Coord, Grid};
+
+  #[test]
+  fn bits() {
+    let mut bits = BitGrid::new(10, 9);
+    assert!(!bits.any());
+    assert!(!bits.set(Coord::new(3, 2), true));
+    assert!(bits.set(Coord::new(3, 2), true));
+    bits.set(Coord::new(9, 8), true);
+    bits.set(Coord::new(0, 7), true);
+    bits.set(Coord::new(10, 0), true);
+    assert!(bits.get(Coord::new(9, 8)));
+    assert!(!bits.get(Coord::new(10, 0)));
+    assert_eq!(bits.count_ones(), 3);
+    assert_eq!(
+      bits.iter_ones().collect::<Vec<_>>(),
+      [Coord::new(3, 2), Coord::new(0, 7), Coord::new(9, 8)]
+    );
+
+    let grid = Grid::from_str_map("#..\n.#.", |c, _| (c == '#').then_some(()));
+    let occupancy = grid.occupancy();
+    assert!(occupancy.get(Coord::new(1, 1)));
+    assert_eq!(occupancy.count_ones(), 2);
+  }
+
+  #[test]
+  fn bulk_ops() {
+    let grid = |map| {
+      let bools = Grid::from_str_map(map, |c, _| Some(c == '#'));
+      BitGrid::from(&bools)
+    };
+    let a = grid("##..\n#...\n....");
+    let b = grid(".#.#\n....\n...#");
+    assert_eq!(a.union(&b), grid("##.#\n#...\n...#"));
+    assert_eq!(a.intersect(&b), grid(".#..\n....\n...."));
+    assert_eq!(a.difference(&b), grid("#...\n#...\n...."));
+    let inverted = a.invert();
+    assert_eq!(inverted, grid("..##\n.###\n####"));
+    assert_eq!(inverted.count_ones(), 9);
+    assert_eq!(inverted.invert(), a);
+
+    let mut bits = a.clone();
+    assert!(!bits.toggle(Coord::new(0, 0)));
+    assert!(bits.toggle(Coord::new(3, 2)));
+    assert!(!bits.toggle(Coord::new(4, 0)));
+    assert_eq!(bits, grid(".#..\n#...\n...#"));
+
+    let bools = Grid::<bool>::from(&bits);
+    assert_eq!(bools.iter().count(), 12);
+    assert_eq!(bools.get(Coord::new(1, 0)), Some(&true));
+    assert_eq!(bools.get(Coord::new(2, 0)), Some(&false));
+    assert_eq!(BitGrid::from(&bools), bits);
+
+    // 8x8 fills exactly one word, so there's no padding to clear
+    assert_eq!(BitGrid::new(8, 8).invert().count_ones(), 64);
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde() {
+    let mut bits = BitGrid::new(10, 7);
+    bits.set(Coord::new(9, 6), true);
+    let json = serde_json::to_string(&bits).unwrap();
+    assert_eq!(serde_json::from_str::<BitGrid>(&json).unwrap(), bits);
+
+    let de = serde_json::from_str::<BitGrid>;
+    assert!(de(r#"{"width":10,"height":7,"words":[0,0]}"#).is_ok());
+    // 70 bits needs two words
+    assert!(de(r#"{"width":10,"height":7,"words":[0]}"#).is_err());
+    assert!(de(r#"{"width":10,"height":7,"words":[0,0,0]}"#).is_err());
+    // Bit 70 is past the end
+    assert!(de(r#"{"width":10,"height":7,"words":[0,64]}"#).is_err());
+    assert!(de(r#"{"width":0,"height":7,"words":[]}"#).is_ok());
+  }
+}
@@ -0,0 +1,205 @@
+use std::fmt::Display;
+
+use crate::Coord;
+
+use super::Grid;
+
+/// What [`Grid::insert_bulk`] does when a value lands on a cell that's
+/// already filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictPolicy {
+  /// Replace what's there.
+  #[default]
+  Overwrite,
+  /// Keep what's there and drop the new value.
+  Skip,
+  /// Don't change the grid at all, and report every conflict.
+  Error,
+}
+
+/// What happened in a [`Grid::insert_bulk`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BulkInsert {
+  /// How many values were put in the grid.
+  pub written: usize,
+  /// Where values landed on filled cells, in the order they came in. With
+  /// [`ConflictPolicy::Overwrite`] these were replaced, and with
+  /// [`ConflictPolicy::Skip`] they were left alone.
+  pub conflicts: Vec<Coord>,
+  /// Coordinates that were off the grid, which are always dropped.
+  pub out_of_bounds: Vec<Coord>,
+}
+
+impl BulkInsert {
+  /// Did every value go in without a conflict or falling off the grid?
+  pub fn is_clean(&self) -> bool {
+    self.conflicts.is_empty() && self.out_of_bounds.is_empty()
+  }
+}
+
+/// Returned by [`Grid::insert_bulk`] with [`ConflictPolicy::Error`] when
+/// anything couldn't be inserted cleanly. The grid is left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BulkInsertError {
+  /// Where values landed on filled cells, including cells filled earlier in
+  /// the same batch.
+  pub conflicts: Vec<Coord>,
+  /// Coordinates that were off the grid.
+  pub out_of_bounds: Vec<Coord>,
+}
+
+impl Display for BulkInsertError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{} conflicting and {} out-of-bounds cells",
+      self.conflicts.len(),
+      self.out_of_bounds.len()
+    )
+  }
+}
+
+impl std::error::Error for BulkInsertError {}
+
+impl<T> Grid<T> {
+  /// Insert lots of values at once, like the output of a generator.
+  ///
+  /// `policy` decides what happens to values landing on cells that are
+  /// already filled, including ones filled earlier in the same batch.
+  /// Values off the grid are dropped either way, and reported.
+  ///
+  /// With [`ConflictPolicy::Error`], it's all or nothing: if there are any
+  /// conflicts or out-of-bounds values, the grid isn't changed and they're
+  /// all reported in the error. The other policies always succeed.
+  ///
+  /// ```
+  /// # use aglet::{ConflictPolicy, Coord, Grid};
+  /// let mut grid = Grid::new(3, 3);
+  /// grid.insert(Coord::new(1, 1), 'x');
+  /// let props = [(Coord::new(0, 0), 'a'), (Coord::new(1, 1), 'b')];
+  ///
+  /// let err = grid.insert_bulk(props, ConflictPolicy::Error).unwrap_err();
+  /// assert_eq!(err.conflicts, [Coord::new(1, 1)]);
+  /// assert_eq!(grid.get(Coord::new(0, 0)), None);
+  ///
+  /// let report = grid.insert_bulk(props, ConflictPolicy::Skip).unwrap();
+  /// assert_eq!(report.written, 1);
+  /// assert_eq!(grid.get(Coord::new(1, 1)), Some(&'x'));
+  /// ```
+  pub fn insert_bulk(
+    &mut self,
+    values: impl IntoIterator<Item = (Coord, T)>,
+    policy: ConflictPolicy,
+  ) -> Result<BulkInsert, BulkInsertError> {
+    let mut report = BulkInsert::default();
+    // With `Error`, everything written was empty before, so undoing is just
+    // emptying it again. Keep writing after the first problem, so later
+    // values landing on the same cells are still caught as conflicts.
+    let mut undo = Vec::new();
+    for (coord, val) in values {
+      let Some(idx) = self.idx(coord) else {
+        report.out_of_bounds.push(coord);
+        continue;
+      };
+      let slot = &mut self.spots[idx];
+      if slot.is_some() {
+        report.conflicts.push(coord);
+        if policy != ConflictPolicy::Overwrite {
+          continue;
+        }
+      }
+      if policy == ConflictPolicy::Error {
+        undo.push(idx);
+      }
+      *slot = Some(val);
+      report.written += 1;
+    }
+
+    if policy == ConflictPolicy::Error && !report.is_clean() {
+      for idx in undo {
+        self.spots[idx] = None;
+      }
+      return Err(BulkInsertError {
+        conflicts: report.conflicts,
+        out_of_bounds: report.out_of_bounds,
+      });
+    }
+    Ok(report)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{BulkInsertError, ConflictPolicy, Coord, Grid};
+
+  fn batch() -> Vec<(Coord, u8)> {
+    vec![
+      (Coord::new(0, 0), 1),
+      (Coord::new(5, 0), 2),
+      (Coord::new(1, 0), 3),
+      (Coord::new(0, 0), 4),
+    ]
+  }
+
+  fn grid() -> Grid<u8> {
+    let mut grid = Grid::new(2, 2);
+    grid.insert(Coord::new(1, 0), 9);
+    grid
+  }
+
+  #[test]
+  fn policies() {
+    let mut over = grid();
+    let report = over
+      .insert_bulk(batch(), ConflictPolicy::Overwrite)
+      .unwrap();
+    assert_eq!(report.written, 3);
+    assert_eq!(report.conflicts, [Coord::new(1, 0), Coord::new(0, 0)]);
+    assert_eq!(report.out_of_bounds, [Coord::new(5, 0)]);
+    assert!(!report.is_clean());
+    assert_eq!(over.get(Coord::new(0, 0)), Some(&4));
+    assert_eq!(over.get(Coord::new(1, 0)), Some(&3));
+
+    let mut skip = grid();
+    let report = skip.insert_bulk(batch(), ConflictPolicy::Skip).unwrap();
+    assert_eq!(report.written, 1);
+    assert_eq!(report.conflicts.len(), 2);
+    assert_eq!(skip.get(Coord::new(0, 0)), Some(&1));
+    assert_eq!(skip.get(Coord::new(1, 0)), Some(&9));
+
+    let mut error = grid();
+    let err = error
+      .insert_bulk(batch(), ConflictPolicy::Error)
+      .unwrap_err();
+    assert_eq!(
+      err,
+      BulkInsertError {
+        conflicts: vec![Coord::new(1, 0), Coord::new(0, 0)],
+        out_of_bounds: vec![Coord::new(5, 0)],
+      }
+    );
+    assert_eq!(error.iter().count(), 1);
+    assert_eq!(error.get(Coord::new(1, 0)), Some(&9));
+
+    let clean = [(Coord::new(0, 1), 5), (Coord::new(1, 1), 6)];
+    let report = error.insert_bulk(clean, ConflictPolicy::Error).unwrap();
+    assert_eq!(report.written, 2);
+    assert!(report.is_clean());
+    assert_eq!(error.iter().count(), 3);
+  }
+
+  #[test]
+  fn error_after_out_of_bounds() {
+    let batch = [
+      (Coord::new(9, 9), 1),
+      (Coord::new(0, 0), 2),
+      (Coord::new(0, 0), 3),
+    ];
+    let mut grid: Grid<u8> = Grid::new(2, 2);
+    let err = grid.insert_bulk(batch, ConflictPolicy::Error).unwrap_err();
+    assert_eq!(err.conflicts, [Coord::new(0, 0)]);
+    assert_eq!(err.out_of_bounds, [Coord::new(9, 9)]);
+    assert_eq!(grid.iter().count(), 0);
+  }
+}
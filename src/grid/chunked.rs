@@ -0,0 +1,430 @@
+use std::{
+  collections::{HashMap, HashSet},
+  sync::mpsc::{self, Receiver, Sender},
+};
+
+use crate::{Coord, CoordVec};
+
+use super::{wrap, Grid};
+
+/// Somewhere to page the chunks of a [`ChunkedGrid`] in from and out to, like
+/// a save file or a world generator.
+///
+/// A `HashMap` of chunks works as a source that just keeps everything in
+/// memory.
+pub trait ChunkSource<T> {
+  /// Load the chunk at this chunk coordinate, or return `None` if there's
+  /// nothing there yet and it should start out empty.
+  ///
+  /// The grid must be `chunk_size` by `chunk_size`.
+  fn load(&mut self, chunk: CoordVec) -> Option<Grid<T>>;
+
+  /// Store a chunk that's being evicted or flushed.
+  fn save(&mut self, chunk: CoordVec, grid: Grid<T>);
+}
+
+impl<T: Clone> ChunkSource<T> for HashMap<CoordVec, Grid<T>> {
+  fn load(&mut self, chunk: CoordVec) -> Option<Grid<T>> {
+    self.get(&chunk).cloned()
+  }
+
+  fn save(&mut self, chunk: CoordVec, grid: Grid<T>) {
+    self.insert(chunk, grid);
+  }
+}
+
+/// An endless grid, split into square chunks that are loaded from a
+/// [`ChunkSource`] as they're needed.
+///
+/// At most `budget` chunks are kept loaded at once. When another one is
+/// needed, the least recently used one is evicted. Chunks are only saved back
+/// to the source if they were changed since they were loaded.
+///
+/// Because looking at a cell might load its chunk, even [`Self::get`] takes
+/// `&mut self`. Use [`Self::get_loaded`] to peek without loading.
+///
+/// Chunks can also be generated off on other threads. [Mark](Self::mark_pending)
+/// a chunk as pending, send the finished grid down a
+/// [`Self::generated_sender`] (or hand it to [`Self::deliver`]), and
+/// [`Self::poll_generated`] on the main thread to pick it up. Until then,
+/// reading from the pending chunk finds nothing, and everything else works
+/// as normal.
+#[derive(Debug)]
+pub struct ChunkedGrid<T, S> {
+  source: S,
+  chunk_size: u32,
+  budget: usize,
+  loaded: HashMap<CoordVec, LoadedChunk<T>>,
+  pending: HashSet<CoordVec>,
+  generated_tx: Sender<(CoordVec, Grid<T>)>,
+  generated_rx: Receiver<(CoordVec, Grid<T>)>,
+  /// Goes up every time a chunk is touched, for finding the least recently
+  /// used one.
+  clock: u64,
+}
+
+#[derive(Debug)]
+struct LoadedChunk<T> {
+  grid: Grid<T>,
+  dirty: bool,
+  last_used: u64,
+}
+
+impl<T, S: ChunkSource<T>> ChunkedGrid<T, S> {
+  /// Panics if `chunk_size` or `budget` is zero.
+  pub fn new(source: S, chunk_size: u32, budget: usize) -> Self {
+    assert!(chunk_size > 0, "chunks must have cells in them");
+    assert!(budget > 0, "must be able to load at least one chunk");
+    let (generated_tx, generated_rx) = mpsc::channel();
+    Self {
+      source,
+      chunk_size,
+      budget,
+      loaded: HashMap::new(),
+      pending: HashSet::new(),
+      generated_tx,
+      generated_rx,
+      clock: 0,
+    }
+  }
+
+  pub fn chunk_size(&self) -> u32 {
+    self.chunk_size
+  }
+
+  pub fn source(&self) -> &S {
+    &self.source
+  }
+
+  pub fn source_mut(&mut self) -> &mut S {
+    &mut self.source
+  }
+
+  /// Split a coordinate into the chunk it's in, and where it is inside that
+  /// chunk.
+  pub fn split_coord(&self, coord: impl Into<CoordVec>) -> (CoordVec, Coord) {
+    let coord = coord.into();
+    let size = self.chunk_size as i64;
+    let chunk = CoordVec::new(
+      (coord.x as i64).div_euclid(size) as i32,
+      (coord.y as i64).div_euclid(size) as i32,
+    );
+    (chunk, wrap(coord, self.chunk_size, self.chunk_size))
+  }
+
+  /// The number of chunks currently loaded.
+  pub fn loaded_count(&self) -> usize {
+    self.loaded.len()
+  }
+
+  pub fn is_loaded(&self, chunk: CoordVec) -> bool {
+    self.loaded.contains_key(&chunk)
+  }
+
+  /// Get a cell, loading its chunk if needed.
+  ///
+  /// Cells in pending chunks are always empty, and don't load anything.
+  pub fn get(&mut self, coord: impl Into<CoordVec>) -> Option<&T> {
+    let (chunk, local) = self.split_coord(coord);
+    if self.pending.contains(&chunk) {
+      return None;
+    }
+    self.chunk(chunk).grid.get(local)
+  }
+
+  /// Get a cell if its chunk is already loaded, without touching the source.
+  pub fn get_loaded(&self, coord: impl Into<CoordVec>) -> Option<&T> {
+    let (chunk, local) = self.split_coord(coord);
+    self.loaded.get(&chunk)?.grid.get(local)
+  }
+
+  /// Writing to a cell in a pending chunk stops waiting for it, and loads it
+  /// from the source instead; see [`Self::cancel_pending`]. The same goes for
+  /// [`Self::insert`] and [`Self::remove`].
+  pub fn get_mut(&mut self, coord: impl Into<CoordVec>) -> Option<&mut T> {
+    let (chunk, local) = self.split_coord(coord);
+    let chunk = self.chunk(chunk);
+    chunk.dirty = true;
+    chunk.grid.get_mut(local)
+  }
+
+  /// Returns the old value
+  pub fn insert(&mut self, coord: impl Into<CoordVec>, val: T) -> Option<T> {
+    let (chunk, local) = self.split_coord(coord);
+    let chunk = self.chunk(chunk);
+    chunk.dirty = true;
+    chunk.grid.insert(local, val)
+  }
+
+  pub fn remove(&mut self, coord: impl Into<CoordVec>) -> Option<T> {
+    let (chunk, local) = self.split_coord(coord);
+    let chunk = self.chunk(chunk);
+    chunk.dirty = true;
+    chunk.grid.remove(local)
+  }
+
+  /// Make sure a chunk is loaded, evicting another one if that goes over
+  /// budget.
+  ///
+  /// If the chunk was pending, this stops waiting for it.
+  pub fn load(&mut self, chunk: CoordVec) {
+    self.chunk(chunk);
+  }
+
+  /// Evict a chunk, saving it if it changed.
+  ///
+  /// Does nothing if it isn't loaded.
+  pub fn evict(&mut self, chunk: CoordVec) {
+    if let Some(loaded) = self.loaded.remove(&chunk) {
+      if loaded.dirty {
+        self.source.save(chunk, loaded.grid);
+      }
+    }
+  }
+
+  /// Mark a chunk as being generated somewhere else, so it shouldn't be
+  /// loaded from the source.
+  ///
+  /// Returns `false`, and does nothing, if the chunk is already loaded or
+  /// pending. So this is a good place to decide whether to kick off a job.
+  pub fn mark_pending(&mut self, chunk: CoordVec) -> bool {
+    if self.loaded.contains_key(&chunk) {
+      return false;
+    }
+    self.pending.insert(chunk)
+  }
+
+  pub fn is_pending(&self, chunk: CoordVec) -> bool {
+    self.pending.contains(&chunk)
+  }
+
+  pub fn pending_chunks(&self) -> impl Iterator<Item = CoordVec> + '_ {
+    self.pending.iter().copied()
+  }
+
+  /// Stop waiting for a pending chunk. If it shows up later, it's thrown
+  /// away.
+  ///
+  /// Returns whether the chunk was pending.
+  pub fn cancel_pending(&mut self, chunk: CoordVec) -> bool {
+    self.pending.remove(&chunk)
+  }
+
+  /// Get a sender that worker threads can send generated chunks down.
+  ///
+  /// Nothing happens to them until [`Self::poll_generated`] is called.
+  pub fn generated_sender(&self) -> Sender<(CoordVec, Grid<T>)> {
+    self.generated_tx.clone()
+  }
+
+  /// Pick up every chunk that's been sent down a
+  /// [`Self::generated_sender`] so far, without blocking.
+  ///
+  /// Returns how many were accepted, as in [`Self::deliver`].
+  pub fn poll_generated(&mut self) -> usize {
+    let mut accepted = 0;
+    while let Ok((chunk, grid)) = self.generated_rx.try_recv() {
+      if self.deliver(chunk, grid) {
+        accepted += 1;
+      }
+    }
+    accepted
+  }
+
+  /// Hand over a generated chunk, loading it and evicting another one if
+  /// that goes over budget.
+  ///
+  /// The chunk counts as changed, so it will be saved to the source when
+  /// it's evicted. If the chunk isn't pending, because it was never marked
+  /// or was cancelled, the grid is thrown away and this returns `false`.
+  /// Panics if the grid is the wrong size.
+  pub fn deliver(&mut self, chunk: CoordVec, grid: Grid<T>) -> bool {
+    if !self.pending.remove(&chunk) {
+      return false;
+    }
+    self.check_size(&grid);
+    self.clock += 1;
+    self.make_room();
+    self.loaded.insert(
+      chunk,
+      LoadedChunk {
+        grid,
+        dirty: true,
+        last_used: self.clock,
+      },
+    );
+    true
+  }
+
+  /// Save every changed chunk, keeping them all loaded.
+  pub fn flush(&mut self)
+  where
+    T: Clone,
+  {
+    for (chunk, loaded) in self.loaded.iter_mut() {
+      if loaded.dirty {
+        self.source.save(*chunk, loaded.grid.clone());
+        loaded.dirty = false;
+      }
+    }
+  }
+
+  /// Evict every chunk, saving the changed ones, and give back the source.
+  pub fn into_source(mut self) -> S {
+    let chunks: Vec<_> = self.loaded.keys().copied().collect();
+    for chunk in chunks {
+      self.evict(chunk);
+    }
+    self.source
+  }
+
+  fn chunk(&mut self, chunk: CoordVec) -> &mut LoadedChunk<T> {
+    self.clock += 1;
+    self.pending.remove(&chunk);
+    if !self.loaded.contains_key(&chunk) {
+      self.make_room();
+      let grid = match self.source.load(chunk) {
+        Some(grid) => {
+          self.check_size(&grid);
+          grid
+        }
+        None => Grid::new(self.chunk_size, self.chunk_size),
+      };
+      self.loaded.insert(
+        chunk,
+        LoadedChunk {
+          grid,
+          dirty: false,
+          last_used: 0,
+        },
+      );
+    }
+    let loaded = self.loaded.get_mut(&chunk).unwrap();
+    loaded.last_used = self.clock;
+    loaded
+  }
+
+  /// Evict the least recently used chunk if there's no room for another.
+  fn make_room(&mut self) {
+    if self.loaded.len() < self.budget {
+      return;
+    }
+    let oldest = self
+      .loaded
+      .iter()
+      .min_by_key(|(_, loaded)| loaded.last_used)
+      .map(|(chunk, _)| *chunk)
+      .unwrap();
+    self.evict(oldest);
+  }
+
+  fn check_size(&self, grid: &Grid<T>) {
+    assert_eq!(
+      (grid.width(), grid.height()),
+      (self.chunk_size, self.chunk_size),
+      "chunk is the wrong size"
+    );
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::{collections::HashMap, thread};
+
+  use crate::{ChunkSource, ChunkedGrid, Coord, CoordVec, Grid};
+
+  /// Counts how often it gets used.
+  #[derive(Default)]
+  struct Counting {
+    chunks: HashMap<CoordVec, Grid<char>>,
+    loads: usize,
+    saves: usize,
+  }
+
+  impl ChunkSource<char> for Counting {
+    fn load(&mut self, chunk: CoordVec) -> Option<Grid<char>> {
+      self.loads += 1;
+      self.chunks.load(chunk)
+    }
+
+    fn save(&mut self, chunk: CoordVec, grid: Grid<char>) {
+      self.saves += 1;
+      self.chunks.save(chunk, grid);
+    }
+  }
+
+  #[test]
+  fn split() {
+    let grid = ChunkedGrid::new(HashMap::<_, Grid<()>>::new(), 4, 1);
+    assert_eq!(
+      grid.split_coord(CoordVec::new(5, -1)),
+      (CoordVec::new(1, -1), Coord::new(1, 3))
+    );
+    assert_eq!(
+      grid.split_coord(CoordVec::new(-4, 3)),
+      (CoordVec::new(-1, 0), Coord::new(0, 3))
+    );
+  }
+
+  #[test]
+  fn lru_eviction() {
+    let mut grid = ChunkedGrid::new(Counting::default(), 4, 2);
+    grid.insert(CoordVec::new(0, 0), 'a');
+    grid.insert(CoordVec::new(-1, 0), 'b');
+    // Touch chunk (0, 0) so (-1, 0) is the least recently used
+    assert_eq!(grid.get(CoordVec::new(0, 0)), Some(&'a'));
+    assert_eq!(grid.get(CoordVec::new(10, 10)), None);
+    assert_eq!(grid.loaded_count(), 2);
+    assert!(!grid.is_loaded(CoordVec::new(-1, 0)));
+    assert_eq!(grid.get_loaded(CoordVec::new(-1, 0)), None);
+    assert_eq!(grid.source().saves, 1);
+
+    // Paging it back in brings the value with it
+    assert_eq!(grid.get(CoordVec::new(-1, 0)), Some(&'b'));
+    assert!(!grid.is_loaded(CoordVec::new(0, 0)));
+    assert!(grid.is_loaded(CoordVec::new(2, 2)));
+    assert_eq!(grid.source().saves, 2);
+
+    // Nothing loaded has changed since, so nothing more is saved
+    let source = grid.into_source();
+    assert_eq!((source.loads, source.saves), (4, 2));
+    assert_eq!(source.chunks.len(), 2);
+  }
+
+  #[test]
+  fn generated_on_another_thread() {
+    let mut grid = ChunkedGrid::new(HashMap::new(), 4, 2);
+    grid.insert(CoordVec::new(0, 0), 'a');
+    assert!(grid.mark_pending(CoordVec::new(1, 0)));
+    assert!(!grid.mark_pending(CoordVec::new(1, 0)));
+    assert!(!grid.mark_pending(CoordVec::new(0, 0)));
+    assert!(grid.mark_pending(CoordVec::new(2, 0)));
+
+    let tx = grid.generated_sender();
+    let worker = thread::spawn(move || {
+      for chunk in [CoordVec::new(1, 0), CoordVec::new(2, 0)] {
+        let mut generated = Grid::new(4, 4);
+        generated.insert(Coord::new(0, 0), 'g');
+        tx.send((chunk, generated)).unwrap();
+      }
+    });
+    // Meanwhile, loaded chunks still work and pending ones are empty
+    assert_eq!(grid.get(CoordVec::new(0, 0)), Some(&'a'));
+    assert_eq!(grid.get(CoordVec::new(4, 0)), None);
+    assert!(grid.cancel_pending(CoordVec::new(2, 0)));
+    worker.join().unwrap();
+
+    assert_eq!(grid.poll_generated(), 1);
+    assert!(!grid.is_pending(CoordVec::new(1, 0)));
+    assert_eq!(grid.get(CoordVec::new(4, 0)), Some(&'g'));
+    assert_eq!(grid.get_loaded(CoordVec::new(8, 0)), None);
+    assert_eq!(grid.pending_chunks().count(), 0);
+
+    // Generated chunks are saved when they get evicted
+    let source = grid.into_source();
+    assert_eq!(
+      source[&CoordVec::new(1, 0)].get(Coord::new(0, 0)),
+      Some(&'g')
+    );
+  }
+}
@@ -0,0 +1,108 @@
+use crate::Coord;
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Make a grid by calling `f` for each cell, in row-major order.
+  ///
+  /// Cells where `f` returns `None` are left empty.
+  pub fn from_fn<F: FnMut(Coord) -> Option<T>>(
+    width: u32,
+    height: u32,
+    mut f: F,
+  ) -> Grid<T> {
+    let mut grid = Grid::new(width, height);
+    for idx in 0..grid.spots.len() {
+      grid.spots[idx] = f(grid.coord_of(idx));
+    }
+    grid
+  }
+
+  /// Make a new grid the same size, by calling `f` on each filled cell.
+  ///
+  /// Empty cells stay empty.
+  pub fn map<U, F: FnMut(Coord, &T) -> U>(&self, mut f: F) -> Grid<U> {
+    Grid {
+      width: self.width,
+      height: self.height,
+      spots: self
+        .spots
+        .iter()
+        .enumerate()
+        .map(|(idx, slot)| slot.as_ref().map(|val| f(self.coord_of(idx), val)))
+        .collect(),
+    }
+  }
+
+  /// Pair up the cells of two grids the same size.
+  ///
+  /// Only cells filled in both grids are filled in the result. Panics if the
+  /// grids are different sizes.
+  pub fn zip<'a, U>(&'a self, other: &'a Grid<U>) -> Grid<(&'a T, &'a U)> {
+    self.merge_with(other, |_, a, b| a.zip(b))
+  }
+
+  /// Combine the cells of two grids the same size with `f`, which gets what's
+  /// in each grid at each cell, filled or not.
+  ///
+  /// Cells where `f` returns `None` are left empty. Panics if the grids are
+  /// different sizes.
+  pub fn merge_with<'a, U, V, F>(
+    &'a self,
+    other: &'a Grid<U>,
+    mut f: F,
+  ) -> Grid<V>
+  where
+    F: FnMut(Coord, Option<&'a T>, Option<&'a U>) -> Option<V>,
+  {
+    assert_eq!(
+      (self.width, self.height),
+      (other.width, other.height),
+      "can only merge grids of the same size"
+    );
+    Grid {
+      width: self.width,
+      height: self.height,
+      spots: self
+        .spots
+        .iter()
+        .zip(other.spots.iter())
+        .enumerate()
+        .map(|(idx, (a, b))| f(self.coord_of(idx), a.as_ref(), b.as_ref()))
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{grid::text::char_grid, Coord, Grid};
+
+  #[test]
+  fn combinators() {
+    let terrain = Grid::from_fn(3, 2, |c| (c.x != 1).then_some(c.x + c.y * 3));
+    assert_eq!(terrain.iter().count(), 4);
+    assert_eq!(terrain.get(Coord::new(2, 1)), Some(&5));
+
+    let doubled = terrain.map(|_, v| v * 2);
+    assert_eq!(doubled.get(Coord::new(2, 1)), Some(&10));
+    assert_eq!(doubled.get(Coord::new(1, 1)), None);
+
+    let objects = char_grid("x.x\n.y.");
+    let zipped = terrain.zip(&objects);
+    assert_eq!(
+      zipped
+        .iter()
+        .map(|(c, (t, o))| (c, **t, **o))
+        .collect::<Vec<_>>(),
+      [(Coord::new(0, 0), 0, 'x'), (Coord::new(2, 0), 2, 'x')]
+    );
+
+    let merged = terrain.merge_with(&objects, |_, t, o| match (t, o) {
+      (_, Some(o)) => Some(*o),
+      (Some(_), None) => Some('.'),
+      (None, None) => None,
+    });
+    assert_eq!(merged.render(|c| c.copied().unwrap_or(' ')), "x x\n.y.");
+  }
+}
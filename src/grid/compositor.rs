@@ -0,0 +1,151 @@
+use crate::{Area, Coord, Region};
+
+use super::Grid;
+
+/// Builds a [`Grid`] out of a stack of shapes, each filled with one value.
+///
+/// Later layers are drawn on top of earlier ones. Because the layers are kept
+/// around, changing one only needs the cells it touches redrawn; see
+/// [`Self::set_layer`] and [`Self::repaint`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Compositor<T> {
+  width: u32,
+  height: u32,
+  layers: Vec<(Region, T)>,
+}
+
+impl<T: Clone> Compositor<T> {
+  /// Make a compositor with no layers that makes grids of the given size.
+  pub fn new(width: u32, height: u32) -> Self {
+    Self {
+      width,
+      height,
+      layers: Vec::new(),
+    }
+  }
+
+  /// Add a layer on top of all the others.
+  ///
+  /// Returns the index of the new layer.
+  pub fn push(&mut self, shape: impl Into<Region>, value: T) -> usize {
+    self.layers.push((shape.into(), value));
+    self.layers.len() - 1
+  }
+
+  /// Get the shape and value of a layer.
+  pub fn layer(&self, idx: usize) -> Option<(&Region, &T)> {
+    self.layers.get(idx).map(|(shape, value)| (shape, value))
+  }
+
+  /// The number of layers.
+  pub fn len(&self) -> usize {
+    self.layers.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.layers.is_empty()
+  }
+
+  /// Change the shape and value of a layer.
+  ///
+  /// Returns the area that needs to be [repainted](Self::repaint) to bring
+  /// an existing grid up to date. Panics if the layer doesn't exist.
+  pub fn set_layer(
+    &mut self,
+    idx: usize,
+    shape: impl Into<Region>,
+    value: T,
+  ) -> Area {
+    let shape = shape.into();
+    let old = &self.layers[idx].0;
+    let dirty = match (old.is_empty(), shape.is_empty()) {
+      (true, _) => shape.bounds(),
+      (_, true) => old.bounds(),
+      _ => old.bounds().union_bounds(&shape.bounds()),
+    };
+    self.layers[idx] = (shape, value);
+    dirty
+  }
+
+  /// The value of the topmost layer covering `coord`, if any.
+  pub fn value_at(&self, coord: Coord) -> Option<&T> {
+    self
+      .layers
+      .iter()
+      .rev()
+      .find(|(shape, _)| shape.contains(coord))
+      .map(|(_, value)| value)
+  }
+
+  /// Draw all the layers into a new grid.
+  ///
+  /// Cells that no layer covers are left empty.
+  pub fn rasterize(&self) -> Grid<T> {
+    let mut grid = Grid::new(self.width, self.height);
+    for (shape, value) in &self.layers {
+      for coord in shape {
+        grid.insert(coord, value.clone());
+      }
+    }
+    grid
+  }
+
+  /// Redraw just the cells in `dirty` of a grid made with [`Self::rasterize`].
+  pub fn repaint(&self, grid: &mut Grid<T>, dirty: Area) {
+    let Some(dirty) = dirty.intersect(&grid.area()) else {
+      return;
+    };
+    for coord in dirty {
+      match self.value_at(coord) {
+        Some(value) => grid.insert(coord, value.clone()),
+        None => grid.remove(coord),
+      };
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Area, Compositor, Coord, Region};
+
+  #[test]
+  fn layering() {
+    let mut comp = Compositor::new(6, 4);
+    comp.push(Area::new(Coord::new(0, 0), 6, 4), '#');
+    let room = comp.push(Area::new(Coord::new(1, 1), 3, 2), '.');
+    let mut door = Region::new();
+    door.insert(Coord::new(4, 1));
+    comp.push(door, '+');
+
+    let grid = comp.rasterize();
+    assert_eq!(grid.get(Coord::new(0, 0)), Some(&'#'));
+    assert_eq!(grid.get(Coord::new(2, 2)), Some(&'.'));
+    assert_eq!(grid.get(Coord::new(4, 1)), Some(&'+'));
+
+    // Make the room bigger, under the door
+    let mut cached = grid.clone();
+    let dirty = comp.set_layer(room, Area::new(Coord::new(1, 1), 4, 2), '.');
+    assert_eq!(dirty, Area::new(Coord::new(1, 1), 4, 2));
+    comp.repaint(&mut cached, dirty);
+
+    let fresh = comp.rasterize();
+    assert_eq!(cached.get(Coord::new(4, 2)), Some(&'.'));
+    assert_eq!(cached.get(Coord::new(4, 1)), Some(&'+'));
+    for coord in cached.area() {
+      assert_eq!(cached.get(coord), fresh.get(coord));
+    }
+  }
+
+  #[test]
+  fn uncovered() {
+    let mut comp = Compositor::new(3, 3);
+    let layer = comp.push(Area::new(Coord::new(0, 0), 2, 2), 1);
+    let mut grid = comp.rasterize();
+    assert_eq!(grid.get(Coord::new(2, 2)), None);
+
+    let dirty = comp.set_layer(layer, Region::new(), 1);
+    comp.repaint(&mut grid, dirty);
+    assert!(grid.iter().next().is_none());
+  }
+}
@@ -0,0 +1,141 @@
+use crate::{Coord, Direction4, EdgeCoord};
+
+use super::{Grid, GridIter};
+
+/// Like a [`Grid`], but it stores things on the edges between cells instead
+/// of in the cells themselves. Handy for walls and doors that live on cell
+/// borders.
+///
+/// An `EdgeGrid` for a `width` by `height` grid of cells includes the edges
+/// around the outside, too.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct EdgeGrid<T> {
+  /// North edges of each cell, plus the south edges of the bottom row
+  horizontal: Grid<T>,
+  /// West edges of each cell, plus the east edges of the rightmost column
+  vertical: Grid<T>,
+}
+
+impl<T> EdgeGrid<T> {
+  /// Make an empty `EdgeGrid` for a grid of cells of the given size.
+  pub fn new(width: u32, height: u32) -> Self {
+    Self {
+      horizontal: Grid::new(width, height + 1),
+      vertical: Grid::new(width + 1, height),
+    }
+  }
+
+  /// Width of the grid of cells.
+  pub fn width(&self) -> u32 {
+    self.horizontal.width()
+  }
+
+  /// Height of the grid of cells.
+  pub fn height(&self) -> u32 {
+    self.vertical.height()
+  }
+
+  pub fn get(&self, edge: EdgeCoord) -> Option<&T> {
+    self.half(edge).get(edge.cell())
+  }
+
+  pub fn get_mut(&mut self, edge: EdgeCoord) -> Option<&mut T> {
+    self.half_mut(edge).get_mut(edge.cell())
+  }
+
+  /// Returns the old value
+  pub fn insert(&mut self, edge: EdgeCoord, val: T) -> Option<T> {
+    self.half_mut(edge).insert(edge.cell(), val)
+  }
+
+  pub fn remove(&mut self, edge: EdgeCoord) -> Option<T> {
+    self.half_mut(edge).remove(edge.cell())
+  }
+
+  pub fn contains(&self, edge: EdgeCoord) -> bool {
+    self.half(edge).contains(edge.cell())
+  }
+
+  /// Get the value on the edge between two cells, if they're adjacent.
+  pub fn between(&self, a: Coord, b: Coord) -> Option<&T> {
+    self.get(EdgeCoord::between(a, b)?)
+  }
+
+  /// Return whether the given edge even fits in the grid.
+  pub fn is_edge_valid(&self, edge: EdgeCoord) -> bool {
+    self.half(edge).is_coord_valid(edge.cell())
+  }
+
+  /// Iterate over all the (filled) edges, horizontal ones first.
+  pub fn iter(&self) -> EdgeGridIter<'_, T> {
+    EdgeGridIter {
+      horizontal: self.horizontal.iter(),
+      vertical: self.vertical.iter(),
+    }
+  }
+
+  fn half(&self, edge: EdgeCoord) -> &Grid<T> {
+    if edge.is_horizontal() {
+      &self.horizontal
+    } else {
+      &self.vertical
+    }
+  }
+
+  fn half_mut(&mut self, edge: EdgeCoord) -> &mut Grid<T> {
+    if edge.is_horizontal() {
+      &mut self.horizontal
+    } else {
+      &mut self.vertical
+    }
+  }
+}
+
+/// Borrowing iterator over the filled edges in an [`EdgeGrid`].
+pub struct EdgeGridIter<'a, T> {
+  horizontal: GridIter<'a, T>,
+  vertical: GridIter<'a, T>,
+}
+
+impl<'a, T> Iterator for EdgeGridIter<'a, T> {
+  type Item = (EdgeCoord, &'a T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if let Some((coord, val)) = self.horizontal.next() {
+      return Some((EdgeCoord::new(coord, Direction4::North), val));
+    }
+    let (coord, val) = self.vertical.next()?;
+    Some((EdgeCoord::new(coord, Direction4::West), val))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, Direction4, EdgeCoord, EdgeGrid};
+
+  #[test]
+  fn edge_grid() {
+    let mut walls = EdgeGrid::new(3, 2);
+    let c = Coord::new(2, 1);
+    for edge in EdgeCoord::around(c) {
+      assert!(walls.is_edge_valid(edge));
+      walls.insert(edge, "wall");
+    }
+    assert!(
+      !walls.is_edge_valid(EdgeCoord::new(Coord::new(2, 2), Direction4::South))
+    );
+
+    assert_eq!(walls.between(c, Coord::new(1, 1)), Some(&"wall"));
+    assert_eq!(walls.between(Coord::new(0, 0), Coord::new(1, 0)), None);
+    assert_eq!(
+      walls.remove(EdgeCoord::new(Coord::new(2, 0), Direction4::South)),
+      Some("wall")
+    );
+    assert_eq!(walls.iter().count(), 3);
+    assert!(walls.iter().all(|(edge, _)| {
+      let (a, b) = edge.cells();
+      a == Some(c) || b == c
+    }));
+  }
+}
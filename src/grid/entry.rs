@@ -0,0 +1,131 @@
+use crate::Coord;
+
+/// A view into a single slot in a [`Grid`], which may be filled or empty.
+///
+/// This is made with [`Grid::entry`], and works just like `HashMap`'s entries.
+///
+/// [`Grid`]: super::Grid
+/// [`Grid::entry`]: super::Grid::entry
+pub enum Entry<'a, T> {
+  Occupied(OccupiedEntry<'a, T>),
+  Vacant(VacantEntry<'a, T>),
+}
+
+impl<'a, T> Entry<'a, T> {
+  pub(crate) fn new(coord: Coord, slot: &'a mut Option<T>) -> Self {
+    if slot.is_some() {
+      Entry::Occupied(OccupiedEntry { coord, slot })
+    } else {
+      Entry::Vacant(VacantEntry { coord, slot })
+    }
+  }
+
+  /// The coordinate of this entry.
+  pub fn coord(&self) -> Coord {
+    match self {
+      Entry::Occupied(it) => it.coord,
+      Entry::Vacant(it) => it.coord,
+    }
+  }
+
+  /// Fill the slot with `default` if it's empty, and return a reference to
+  /// the value in it.
+  pub fn or_insert(self, default: T) -> &'a mut T {
+    self.or_insert_with(|| default)
+  }
+
+  /// Fill the slot with the result of `default` if it's empty, and return a
+  /// reference to the value in it.
+  pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> &'a mut T {
+    match self {
+      Entry::Occupied(it) => it.into_mut(),
+      Entry::Vacant(it) => it.insert(default()),
+    }
+  }
+
+  /// Like [`Self::or_insert_with`], but the function gets the coordinate.
+  pub fn or_insert_with_coord<F: FnOnce(Coord) -> T>(
+    self,
+    default: F,
+  ) -> &'a mut T {
+    match self {
+      Entry::Occupied(it) => it.into_mut(),
+      Entry::Vacant(it) => {
+        let val = default(it.coord);
+        it.insert(val)
+      }
+    }
+  }
+
+  /// Modify the value in the slot if there is one.
+  pub fn and_modify<F: FnOnce(&mut T)>(mut self, f: F) -> Self {
+    if let Entry::Occupied(it) = &mut self {
+      f(it.get_mut());
+    }
+    self
+  }
+}
+
+impl<'a, T: Default> Entry<'a, T> {
+  /// Fill the slot with the default value if it's empty, and return a
+  /// reference to the value in it.
+  pub fn or_default(self) -> &'a mut T {
+    self.or_insert_with(T::default)
+  }
+}
+
+/// A filled slot in a [`Grid`](super::Grid).
+pub struct OccupiedEntry<'a, T> {
+  coord: Coord,
+  // Invariant: this is always `Some`
+  slot: &'a mut Option<T>,
+}
+
+impl<'a, T> OccupiedEntry<'a, T> {
+  /// The coordinate of this entry.
+  pub fn coord(&self) -> Coord {
+    self.coord
+  }
+
+  pub fn get(&self) -> &T {
+    self.slot.as_ref().unwrap()
+  }
+
+  pub fn get_mut(&mut self) -> &mut T {
+    self.slot.as_mut().unwrap()
+  }
+
+  /// Turn this into a reference to the value that lives as long as the grid borrow.
+  pub fn into_mut(self) -> &'a mut T {
+    self.slot.as_mut().unwrap()
+  }
+
+  /// Replace the value in the slot, returning the old one.
+  pub fn insert(&mut self, val: T) -> T {
+    self.slot.replace(val).unwrap()
+  }
+
+  /// Empty the slot, returning the value that was in it.
+  pub fn remove(self) -> T {
+    self.slot.take().unwrap()
+  }
+}
+
+/// An empty slot in a [`Grid`](super::Grid).
+pub struct VacantEntry<'a, T> {
+  coord: Coord,
+  // Invariant: this is always `None`
+  slot: &'a mut Option<T>,
+}
+
+impl<'a, T> VacantEntry<'a, T> {
+  /// The coordinate of this entry.
+  pub fn coord(&self) -> Coord {
+    self.coord
+  }
+
+  /// Fill the slot, returning a reference to the new value.
+  pub fn insert(self, val: T) -> &'a mut T {
+    self.slot.insert(val)
+  }
+}
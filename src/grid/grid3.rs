@@ -0,0 +1,297 @@
+use crate::{Coord, Coord3};
+
+use super::{Grid, GridIter};
+
+/// A stack of same-sized layers, each like a [`Grid`]: the 3d version of
+/// a grid, for things like z-levels.
+///
+/// Each layer is stored contiguously, so looking at one layer as a
+/// [`GridView`] is free.
+///
+/// With the `serde` feature, these serialize like a [`Grid`] with an extra
+/// `depth`, with the cells in the runs going layer by layer. The runs must
+/// add up to exactly `width * height * depth` cells.
+#[derive(Debug, Clone)]
+pub struct Grid3<T> {
+  pub(super) width: u32,
+  pub(super) height: u32,
+  pub(super) depth: u32,
+  pub(super) spots: Vec<Option<T>>,
+}
+
+impl<T> Grid3<T> {
+  pub fn new(width: u32, height: u32, depth: u32) -> Self {
+    Self {
+      width,
+      height,
+      depth,
+      spots: std::iter::repeat_with(|| None)
+        .take((width * height * depth) as usize)
+        .collect(),
+    }
+  }
+
+  /// Stack up some grids, the first one being layer 0.
+  ///
+  /// Panics if the grids aren't all the same size.
+  pub fn from_layers(layers: Vec<Grid<T>>) -> Self {
+    let (width, height) = layers
+      .first()
+      .map_or((0, 0), |grid| (grid.width, grid.height));
+    let depth = layers.len() as u32;
+    let mut spots = Vec::with_capacity((width * height * depth) as usize);
+    for grid in layers {
+      assert_eq!(
+        (grid.width, grid.height),
+        (width, height),
+        "every layer must be the same size"
+      );
+      spots.extend(grid.spots);
+    }
+    Self {
+      width,
+      height,
+      depth,
+      spots,
+    }
+  }
+
+  /// Split this back up into one grid per layer.
+  pub fn into_layers(self) -> Vec<Grid<T>> {
+    let (width, height) = (self.width, self.height);
+    let mut spots = self.spots.into_iter();
+    (0..self.depth)
+      .map(|_| Grid {
+        width,
+        height,
+        spots: spots.by_ref().take((width * height) as usize).collect(),
+      })
+      .collect()
+  }
+
+  /// Add a layer on top, so it's at `z = depth`.
+  ///
+  /// Panics if it isn't the same size as the other layers.
+  pub fn push_layer(&mut self, layer: Grid<T>) {
+    assert_eq!(
+      (layer.width, layer.height),
+      (self.width, self.height),
+      "every layer must be the same size"
+    );
+    self.spots.extend(layer.spots);
+    self.depth += 1;
+  }
+
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  /// The number of layers.
+  pub fn depth(&self) -> u32 {
+    self.depth
+  }
+
+  pub fn get(&self, coord: Coord3) -> Option<&T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].as_ref()
+  }
+
+  pub fn get_mut(&mut self, coord: Coord3) -> Option<&mut T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].as_mut()
+  }
+
+  /// Returns the old value. Like [`Grid::insert`], out of bounds values are
+  /// dropped.
+  pub fn insert(&mut self, coord: Coord3, val: T) -> Option<T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].replace(val)
+  }
+
+  pub fn remove(&mut self, coord: Coord3) -> Option<T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].take()
+  }
+
+  pub fn contains(&self, coord: Coord3) -> bool {
+    self.get(coord).is_some()
+  }
+
+  pub fn is_coord_valid(&self, coord: Coord3) -> bool {
+    self.idx(coord).is_some()
+  }
+
+  /// Iterate over the filled cells, layer by layer.
+  pub fn iter(&self) -> impl Iterator<Item = (Coord3, &T)> {
+    self
+      .spots
+      .iter()
+      .enumerate()
+      .filter_map(|(idx, slot)| Some((self.coord_of(idx), slot.as_ref()?)))
+  }
+
+  pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord3, &mut T)> {
+    let (width, height) = (self.width, self.height);
+    self
+      .spots
+      .iter_mut()
+      .enumerate()
+      .filter_map(move |(idx, slot)| {
+        Some((coord_of(idx, width, height), slot.as_mut()?))
+      })
+  }
+
+  /// Look at one layer as a 2d grid, or `None` if there's no such layer.
+  pub fn layer(&self, z: u32) -> Option<GridView<'_, T>> {
+    if z >= self.depth {
+      return None;
+    }
+    let size = (self.width * self.height) as usize;
+    let start = z as usize * size;
+    Some(GridView {
+      width: self.width,
+      height: self.height,
+      spots: &self.spots[start..start + size],
+    })
+  }
+
+  /// Look at each layer in turn, from `z = 0` up.
+  pub fn layers(&self) -> impl Iterator<Item = GridView<'_, T>> {
+    (0..self.depth).map(|z| self.layer(z).unwrap())
+  }
+
+  fn idx(&self, coord: Coord3) -> Option<usize> {
+    if coord.x < self.width && coord.y < self.height && coord.z < self.depth {
+      Some(
+        ((coord.z as usize * self.height as usize) + coord.y as usize)
+          * self.width as usize
+          + coord.x as usize,
+      )
+    } else {
+      None
+    }
+  }
+
+  fn coord_of(&self, idx: usize) -> Coord3 {
+    coord_of(idx, self.width, self.height)
+  }
+}
+
+fn coord_of(idx: usize, width: u32, height: u32) -> Coord3 {
+  let (width, height) = (width as usize, height as usize);
+  Coord3::new(
+    (idx % width) as u32,
+    (idx / width % height) as u32,
+    (idx / (width * height)) as u32,
+  )
+}
+
+/// A read-only look at one layer of a [`Grid3`], made with [`Grid3::layer`].
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+  width: u32,
+  height: u32,
+  spots: &'a [Option<T>],
+}
+
+impl<'a, T> GridView<'a, T> {
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  pub fn get(&self, coord: Coord) -> Option<&'a T> {
+    if coord.x < self.width && coord.y < self.height {
+      self.spots[coord.y as usize * self.width as usize + coord.x as usize]
+        .as_ref()
+    } else {
+      None
+    }
+  }
+
+  pub fn contains(&self, coord: Coord) -> bool {
+    self.get(coord).is_some()
+  }
+
+  pub fn iter(&self) -> GridIter<'a, T> {
+    GridIter {
+      inner: self.spots.iter().enumerate(),
+      width: self.width,
+    }
+  }
+
+  /// Copy this layer out into its own grid.
+  pub fn to_grid(&self) -> Grid<T>
+  where
+    T: Clone,
+  {
+    Grid {
+      width: self.width,
+      height: self.height,
+      spots: self.spots.to_vec(),
+    }
+  }
+}
+
+impl<T> Clone for GridView<'_, T> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, Coord3, Grid, Grid3};
+
+  #[test]
+  fn get_and_insert() {
+    let mut grid = Grid3::new(3, 2, 4);
+    assert_eq!(grid.insert(Coord3::new(2, 1, 3), 'a'), None);
+    assert_eq!(grid.insert(Coord3::new(0, 1, 0), 'b'), None);
+    assert_eq!(grid.insert(Coord3::new(2, 1, 3), 'c'), Some('a'));
+    assert_eq!(grid.get(Coord3::new(2, 1, 3)), Some(&'c'));
+    assert_eq!(grid.get(Coord3::new(2, 1, 4)), None);
+    assert!(!grid.is_coord_valid(Coord3::new(3, 0, 0)));
+    assert_eq!(grid.insert(Coord3::new(3, 0, 0), 'x'), None);
+    assert_eq!(grid.iter().count(), 2);
+    *grid.get_mut(Coord3::new(0, 1, 0)).unwrap() = 'd';
+    assert_eq!(
+      grid.iter().collect::<Vec<_>>(),
+      [(Coord3::new(0, 1, 0), &'d'), (Coord3::new(2, 1, 3), &'c')]
+    );
+    assert_eq!(grid.remove(Coord3::new(0, 1, 0)), Some('d'));
+    assert_eq!(grid.iter_mut().count(), 1);
+  }
+
+  #[test]
+  fn layers() {
+    let floors = vec![
+      Grid::from_str_map("#.\n.#", |c, _| (c == '#').then_some(c)),
+      Grid::from_str_map("..\n>.", |c, _| (c != '.').then_some(c)),
+    ];
+    let mut grid = Grid3::from_layers(floors);
+    assert_eq!((grid.width(), grid.height(), grid.depth()), (2, 2, 2));
+    assert_eq!(grid.get(Coord3::new(0, 1, 1)), Some(&'>'));
+
+    let top = grid.layer(1).unwrap();
+    assert_eq!(top.get(Coord::new(0, 1)), Some(&'>'));
+    assert_eq!(top.iter().count(), 1);
+    assert_eq!(top.to_grid().to_string(), "..\n>.");
+    assert!(grid.layer(2).is_none());
+
+    grid.push_layer(Grid::new(2, 2));
+    assert_eq!(grid.layers().count(), 3);
+    let back: Vec<_> =
+      grid.into_layers().iter().map(|g| g.to_string()).collect();
+    assert_eq!(back, ["#.\n.#", "..\n>.", "..\n.."]);
+  }
+}
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+
+use crate::Coord;
+
+use super::Grid;
+
+/// The cells that changed between two grids of the same size, made with
+/// [`Grid::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridDiff<T> {
+  changes: Vec<(Coord, Option<T>)>,
+}
+
+impl<T> GridDiff<T> {
+  /// Is there no difference at all?
+  pub fn is_empty(&self) -> bool {
+    self.changes.is_empty()
+  }
+
+  /// The number of changed cells.
+  pub fn len(&self) -> usize {
+    self.changes.len()
+  }
+
+  /// Iterate over each changed cell and what it changed to.
+  pub fn iter(&self) -> impl Iterator<Item = (Coord, Option<&T>)> {
+    self
+      .changes
+      .iter()
+      .map(|(coord, val)| (*coord, val.as_ref()))
+  }
+}
+
+impl<T: Clone + PartialEq> Grid<T> {
+  /// Find the cells that need to change to turn this grid into `other`.
+  ///
+  /// Panics if the grids aren't the same size.
+  pub fn diff(&self, other: &Grid<T>) -> GridDiff<T> {
+    assert_eq!(
+      (self.width, self.height),
+      (other.width, other.height),
+      "can only diff grids of the same size"
+    );
+    let changes = self
+      .spots
+      .iter()
+      .zip(other.spots.iter())
+      .enumerate()
+      .filter(|(_, (old, new))| old != new)
+      .map(|(idx, (_, new))| (self.coord_of(idx), new.clone()))
+      .collect();
+    GridDiff { changes }
+  }
+
+  /// Apply the changes from [`Self::diff`].
+  pub fn apply_diff(&mut self, diff: &GridDiff<T>) {
+    for (coord, val) in diff.iter() {
+      match val {
+        Some(val) => self.insert(coord, val.clone()),
+        None => self.remove(coord),
+      };
+    }
+  }
+}
+
+/// Keeps the last few states of a [`Grid`], for rewinding time or replays.
+///
+/// Only the latest grid is stored in full. Older turns are stored as the
+/// [`GridDiff`] needed to step back to them, so a long history of mostly
+/// unchanging grids stays small.
+///
+/// Turns are numbered from 0, the grid the history was made with, and go up
+/// by one each time a grid is [pushed](Self::push).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawGridHistory<T>"))]
+pub struct GridHistory<T> {
+  current: Grid<T>,
+  /// Oldest first. Each one turns the turn after it back into its turn.
+  undo: VecDeque<GridDiff<T>>,
+  turn: usize,
+  capacity: usize,
+}
+
+impl<T: Clone + PartialEq> GridHistory<T> {
+  /// Start a history at `initial`, remembering up to `capacity` turns
+  /// before the current one.
+  pub fn new(initial: Grid<T>, capacity: usize) -> Self {
+    Self {
+      current: initial,
+      undo: VecDeque::new(),
+      turn: 0,
+      capacity,
+    }
+  }
+
+  /// The latest grid.
+  pub fn current(&self) -> &Grid<T> {
+    &self.current
+  }
+
+  /// The number of the latest turn.
+  pub fn turn(&self) -> usize {
+    self.turn
+  }
+
+  /// The number of the oldest turn still remembered.
+  pub fn oldest_turn(&self) -> usize {
+    self.turn - self.undo.len()
+  }
+
+  /// Record the next turn.
+  ///
+  /// If this goes over capacity, the oldest turn is forgotten.
+  /// Panics if `next` isn't the same size as the current grid.
+  pub fn push(&mut self, next: Grid<T>) {
+    let undo = next.diff(&self.current);
+    self.current = next;
+    self.turn += 1;
+    if self.capacity == 0 {
+      return;
+    }
+    if self.undo.len() == self.capacity {
+      self.undo.pop_front();
+    }
+    self.undo.push_back(undo);
+  }
+
+  /// Reconstruct the grid as it was on the given turn, or `None` if that turn
+  /// is too old or hasn't happened yet.
+  pub fn at(&self, turn: usize) -> Option<Grid<T>> {
+    if turn > self.turn || turn < self.oldest_turn() {
+      return None;
+    }
+    let mut grid = self.current.clone();
+    for diff in self.undo.iter().rev().take(self.turn - turn) {
+      grid.apply_diff(diff);
+    }
+    Some(grid)
+  }
+
+  /// Go back `n` turns, forgetting everything after that.
+  ///
+  /// If there aren't `n` turns remembered, this goes back as far as it can.
+  /// Returns the number of turns actually rewound.
+  pub fn rewind(&mut self, n: usize) -> usize {
+    let n = n.min(self.undo.len());
+    for _ in 0..n {
+      let diff = self.undo.pop_back().unwrap();
+      self.current.apply_diff(&diff);
+    }
+    self.turn -= n;
+    n
+  }
+}
+
+/// What a [`GridHistory`] deserializes from, before checking the turns add
+/// up.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "GridHistory")]
+struct RawGridHistory<T> {
+  current: Grid<T>,
+  undo: VecDeque<GridDiff<T>>,
+  turn: usize,
+  capacity: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<T> TryFrom<RawGridHistory<T>> for GridHistory<T> {
+  type Error = String;
+
+  fn try_from(raw: RawGridHistory<T>) -> Result<Self, Self::Error> {
+    if raw.undo.len() > raw.capacity {
+      return Err(format!(
+        "history remembers {} turns, but its capacity is {}",
+        raw.undo.len(),
+        raw.capacity
+      ));
+    }
+    if raw.undo.len() > raw.turn {
+      return Err(format!(
+        "history remembers {} turns, but it's only on turn {}",
+        raw.undo.len(),
+        raw.turn
+      ));
+    }
+    Ok(GridHistory {
+      current: raw.current,
+      undo: raw.undo,
+      turn: raw.turn,
+      capacity: raw.capacity,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{grid::text::char_grid, Coord, GridHistory};
+
+  #[test]
+  fn diff() {
+    let a = char_grid("ab.\n..c");
+    let b = char_grid("a..\nx.c");
+    let diff = a.diff(&b);
+    assert_eq!(
+      diff.iter().collect::<Vec<_>>(),
+      [(Coord::new(1, 0), None), (Coord::new(0, 1), Some(&'x'))]
+    );
+    let mut patched = a.clone();
+    patched.apply_diff(&diff);
+    assert_eq!(patched.to_string(), b.to_string());
+    assert!(a.diff(&a).is_empty());
+  }
+
+  #[test]
+  fn rewind() {
+    let mut history = GridHistory::new(char_grid("@.."), 2);
+    history.push(char_grid(".@."));
+    history.push(char_grid("..@"));
+    history.push(char_grid("..@"));
+    assert_eq!(history.turn(), 3);
+    assert_eq!(history.oldest_turn(), 1);
+
+    assert!(history.at(0).is_none());
+    assert!(history.at(4).is_none());
+    assert_eq!(history.at(1).unwrap().to_string(), ".@.");
+    assert_eq!(history.at(3).unwrap().to_string(), "..@");
+
+    assert_eq!(history.rewind(5), 2);
+    assert_eq!(history.turn(), 1);
+    assert_eq!(history.current().to_string(), ".@.");
+    history.push(char_grid("@.."));
+    assert_eq!(history.turn(), 2);
+    assert_eq!(history.at(1).unwrap().to_string(), ".@.");
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde() {
+    let mut history = GridHistory::new(char_grid("@.."), 2);
+    history.push(char_grid(".@."));
+    history.push(char_grid("..@"));
+    let json = serde_json::to_string(&history).unwrap();
+    let back: GridHistory<char> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.oldest_turn(), 0);
+    assert_eq!(back.at(1).unwrap().to_string(), ".@.");
+
+    let load = |turn: usize, capacity: usize| {
+      let bad = json
+        .replace(r#""turn":2"#, &format!(r#""turn":{}"#, turn))
+        .replace(r#""capacity":2"#, &format!(r#""capacity":{}"#, capacity));
+      serde_json::from_str::<GridHistory<char>>(&bad)
+    };
+    assert!(load(2, 2).is_ok());
+    // Two turns remembered before turn 1
+    assert!(load(1, 2).is_err());
+    // Two turns remembered with room for one
+    assert!(load(2, 1).is_err());
+  }
+}
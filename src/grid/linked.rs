@@ -0,0 +1,194 @@
+use std::fmt::Debug;
+
+use crate::{Area, Coord};
+
+use super::Grid;
+
+/// A grid plus a second grid worked out from it, kept in sync.
+///
+/// This is for things like autotiling, where each tile in the grid you draw
+/// depends on a cell of the grid your game logic uses and its neighbors.
+/// Every change goes through here, and re-derives the changed cell and the
+/// 8 cells around it, so the derived grid can't go stale.
+///
+/// `derive` gets the source grid and a coordinate, and returns what goes in
+/// the derived grid there. It should only look at that cell and its
+/// neighbors, or other cells won't be updated when they should be.
+///
+/// ```
+/// # use aglet::{Coord, Grid, LinkedGrids};
+/// // Each wall counts the walls beside it
+/// let walls = Grid::from_str_map("#..\n...", |c, _| (c == '#').then_some(()));
+/// let mut linked = LinkedGrids::new(walls, |walls: &Grid<()>, c: Coord| {
+///     walls.contains(c).then(|| walls.neighbors(c).count())
+/// });
+/// assert_eq!(linked.derived().get(Coord::new(0, 0)), Some(&0));
+///
+/// linked.insert(Coord::new(1, 0), ());
+/// assert_eq!(linked.derived().get(Coord::new(0, 0)), Some(&1));
+/// assert_eq!(linked.derived().get(Coord::new(1, 0)), Some(&1));
+/// ```
+#[derive(Clone)]
+pub struct LinkedGrids<A, B, F> {
+  source: Grid<A>,
+  derived: Grid<B>,
+  derive: F,
+}
+
+impl<A, B, F> LinkedGrids<A, B, F>
+where
+  F: FnMut(&Grid<A>, Coord) -> Option<B>,
+{
+  /// Link a grid up, deriving the whole second grid from it.
+  pub fn new(source: Grid<A>, derive: F) -> Self {
+    let derived = Grid::new(source.width(), source.height());
+    let mut out = Self {
+      source,
+      derived,
+      derive,
+    };
+    out.refresh_all();
+    out
+  }
+
+  /// The grid the other is derived from.
+  pub fn source(&self) -> &Grid<A> {
+    &self.source
+  }
+
+  /// The grid worked out from the source.
+  pub fn derived(&self) -> &Grid<B> {
+    &self.derived
+  }
+
+  /// Returns the old value. Like [`Grid::insert`], out of bounds values are
+  /// dropped.
+  pub fn insert(&mut self, coord: Coord, val: A) -> Option<A> {
+    let old = self.source.insert(coord, val);
+    self.refresh(coord);
+    old
+  }
+
+  pub fn remove(&mut self, coord: Coord) -> Option<A> {
+    let old = self.source.remove(coord);
+    self.refresh(coord);
+    old
+  }
+
+  /// Change the value at a coordinate in place, if there is one.
+  ///
+  /// Returns whatever `f` does, or `None` if there was no value.
+  pub fn update<R>(
+    &mut self,
+    coord: Coord,
+    f: impl FnOnce(&mut A) -> R,
+  ) -> Option<R> {
+    let out = f(self.source.get_mut(coord)?);
+    self.refresh(coord);
+    Some(out)
+  }
+
+  /// Make lots of changes at once, all within `area`.
+  ///
+  /// This re-derives everything in `area` and around it once at the end,
+  /// instead of once per change. Changes outside of `area` won't be picked
+  /// up until the cells around them are refreshed.
+  pub fn edit_area<R>(
+    &mut self,
+    area: Area,
+    f: impl FnOnce(&mut Grid<A>) -> R,
+  ) -> R {
+    let out = f(&mut self.source);
+    if let Some(dirty) = self.source.area().intersect(&area.expand(1)) {
+      for coord in dirty {
+        self.derive_one(coord);
+      }
+    }
+    out
+  }
+
+  /// Re-derive a cell and its 8 neighbors.
+  ///
+  /// Changes through this type do this already; it's for when `derive` also
+  /// depends on something outside the source grid that changed.
+  pub fn refresh(&mut self, coord: Coord) {
+    if self.source.is_coord_valid(coord) {
+      self.derive_one(coord);
+    }
+    for neighbor in coord.neighbors8_within(self.source.area()) {
+      self.derive_one(neighbor);
+    }
+  }
+
+  /// Re-derive the whole second grid.
+  pub fn refresh_all(&mut self) {
+    for coord in self.source.area() {
+      self.derive_one(coord);
+    }
+  }
+
+  /// Unlink the grids, giving back the source and derived ones.
+  pub fn into_parts(self) -> (Grid<A>, Grid<B>) {
+    (self.source, self.derived)
+  }
+
+  fn derive_one(&mut self, coord: Coord) {
+    match (self.derive)(&self.source, coord) {
+      Some(val) => self.derived.insert(coord, val),
+      None => self.derived.remove(coord),
+    };
+  }
+}
+
+impl<A: Debug, B: Debug, F> Debug for LinkedGrids<A, B, F> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("LinkedGrids")
+      .field("source", &self.source)
+      .field("derived", &self.derived)
+      .finish_non_exhaustive()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Area, Coord, Grid, LinkedGrids};
+
+  /// How many of the 8 cells around each water cell are also water.
+  fn shore(water: &Grid<()>, c: Coord) -> Option<usize> {
+    water.contains(c).then(|| water.neighbors8(c).count())
+  }
+
+  #[test]
+  fn stays_in_sync() {
+    let water = Grid::from_fn(4, 4, |c| (c.x < 2).then_some(()));
+    let mut linked = LinkedGrids::new(water, shore);
+    let check = |linked: &LinkedGrids<(), usize, _>| {
+      let fresh = LinkedGrids::new(linked.source().clone(), shore);
+      let tiles = |l: &LinkedGrids<(), usize, _>| {
+        l.derived().iter().map(|(c, n)| (c, *n)).collect::<Vec<_>>()
+      };
+      assert_eq!(tiles(linked), tiles(&fresh));
+    };
+    check(&linked);
+
+    assert_eq!(linked.insert(Coord::new(2, 1), ()), None);
+    assert_eq!(linked.derived().get(Coord::new(1, 0)), Some(&4));
+    check(&linked);
+    assert_eq!(linked.update(Coord::new(3, 3), |_| ()), None);
+    assert_eq!(linked.remove(Coord::new(1, 3)), Some(()));
+    assert_eq!(linked.derived().get(Coord::new(1, 3)), None);
+    check(&linked);
+
+    let corner = Area::new(Coord::new(2, 2), 2, 2);
+    linked.edit_area(corner, |g| {
+      for c in corner {
+        g.insert(c, ());
+      }
+    });
+    check(&linked);
+
+    let (source, derived) = linked.into_parts();
+    assert!(source.contains(Coord::new(3, 3)));
+    assert_eq!(derived.get(Coord::new(3, 3)), Some(&3));
+  }
+}
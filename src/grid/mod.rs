@@ -0,0 +1,956 @@
+mod attack;
+mod automaton;
+mod autotile;
+mod bitgrid;
+mod bulk;
+mod chunked;
+mod combine;
+mod compositor;
+mod edge_grid;
+mod entry;
+mod grid3;
+mod history;
+mod linked;
+mod morphology;
+mod occupancy;
+#[cfg(feature = "rayon")]
+mod par;
+mod quantize;
+#[cfg(feature = "rand")]
+mod random;
+mod resize;
+#[cfg(feature = "serde")]
+mod ser;
+mod spawner;
+mod terrain;
+mod text;
+mod torus;
+mod transform;
+mod voronoi;
+mod world;
+
+pub use attack::*;
+pub use automaton::*;
+pub use bulk::*;
+pub use chunked::*;
+pub use compositor::*;
+pub use edge_grid::*;
+pub use entry::*;
+pub use grid3::*;
+pub use history::*;
+pub use linked::*;
+pub use occupancy::*;
+pub use spawner::*;
+pub use terrain::*;
+pub use torus::*;
+pub use transform::*;
+pub use voronoi::*;
+pub use world::*;
+
+use std::{
+  fmt::{Debug, Write},
+  iter::{Enumerate, FusedIterator},
+  slice, vec,
+};
+
+use crate::{Area, Direction8, Direction9, Neighbors, UnsignedScalar};
+
+use super::Coord;
+
+/// Like a `HashMap<Coord, T>` but faster. Each grid point might store something.
+///
+/// With the `serde` feature, grids serialize as their `width`, `height`, and
+/// `cells` in row-major order. The cells are a list of runs: `{"empty": n}` is
+/// `n` empty cells in a row, and `{"filled": [a, b, ...]}` is that many filled
+/// cells with those values. So in JSON, a 3x2 grid with `'a'` at the top-left
+/// and `'b'` at the bottom-right is
+/// `{"width":3,"height":2,"cells":[{"filled":["a"]},{"empty":4},{"filled":["b"]}]}`.
+/// When deserializing, the runs must add up to exactly `width * height` cells.
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+  width: u32,
+  height: u32,
+  spots: Vec<Option<T>>,
+}
+
+impl<T> Grid<T> {
+  pub fn new(width: u32, height: u32) -> Grid<T> {
+    Self {
+      width,
+      height,
+      spots: std::iter::repeat_with(|| None)
+        .take((width * height) as usize)
+        .collect(),
+    }
+  }
+
+  pub fn get<S: UnsignedScalar>(&self, coord: Coord<S>) -> Option<&T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].as_ref()
+  }
+
+  pub fn get_mut<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+  ) -> Option<&mut T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].as_mut()
+  }
+
+  /// Returns the old value
+  pub fn insert<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+    val: T,
+  ) -> Option<T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].replace(val)
+  }
+
+  pub fn get_or_insert_with<S: UnsignedScalar, F: FnOnce() -> T>(
+    &mut self,
+    coord: Coord<S>,
+    fallback: F,
+  ) -> &mut T {
+    // Workaround "get or insert" limitation in borrowck
+    if self.get(coord).is_some() {
+      return self.get_mut(coord).unwrap();
+    }
+    self.insert(coord, fallback());
+    self.get_mut(coord).unwrap()
+  }
+  pub fn get_or_insert<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+    fallback: T,
+  ) -> &mut T {
+    self.get_or_insert_with(coord, || fallback)
+  }
+
+  pub fn remove<S: UnsignedScalar>(&mut self, coord: Coord<S>) -> Option<T> {
+    let idx = self.idx(coord)?;
+    self.spots[idx].take()
+  }
+
+  /// Directly insert an option into the map, removing the old value if it's `None`.
+  ///
+  /// Returns the old value.
+  pub fn insert_direct<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+    val: Option<T>,
+  ) -> Option<T> {
+    let idx = self.idx(coord)?;
+    std::mem::replace(&mut self.spots[idx], val)
+  }
+
+  /// Get the slot at the given coord for in-place manipulation,
+  /// like `HashMap::entry`.
+  ///
+  /// Returns `None` if the coord is out of bounds.
+  pub fn entry<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+  ) -> Option<Entry<'_, T>> {
+    let idx = self.idx(coord)?;
+    let coord = self.coord_of(idx);
+    Some(Entry::new(coord, &mut self.spots[idx]))
+  }
+
+  /// Take all the values out of this grid, leaving it empty.
+  ///
+  /// The returned grid is the same size as this one.
+  pub fn take(&mut self) -> Grid<T> {
+    let empty = Grid::new(self.width, self.height);
+    std::mem::replace(self, empty)
+  }
+
+  /// Only keep the values for which `keep` returns `true`,
+  /// emptying all the other slots.
+  pub fn retain<F: FnMut(Coord, &mut T) -> bool>(&mut self, mut keep: F) {
+    let width = self.width;
+    for (idx, slot) in self.spots.iter_mut().enumerate() {
+      if let Some(it) = slot {
+        let idx = idx as u32;
+        if !keep(Coord::new(idx % width, idx / width), it) {
+          *slot = None;
+        }
+      }
+    }
+  }
+
+  /// Empty every slot in the grid. The size stays the same.
+  pub fn clear(&mut self) {
+    self.spots.iter_mut().for_each(|slot| *slot = None);
+  }
+
+  /// Empty the grid, iterating over all the values that were in it.
+  ///
+  /// If the iterator is dropped early, the rest of the values are dropped too.
+  pub fn drain(&mut self) -> GridDrain<'_, T> {
+    GridDrain {
+      inner: self.spots.iter_mut().enumerate(),
+      width: self.width,
+    }
+  }
+
+  pub fn contains<S: UnsignedScalar>(&self, coord: Coord<S>) -> bool {
+    match self.idx(coord) {
+      Some(idx) => self.spots[idx].is_some(),
+      None => false,
+    }
+  }
+
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  /// Iterate over all the (filled) slots in the grid.
+  pub fn iter(&self) -> GridIter<'_, T> {
+    GridIter {
+      inner: self.spots.iter().enumerate(),
+      width: self.width,
+    }
+  }
+
+  /// Iterate mutably over all the (filled) slots in the grid.
+  pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
+    GridIterMut {
+      inner: self.spots.iter_mut().enumerate(),
+      width: self.width,
+    }
+  }
+
+  /// Iterate over the coordinates of the filled slots.
+  pub fn keys(
+    &self,
+  ) -> impl DoubleEndedIterator<Item = Coord> + FusedIterator + '_ {
+    self.iter().map(|(coord, _)| coord)
+  }
+
+  /// Iterate over the values in the filled slots.
+  pub fn values(
+    &self,
+  ) -> impl DoubleEndedIterator<Item = &T> + FusedIterator + '_ {
+    self.iter().map(|(_, val)| val)
+  }
+
+  /// Iterate mutably over the values in the filled slots.
+  pub fn values_mut(
+    &mut self,
+  ) -> impl DoubleEndedIterator<Item = &mut T> + FusedIterator + '_ {
+    self.iter_mut().map(|(_, val)| val)
+  }
+
+  /// Iterate over every coordinate in the grid, filled or not, in row-major
+  /// order.
+  pub fn coords(
+    &self,
+  ) -> impl DoubleEndedIterator<Item = Coord> + ExactSizeIterator + FusedIterator
+  {
+    let width = self.width;
+    (0..self.spots.len()).map(move |idx| coord_at(idx, width))
+  }
+
+  /// Iterate over the filled slots nearest-first, ring by ring outwards from
+  /// `origin`.
+  ///
+  /// Distance is Chebyshev distance, so each ring is a square. Within a
+  /// ring, slots go clockwise from due north, like in a [`RingIter`](crate::RingIter). The
+  /// origin doesn't have to be on the grid.
+  ///
+  /// Only the parts of each ring on the grid are visited, starting with the
+  /// first ring that touches it, so a far-off origin costs no more than a
+  /// nearby one.
+  pub fn iter_from(
+    &self,
+    origin: Coord,
+  ) -> impl Iterator<Item = (Coord, &T)> + '_ {
+    let (width, height) = (self.width as i64, self.height as i64);
+    let (x, y) = (origin.x as i64, origin.y as i64);
+    // How far it is to the nearest and farthest cells along each axis
+    let near = |at: i64, len: i64| (at - (len - 1)).max(0);
+    let far = |at: i64, len: i64| at.max(len - 1 - at);
+    let (first, last) = if width == 0 || height == 0 {
+      (1, 0)
+    } else {
+      (
+        near(x, width).max(near(y, height)),
+        far(x, width).max(far(y, height)),
+      )
+    };
+    (first..=last)
+      .flat_map(move |radius| clipped_ring((x, y), radius, (width, height)))
+      .filter_map(move |coord| Some((coord, self.get(coord)?)))
+  }
+
+  /// Iterate over the filled orthagonal neighbors of the given coord.
+  pub fn neighbors(&self, coord: Coord) -> GridNeighbors<'_, T> {
+    GridNeighbors {
+      grid: self,
+      inner: coord.neighbors4_bounded(self.width, self.height),
+    }
+  }
+
+  /// Iterate over the filled orthagonal and diagonal neighbors of the given coord.
+  pub fn neighbors8(&self, coord: Coord) -> GridNeighbors<'_, T> {
+    GridNeighbors {
+      grid: self,
+      inner: coord.neighbors8_bounded(self.width, self.height),
+    }
+  }
+
+  /// Return whether the given coord even fits in the grid.
+  pub fn is_coord_valid<S: UnsignedScalar>(&self, coord: Coord<S>) -> bool {
+    self.idx(coord).is_some()
+  }
+
+  /// Return an area covering the whole grid (including empties).
+  pub fn area(&self) -> Area {
+    Area::new(Coord::ZERO, self.width(), self.height())
+  }
+
+  /// Stamp a 3x3 "nine-slice" over the given area.
+  ///
+  /// Each cell in the area is filled with `piece(dir)`, where `dir` says which
+  /// part of the stamp the cell falls on: the four corners get the diagonal
+  /// directions, the edges get the orthogonal ones, and the interior gets
+  /// [`Direction9::Center`]. This is handy for UI panels or rooms with distinct
+  /// corner tiles.
+  ///
+  /// If the area is only 1 wide or tall, the west or north pieces win.
+  /// Cells out of bounds of the grid are skipped.
+  pub fn draw_nine_slice<F: FnMut(Direction9) -> T>(
+    &mut self,
+    area: Area,
+    mut piece: F,
+  ) {
+    for y in area.y1()..area.y2() {
+      for x in area.x1()..area.x2() {
+        let coord = Coord::new(x, y);
+        if !self.is_coord_valid(coord) {
+          continue;
+        }
+
+        let horiz = if x == area.x1() {
+          -1
+        } else if x == area.x2() - 1 {
+          1
+        } else {
+          0
+        };
+        let vert = if y == area.y1() {
+          -1
+        } else if y == area.y2() - 1 {
+          1
+        } else {
+          0
+        };
+        let dir = match (horiz, vert) {
+          (-1, -1) => Direction9::NorthWest,
+          (0, -1) => Direction9::North,
+          (1, -1) => Direction9::NorthEast,
+          (-1, 0) => Direction9::West,
+          (1, 0) => Direction9::East,
+          (-1, 1) => Direction9::SouthWest,
+          (0, 1) => Direction9::South,
+          (1, 1) => Direction9::SouthEast,
+          _ => Direction9::Center,
+        };
+        self.insert(coord, piece(dir));
+      }
+    }
+  }
+
+  fn coord_of(&self, idx: usize) -> Coord {
+    coord_at(idx, self.width)
+  }
+
+  fn idx<S: UnsignedScalar>(&self, coord: Coord<S>) -> Option<usize> {
+    let x = coord.x.to_usize()?;
+    let y = coord.y.to_usize()?;
+    if x >= self.width as usize || y >= self.height as usize {
+      None
+    } else {
+      Some(self.width as usize * y + x)
+    }
+  }
+}
+
+impl<T: Debug> Grid<T> {
+  /// Describe everything the grid knows about the given cell, in a human-readable
+  /// multi-line string.
+  ///
+  /// This includes the cell's value, where it is in relation to the grid bounds,
+  /// and the values of all its neighbors. Handy for debug consoles.
+  pub fn describe(&self, coord: Coord) -> String {
+    // Writing to a String can't fail
+    let mut out = String::new();
+
+    write!(out, "{} in {}x{} grid", coord, self.width, self.height).unwrap();
+    if !self.is_coord_valid(coord) {
+      out.push_str(" (out of bounds)");
+      return out;
+    }
+    let mut edges = Vec::new();
+    if coord.y == 0 {
+      edges.push("north");
+    }
+    if coord.x == self.width - 1 {
+      edges.push("east");
+    }
+    if coord.y == self.height - 1 {
+      edges.push("south");
+    }
+    if coord.x == 0 {
+      edges.push("west");
+    }
+    match edges.len() {
+      0 => {}
+      1 => write!(out, " (on {} edge)", edges[0]).unwrap(),
+      _ => write!(out, " (on {} edges)", edges.join(" and ")).unwrap(),
+    }
+    out.push('\n');
+
+    write!(out, "  value: ").unwrap();
+    self.describe_slot(&mut out, coord);
+
+    for dir in Direction8::DIRECTIONS {
+      match coord.offset8(dir).filter(|c| self.is_coord_valid(*c)) {
+        Some(neighbor) => {
+          write!(out, "\n  {:?} {}: ", dir, neighbor).unwrap();
+          self.describe_slot(&mut out, neighbor);
+        }
+        None => write!(out, "\n  {:?}: out of bounds", dir).unwrap(),
+      }
+    }
+
+    out
+  }
+
+  fn describe_slot(&self, out: &mut String, coord: Coord) {
+    match self.get(coord) {
+      Some(it) => write!(out, "{:?}", it).unwrap(),
+      None => out.push_str("empty"),
+    }
+  }
+}
+
+impl Grid<f32> {
+  /// Find the cell where this and `other` differ the most, and by how much.
+  ///
+  /// A cell that's filled in one grid but empty in the other (or NaN in only one)
+  /// counts as infinitely different.
+  ///
+  /// Returns `None` if the grids are different sizes, or if neither has any cells
+  /// filled in.
+  pub fn max_difference(&self, other: &Grid<f32>) -> Option<(Coord, f32)> {
+    if self.width != other.width || self.height != other.height {
+      return None;
+    }
+
+    let mut worst: Option<(Coord, f32)> = None;
+    for (idx, (a, b)) in self.spots.iter().zip(other.spots.iter()).enumerate() {
+      let diff = match (a, b) {
+        (None, None) => continue,
+        (Some(a), Some(b)) if a.is_nan() && b.is_nan() => 0.0,
+        (Some(a), Some(b)) if !a.is_nan() && !b.is_nan() => (a - b).abs(),
+        _ => f32::INFINITY,
+      };
+      if worst.is_none_or(|(_, w)| diff > w) {
+        let idx = idx as u32;
+        worst = Some((Coord::new(idx % self.width, idx / self.width), diff));
+      }
+    }
+    worst
+  }
+
+  /// Check if this grid is the same as `other`, allowing each cell to differ by
+  /// up to `epsilon`.
+  ///
+  /// This is handy for testing floating-point passes like diffusion or noise,
+  /// where exact equality is too strict.
+  /// Grids of different sizes are never equal.
+  pub fn approx_eq(&self, other: &Grid<f32>, epsilon: f32) -> bool {
+    if self.width != other.width || self.height != other.height {
+      return false;
+    }
+    match self.max_difference(other) {
+      Some((_, diff)) => diff <= epsilon,
+      None => true,
+    }
+  }
+}
+
+impl<T> IntoIterator for Grid<T> {
+  type Item = (Coord, T);
+
+  type IntoIter = GridIntoIter<T>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    GridIntoIter {
+      inner: self.spots.into_iter().enumerate(),
+      width: self.width,
+    }
+  }
+}
+
+/// Iterator over the filled neighbors of a slot in a [`Grid`].
+pub struct GridNeighbors<'a, T> {
+  grid: &'a Grid<T>,
+  inner: Neighbors,
+}
+
+impl<'a, T> Iterator for GridNeighbors<'a, T> {
+  type Item = (Coord, &'a T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let grid = self.grid;
+    self
+      .inner
+      .by_ref()
+      .find_map(|coord| grid.get(coord).map(|it| (coord, it)))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, self.inner.size_hint().1)
+  }
+}
+
+/// Borrowing iterator over the filled slots in a [`Grid`].
+///
+/// This goes in row-major order, and can go backwards too.
+pub struct GridIter<'a, T> {
+  inner: Enumerate<slice::Iter<'a, Option<T>>>,
+  width: u32,
+}
+
+impl<'a, T> Iterator for GridIter<'a, T> {
+  type Item = (Coord, &'a T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot.as_ref()?)))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.inner.len()))
+  }
+}
+
+impl<T> DoubleEndedIterator for GridIter<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .by_ref()
+      .rev()
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot.as_ref()?)))
+  }
+}
+
+impl<T> FusedIterator for GridIter<'_, T> {}
+
+/// Mutably borrowing iterator over the filled slots in a [`Grid`].
+pub struct GridIterMut<'a, T> {
+  inner: Enumerate<slice::IterMut<'a, Option<T>>>,
+  width: u32,
+}
+
+impl<'a, T> Iterator for GridIterMut<'a, T> {
+  type Item = (Coord, &'a mut T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot.as_mut()?)))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.inner.len()))
+  }
+}
+
+impl<T> DoubleEndedIterator for GridIterMut<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .by_ref()
+      .rev()
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot.as_mut()?)))
+  }
+}
+
+impl<T> FusedIterator for GridIterMut<'_, T> {}
+
+/// Iterator emptying the filled slots in a [`Grid`]; see [`Grid::drain`].
+pub struct GridDrain<'a, T> {
+  inner: Enumerate<slice::IterMut<'a, Option<T>>>,
+  width: u32,
+}
+
+impl<'a, T> Iterator for GridDrain<'a, T> {
+  type Item = (Coord, T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot.take()?)))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.inner.len()))
+  }
+}
+
+impl<T> DoubleEndedIterator for GridDrain<'_, T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .by_ref()
+      .rev()
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot.take()?)))
+  }
+}
+
+impl<T> FusedIterator for GridDrain<'_, T> {}
+
+impl<'a, T> Drop for GridDrain<'a, T> {
+  fn drop(&mut self) {
+    self.for_each(drop);
+  }
+}
+
+/// Owning iterator over the filled slots in a [`Grid`].
+pub struct GridIntoIter<T> {
+  inner: Enumerate<vec::IntoIter<Option<T>>>,
+  width: u32,
+}
+
+impl<T> Iterator for GridIntoIter<T> {
+  type Item = (Coord, T);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot?)))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    (0, Some(self.inner.len()))
+  }
+}
+
+impl<T> DoubleEndedIterator for GridIntoIter<T> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    let width = self.width;
+    self
+      .inner
+      .by_ref()
+      .rev()
+      .find_map(|(idx, slot)| Some((coord_at(idx, width), slot?)))
+  }
+}
+
+impl<T> FusedIterator for GridIntoIter<T> {}
+
+/// The coordinate of a slot in a grid of the given width.
+fn coord_at(idx: usize, width: u32) -> Coord {
+  let idx = idx as u32;
+  Coord::new(idx % width, idx / width)
+}
+
+/// The cells of the ring at Chebyshev distance `radius` from `center` that
+/// are inside a grid of the given size, in the same order as a
+/// [`RingIter`](crate::RingIter).
+fn clipped_ring(
+  (x, y): (i64, i64),
+  radius: i64,
+  (width, height): (i64, i64),
+) -> impl Iterator<Item = Coord> {
+  let r = radius;
+  // Each side is a start, a step, and a length. The top is split in two so
+  // the ring starts due north.
+  let sides = if r == 0 {
+    vec![((x, y), (0, 0), 1)]
+  } else {
+    vec![
+      ((x, y - r), (1, 0), r),
+      ((x + r, y - r), (0, 1), 2 * r),
+      ((x + r, y + r), (-1, 0), 2 * r),
+      ((x - r, y + r), (0, -1), 2 * r),
+      ((x - r, y - r), (1, 0), r),
+    ]
+  };
+  sides
+    .into_iter()
+    .flat_map(move |((sx, sy), (dx, dy), len)| {
+      // The steps along one axis that stay in `0..size`
+      let clip = |start: i64, step: i64, size: i64| match step {
+        0 if (0..size).contains(&start) => 0..len,
+        0 => 0..0,
+        1 => -start..size - start,
+        _ => start - size + 1..start + 1,
+      };
+      let (xs, ys) = (clip(sx, dx, width), clip(sy, dy, height));
+      let steps = xs.start.max(ys.start).max(0)..xs.end.min(ys.end).min(len);
+      steps.map(move |t| Coord::new((sx + dx * t) as u32, (sy + dy * t) as u32))
+    })
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{grid::text::char_grid, Area, Coord, Direction9, Entry, Grid};
+
+  #[test]
+  fn nine_slice() {
+    let mut grid = Grid::new(6, 5);
+    grid.draw_nine_slice(Area::new(Coord::new(1, 1), 4, 3), |dir| match dir {
+      Direction9::NorthWest
+      | Direction9::NorthEast
+      | Direction9::SouthWest
+      | Direction9::SouthEast => '+',
+      Direction9::North | Direction9::South => '-',
+      Direction9::West | Direction9::East => '|',
+      Direction9::Center => '.',
+    });
+
+    let rows: Vec<String> = (0..5)
+      .map(|y| {
+        (0..6)
+          .map(|x| *grid.get(Coord::new(x, y)).unwrap_or(&' '))
+          .collect()
+      })
+      .collect();
+    assert_eq!(rows, ["      ", " +--+ ", " |..| ", " +--+ ", "      "]);
+  }
+
+  #[test]
+  fn iterators() {
+    let mut grid = char_grid("a.b\n.c.");
+    let backwards: Vec<_> = grid.iter().rev().map(|(_, c)| *c).collect();
+    assert_eq!(backwards, ['c', 'b', 'a']);
+    let mut iter = grid.iter();
+    assert_eq!(iter.size_hint(), (0, Some(6)));
+    assert_eq!(iter.next(), Some((Coord::new(0, 0), &'a')));
+    assert_eq!(iter.next_back(), Some((Coord::new(1, 1), &'c')));
+    assert_eq!(iter.next(), Some((Coord::new(2, 0), &'b')));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(
+      grid.keys().collect::<Vec<_>>(),
+      [Coord::new(0, 0), Coord::new(2, 0), Coord::new(1, 1)]
+    );
+    assert_eq!(grid.values().collect::<String>(), "abc");
+    for c in grid.values_mut().rev() {
+      *c = c.to_ascii_uppercase();
+    }
+    assert_eq!(grid.to_string(), "A.B\n.C.");
+    assert_eq!(grid.coords().len(), 6);
+    assert_eq!(grid.coords().next_back(), Some(Coord::new(2, 1)));
+
+    let mut drain = grid.clone().into_iter();
+    assert_eq!(drain.next_back(), Some((Coord::new(1, 1), 'C')));
+    assert_eq!(
+      grid.drain().rev().map(|(_, c)| c).collect::<String>(),
+      "CBA"
+    );
+    assert_eq!(grid.iter().count(), 0);
+  }
+
+  #[test]
+  fn approx_eq() {
+    let mut a = Grid::new(3, 3);
+    let mut b = Grid::new(3, 3);
+    assert!(a.approx_eq(&b, 0.0));
+    assert_eq!(a.max_difference(&b), None);
+
+    a.insert(Coord::new(0, 0), 1.0);
+    b.insert(Coord::new(0, 0), 1.0 + 1e-6);
+    a.insert(Coord::new(2, 1), 5.0);
+    b.insert(Coord::new(2, 1), 5.5);
+    assert!(!a.approx_eq(&b, 1e-3));
+    assert!(a.approx_eq(&b, 0.5));
+    assert_eq!(a.max_difference(&b), Some((Coord::new(2, 1), 0.5)));
+
+    a.insert(Coord::new(1, 2), 0.0);
+    assert_eq!(
+      a.max_difference(&b),
+      Some((Coord::new(1, 2), f32::INFINITY))
+    );
+    assert!(!a.approx_eq(&b, 100.0));
+    assert!(!a.approx_eq(&Grid::new(3, 4), 100.0));
+  }
+
+  #[test]
+  fn describe() {
+    let mut grid = Grid::new(3, 2);
+    grid.insert(Coord::new(0, 0), "wall");
+    grid.insert(Coord::new(1, 0), "door");
+
+    assert_eq!(
+      grid.describe(Coord::new(1, 0)),
+      "(1, 0) in 3x2 grid (on north edge)
+  value: \"door\"
+  North: out of bounds
+  NorthEast: out of bounds
+  East (2, 0): empty
+  SouthEast (2, 1): empty
+  South (1, 1): empty
+  SouthWest (0, 1): empty
+  West (0, 0): \"wall\"
+  NorthWest: out of bounds"
+    );
+    assert!(grid
+      .describe(Coord::new(2, 1))
+      .starts_with("(2, 1) in 3x2 grid (on east and south edges)\n"));
+    assert_eq!(
+      grid.describe(Coord::new(5, 5)),
+      "(5, 5) in 3x2 grid (out of bounds)"
+    );
+  }
+
+  #[test]
+  fn neighbors() {
+    let mut grid = Grid::new(3, 3);
+    grid.insert(Coord::new(0, 0), 'a');
+    grid.insert(Coord::new(1, 0), 'b');
+    grid.insert(Coord::new(2, 2), 'c');
+
+    let n: Vec<_> = grid.neighbors(Coord::new(0, 0)).collect();
+    assert_eq!(n, [(Coord::new(1, 0), &'b')]);
+    let n: Vec<_> = grid.neighbors8(Coord::new(1, 1)).collect();
+    assert_eq!(
+      n,
+      [
+        (Coord::new(1, 0), &'b'),
+        (Coord::new(2, 2), &'c'),
+        (Coord::new(0, 0), &'a')
+      ]
+    );
+  }
+
+  #[test]
+  fn entry() {
+    let mut grid = Grid::new(3, 3);
+    *grid.entry(Coord::new(1, 1)).unwrap().or_insert(0) += 1;
+    *grid.entry(Coord::new(1, 1)).unwrap().or_insert(0) += 1;
+    assert_eq!(grid.get(Coord::new(1, 1)), Some(&2));
+    assert!(grid.entry(Coord::new(3, 0)).is_none());
+
+    grid
+      .entry(Coord::new(2, 0))
+      .unwrap()
+      .and_modify(|x| *x = 100)
+      .or_insert_with_coord(|c| c.x * 10);
+    assert_eq!(grid.get(Coord::new(2, 0)), Some(&20));
+
+    match grid.entry(Coord::new(2, 0)).unwrap() {
+      Entry::Occupied(it) => {
+        assert_eq!(it.coord(), Coord::new(2, 0));
+        assert_eq!(it.remove(), 20);
+      }
+      Entry::Vacant(_) => panic!(),
+    }
+    assert!(!grid.contains(Coord::new(2, 0)));
+  }
+
+  #[test]
+  fn bulk_removal() {
+    let mut grid = Grid::new(3, 2);
+    for coord in grid.area() {
+      grid.insert(coord, coord.x + coord.y);
+    }
+
+    grid.retain(|_, v| *v % 2 == 0);
+    assert_eq!(grid.iter().count(), 3);
+    assert!(!grid.contains(Coord::new(1, 0)));
+
+    let taken = grid.take();
+    assert_eq!(taken.iter().count(), 3);
+    assert_eq!(grid.iter().count(), 0);
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+
+    grid = taken.clone();
+    let drained: Vec<_> = grid.drain().collect();
+    assert_eq!(
+      drained,
+      [
+        (Coord::new(0, 0), 0),
+        (Coord::new(2, 0), 2),
+        (Coord::new(1, 1), 2)
+      ]
+    );
+    assert_eq!(grid.iter().count(), 0);
+
+    grid = taken.clone();
+    assert_eq!(grid.drain().next(), Some((Coord::new(0, 0), 0)));
+    assert_eq!(grid.iter().count(), 0);
+
+    grid = taken;
+    grid.clear();
+    assert_eq!(grid.iter().count(), 0);
+  }
+
+  #[test]
+  fn iter_from() {
+    let grid = char_grid("a..b\n.c..\n...d");
+    let order =
+      |origin| grid.iter_from(origin).map(|(_, c)| *c).collect::<String>();
+    assert_eq!(order(Coord::new(1, 1)), "cabd");
+    assert_eq!(order(Coord::new(6, 0)), "dbca");
+    assert_eq!(
+      grid.iter_from(Coord::new(3, 2)).next(),
+      Some((Coord::new(3, 2), &'d'))
+    );
+    assert_eq!(Grid::<()>::new(0, 0).iter_from(Coord::ZERO).count(), 0);
+  }
+
+  #[test]
+  fn iter_from_far_away() {
+    let grid = char_grid("abc\ndef\nghi");
+    let order =
+      |origin| grid.iter_from(origin).map(|(_, c)| *c).collect::<String>();
+    // Off the grid, the first ring to touch it is 2 away
+    assert_eq!(order(Coord::new(4, 1)), "ifchebgda");
+    assert_eq!(order(Coord::new(5000, 5000)), "ihefgdabc");
+    assert_eq!(grid.iter_from(Coord::new(u32::MAX, u32::MAX)).count(), 9);
+
+    // A long thin grid only visits one or two cells per ring
+    let mut row = Grid::new(2000, 1);
+    row.insert(Coord::new(1999, 0), 'z');
+    row.insert(Coord::new(0, 0), 'a');
+    let found: Vec<_> = row.iter_from(Coord::new(10, 0)).collect();
+    assert_eq!(
+      found,
+      [(Coord::new(0, 0), &'a'), (Coord::new(1999, 0), &'z')]
+    );
+    // From far below, the whole row is on the top of one ring, which goes
+    // clockwise from due north
+    let found: Vec<_> = row.iter_from(Coord::new(10, 7000)).collect();
+    assert_eq!(
+      found,
+      [(Coord::new(1999, 0), &'z'), (Coord::new(0, 0), &'a')]
+    );
+  }
+
+  #[test]
+  fn nine_slice_clipped() {
+    let mut grid = Grid::new(2, 2);
+    grid.draw_nine_slice(Area::new(Coord::new(1, 1), 3, 3), |dir| dir);
+    assert_eq!(grid.get(Coord::new(1, 1)), Some(&Direction9::NorthWest));
+    assert_eq!(grid.iter().count(), 1);
+  }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+
+use crate::{Connectivity, Region};
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Get every cell whose value matches `pred`.
+  ///
+  /// Empty cells never match.
+  pub fn select<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Region {
+    let mut out = Region::with_bounds(self.area());
+    for (coord, val) in self.iter() {
+      if pred(val) {
+        out.insert(coord);
+      }
+    }
+    out
+  }
+
+  /// Get the cells matching `pred`, plus every cell next to one of them.
+  ///
+  /// This is morphological dilation. For example, dilating the floor gives
+  /// the floor and every cell that should be a wall around it.
+  pub fn dilate<F: FnMut(&T) -> bool>(
+    &self,
+    connectivity: Connectivity,
+    pred: F,
+  ) -> Region {
+    self.grow_n(1, connectivity, pred)
+  }
+
+  /// Get the cells matching `pred` that are completely surrounded by other
+  /// matching cells.
+  ///
+  /// This is morphological erosion. Cells off the edge of the grid don't
+  /// match, so matching cells along the edge are always eroded away.
+  pub fn erode<F: FnMut(&T) -> bool>(
+    &self,
+    connectivity: Connectivity,
+    pred: F,
+  ) -> Region {
+    let matching = self.select(pred);
+    let neighbors = match connectivity {
+      Connectivity::Four => 4,
+      Connectivity::Eight => 8,
+    };
+    matching
+      .iter()
+      .filter(|coord| {
+        let inside = connectivity
+          .neighbors_within(*coord, self.area())
+          .filter(|next| matching.contains(*next));
+        inside.count() == neighbors
+      })
+      .collect()
+  }
+
+  /// Get the cells that don't match `pred` but are next to one that does.
+  ///
+  /// This is the outer border of the matching cells: the walls around the
+  /// floor, say. Subtract the [eroded](Self::erode) cells from the matching
+  /// ones instead to get the inner border.
+  pub fn outline<F: FnMut(&T) -> bool>(
+    &self,
+    connectivity: Connectivity,
+    pred: F,
+  ) -> Region {
+    let matching = self.select(pred);
+    self
+      .grow_region(&matching, 1, connectivity)
+      .difference(&matching)
+  }
+
+  /// Get the cells matching `pred`, plus every cell at most `steps` steps
+  /// away from one of them.
+  ///
+  /// This is the same as [dilating](Self::dilate) `steps` times over, but
+  /// faster. Handy for keeping monsters from spawning too close to the
+  /// player.
+  pub fn grow_n<F: FnMut(&T) -> bool>(
+    &self,
+    steps: u32,
+    connectivity: Connectivity,
+    pred: F,
+  ) -> Region {
+    self.grow_region(&self.select(pred), steps, connectivity)
+  }
+
+  fn grow_region(
+    &self,
+    start: &Region,
+    steps: u32,
+    connectivity: Connectivity,
+  ) -> Region {
+    let mut out = Region::with_bounds(self.area());
+    let mut queue = VecDeque::new();
+    for coord in start {
+      out.insert(coord);
+      queue.push_back((coord, 0));
+    }
+    while let Some((coord, dist)) = queue.pop_front() {
+      if dist == steps {
+        continue;
+      }
+      for next in connectivity.neighbors_within(coord, self.area()) {
+        if out.insert(next) {
+          queue.push_back((next, dist + 1));
+        }
+      }
+    }
+    out
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Connectivity, Grid, Region};
+
+  fn grid(map: &str) -> Grid<bool> {
+    Grid::from_str_map(map, |c, _| Some(c == '#'))
+  }
+
+  fn draw(region: &Region, like: &Grid<bool>) -> String {
+    let marked = Grid::from_str_map(&like.render(|_| '.'), |_, coord| {
+      region.contains(coord).then_some('#')
+    });
+    marked.render(|c| c.copied().unwrap_or('.'))
+  }
+
+  #[test]
+  fn dilate_and_erode() {
+    let map = grid(".....\n.###.\n.###.\n.###.\n.....");
+    assert_eq!(
+      draw(&map.dilate(Connectivity::Four, |b| *b), &map),
+      ".###.\n#####\n#####\n#####\n.###."
+    );
+    assert_eq!(
+      draw(&map.outline(Connectivity::Eight, |b| *b), &map),
+      "#####\n#...#\n#...#\n#...#\n#####"
+    );
+    assert_eq!(
+      draw(&map.erode(Connectivity::Eight, |b| *b), &map),
+      ".....\n.....\n..#..\n.....\n....."
+    );
+    let edge = grid("###\n###");
+    assert!(edge.erode(Connectivity::Four, |b| *b).is_empty());
+  }
+
+  #[test]
+  fn grow() {
+    let map = grid(".......\n.......\n...#...\n.......\n.......");
+    assert_eq!(
+      draw(&map.grow_n(2, Connectivity::Four, |b| *b), &map),
+      "...#...\n..###..\n.#####.\n..###..\n...#..."
+    );
+    assert_eq!(
+      map.grow_n(0, Connectivity::Eight, |b| *b),
+      map.select(|b| *b)
+    );
+    let twice = Grid::from_str_map(
+      &draw(&map.dilate(Connectivity::Eight, |b| *b), &map),
+      |c, _| Some(c == '#'),
+    );
+    assert_eq!(
+      twice.dilate(Connectivity::Eight, |b| *b),
+      map.grow_n(2, Connectivity::Eight, |b| *b)
+    );
+  }
+}
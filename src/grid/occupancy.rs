@@ -0,0 +1,166 @@
+use crate::{BitGrid, Coord, UnsignedScalar};
+
+use super::{Grid, GridIter};
+
+/// A [`Grid`] that keeps a [`BitGrid`] of which cells are filled up to date
+/// as it changes.
+///
+/// So checking whether a cell is empty in a hot loop is one bit test, and
+/// scanning for filled cells skips empty stretches a word at a time. All
+/// changes have to go through this wrapper to keep the two in sync, so the
+/// grid underneath is only lent out immutably.
+///
+/// With the `serde` feature, this serializes as just the grid, and the bits
+/// are rebuilt when deserializing.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(
+  feature = "serde",
+  serde(from = "Grid<T>", bound = "T: serde::Deserialize<'de>")
+)]
+pub struct OccupancyGrid<T> {
+  grid: Grid<T>,
+  occupancy: BitGrid,
+}
+
+impl<T> OccupancyGrid<T> {
+  pub fn new(width: u32, height: u32) -> Self {
+    Grid::new(width, height).into()
+  }
+
+  pub fn width(&self) -> u32 {
+    self.grid.width()
+  }
+
+  pub fn height(&self) -> u32 {
+    self.grid.height()
+  }
+
+  pub fn as_grid(&self) -> &Grid<T> {
+    &self.grid
+  }
+
+  pub fn into_grid(self) -> Grid<T> {
+    self.grid
+  }
+
+  /// The bits of which cells are filled.
+  pub fn occupancy(&self) -> &BitGrid {
+    &self.occupancy
+  }
+
+  /// Is the cell at `coord` filled? This only checks the bits.
+  pub fn is_occupied<S: UnsignedScalar>(&self, coord: Coord<S>) -> bool {
+    self.occupancy.get(coord)
+  }
+
+  pub fn get<S: UnsignedScalar>(&self, coord: Coord<S>) -> Option<&T> {
+    self.grid.get(coord)
+  }
+
+  /// Filling or emptying a cell has to go through [`Self::insert`] or
+  /// [`Self::remove`], so this only lets you change what's already there.
+  pub fn get_mut<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+  ) -> Option<&mut T> {
+    self.grid.get_mut(coord)
+  }
+
+  /// Returns the old value
+  pub fn insert<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+    val: T,
+  ) -> Option<T> {
+    self.insert_direct(coord, Some(val))
+  }
+
+  pub fn remove<S: UnsignedScalar>(&mut self, coord: Coord<S>) -> Option<T> {
+    self.insert_direct(coord, None)
+  }
+
+  /// Like [`Grid::insert_direct`].
+  pub fn insert_direct<S: UnsignedScalar>(
+    &mut self,
+    coord: Coord<S>,
+    val: Option<T>,
+  ) -> Option<T> {
+    self.occupancy.set(coord, val.is_some());
+    self.grid.insert_direct(coord, val)
+  }
+
+  /// Like [`Grid::retain`].
+  pub fn retain<F: FnMut(Coord, &mut T) -> bool>(&mut self, mut keep: F) {
+    let occupancy = &mut self.occupancy;
+    self.grid.retain(|coord, val| {
+      let kept = keep(coord, val);
+      if !kept {
+        occupancy.set(coord, false);
+      }
+      kept
+    });
+  }
+
+  pub fn clear(&mut self) {
+    self.grid.clear();
+    self.occupancy = BitGrid::new(self.width(), self.height());
+  }
+
+  pub fn iter(&self) -> GridIter<'_, T> {
+    self.grid.iter()
+  }
+}
+
+impl<T> From<Grid<T>> for OccupancyGrid<T> {
+  fn from(grid: Grid<T>) -> Self {
+    Self {
+      occupancy: grid.occupancy(),
+      grid,
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for OccupancyGrid<T> {
+  fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+    self.grid.serialize(ser)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, OccupancyGrid};
+
+  #[test]
+  fn stays_in_sync() {
+    let mut grid = OccupancyGrid::new(5, 5);
+    grid.insert(Coord::new(1, 1), 1);
+    grid.insert(Coord::new(2, 3), 2);
+    grid.insert(Coord::new(4, 4), 3);
+    grid.insert(Coord::new(9, 9), 4);
+    grid.remove(Coord::new(2, 3));
+    *grid.get_mut(Coord::new(4, 4)).unwrap() += 10;
+    assert!(grid.is_occupied(Coord::new(1, 1)));
+    assert!(!grid.is_occupied(Coord::new(2, 3)));
+    assert_eq!(grid.occupancy(), &grid.as_grid().occupancy());
+
+    grid.retain(|_, val| *val > 10);
+    assert_eq!(
+      grid.occupancy().iter_ones().collect::<Vec<_>>(),
+      [Coord::new(4, 4)]
+    );
+    grid.clear();
+    assert!(!grid.occupancy().any());
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serde_rebuilds_bits() {
+    let mut grid = OccupancyGrid::new(3, 3);
+    grid.insert(Coord::new(2, 1), 'x');
+    let json = serde_json::to_string(&grid).unwrap();
+    let back: OccupancyGrid<char> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.occupancy(), grid.occupancy());
+  }
+}
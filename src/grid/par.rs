@@ -0,0 +1,104 @@
+use rayon::prelude::*;
+
+use crate::Coord;
+
+use super::Grid;
+
+/// Parallel iteration, with the `rayon` feature.
+impl<T> Grid<T> {
+  /// Iterate in parallel over all the (filled) slots in the grid.
+  pub fn par_iter(&self) -> impl ParallelIterator<Item = (Coord, &T)>
+  where
+    T: Sync,
+  {
+    let width = self.width;
+    self
+      .spots
+      .par_iter()
+      .enumerate()
+      .filter_map(move |(idx, slot)| {
+        let idx = idx as u32;
+        Some((Coord::new(idx % width, idx / width), slot.as_ref()?))
+      })
+  }
+
+  /// Iterate mutably in parallel over all the (filled) slots in the grid.
+  pub fn par_iter_mut(
+    &mut self,
+  ) -> impl ParallelIterator<Item = (Coord, &mut T)>
+  where
+    T: Send,
+  {
+    let width = self.width;
+    self
+      .spots
+      .par_iter_mut()
+      .enumerate()
+      .filter_map(move |(idx, slot)| {
+        let idx = idx as u32;
+        Some((Coord::new(idx % width, idx / width), slot.as_mut()?))
+      })
+  }
+
+  /// Make a new grid the same size as this one by calling `f` on every slot
+  /// in parallel, filled or not.
+  ///
+  /// This is handy for cellular automata, where `f` can look at the
+  /// neighbors of each cell in this grid.
+  pub fn par_map<U, F>(&self, f: F) -> Grid<U>
+  where
+    T: Sync,
+    U: Send,
+    F: Fn(Coord, Option<&T>) -> Option<U> + Sync,
+  {
+    let width = self.width;
+    let spots = self
+      .spots
+      .par_chunks(width.max(1) as usize)
+      .enumerate()
+      .flat_map_iter(|(y, row)| {
+        let f = &f;
+        row.iter().enumerate().map(move |(x, slot)| {
+          f(Coord::new(x as u32, y as u32), slot.as_ref())
+        })
+      })
+      .collect();
+    Grid {
+      width,
+      height: self.height,
+      spots,
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rayon::prelude::*;
+
+  use crate::{Coord, Grid};
+
+  #[test]
+  fn par_iters() {
+    let mut grid = Grid::new(50, 40);
+    for coord in grid.area() {
+      if (coord.x + coord.y) % 3 == 0 {
+        grid.insert(coord, coord.x * coord.y);
+      }
+    }
+
+    let mut seq: Vec<_> = grid.iter().map(|(c, v)| (c, *v)).collect();
+    let mut par: Vec<_> = grid.par_iter().map(|(c, v)| (c, *v)).collect();
+    seq.sort_by_key(|(c, _)| (c.y, c.x));
+    par.sort_by_key(|(c, _)| (c.y, c.x));
+    assert_eq!(seq, par);
+
+    grid.par_iter_mut().for_each(|(_, v)| *v += 1);
+    assert_eq!(grid.get(Coord::new(3, 3)), Some(&10));
+
+    let mapped =
+      grid.par_map(|c, v| Some(v.copied().unwrap_or(0) + c.to_2d_idx(50)));
+    assert_eq!((mapped.width(), mapped.height()), (50, 40));
+    assert_eq!(mapped.get(Coord::new(3, 3)), Some(&(10 + 153)));
+    assert_eq!(mapped.get(Coord::new(1, 0)), Some(&1));
+  }
+}
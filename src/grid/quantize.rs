@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+
+use super::Grid;
+
+impl Grid<f32> {
+  /// Sort each cell into one of `levels` even tiers, from `0` for values
+  /// near 0 up to `levels - 1` for values near 1.
+  ///
+  /// Values outside 0 to 1 are clamped. Empty cells stay empty. This is the
+  /// usual way to turn a heightmap into water, sand, grass, and mountains.
+  ///
+  /// Panics if `levels` is zero.
+  pub fn quantize(&self, levels: u8) -> Grid<u8> {
+    assert!(levels > 0, "need at least one level");
+    self.map(|_, val| level_of(*val, levels))
+  }
+
+  /// Like [`Self::quantize`], but without speckles along the boundaries
+  /// between tiers.
+  ///
+  /// With plain quantizing, noise in cells that are right on a threshold
+  /// makes them flicker back and forth between tiers. Here, a cell within
+  /// `margin` of a threshold isn't sure which side it's on, so it goes with
+  /// whichever sure neighbor is closest instead. So boundaries come out
+  /// smooth, running down the middle of the unsure bands.
+  ///
+  /// Unsure cells that aren't connected to any sure cells fall back to plain
+  /// quantizing.
+  ///
+  /// Panics if `levels` is zero.
+  pub fn quantize_hysteresis(&self, levels: u8, margin: f32) -> Grid<u8> {
+    assert!(levels > 0, "need at least one level");
+    let range = |val: f32| {
+      (
+        level_of(val - margin, levels),
+        level_of(val + margin, levels),
+      )
+    };
+
+    let mut out: Grid<u8> = Grid::new(self.width, self.height);
+    let mut queue = VecDeque::new();
+    for (coord, val) in self.iter() {
+      let (lo, hi) = range(*val);
+      if lo == hi {
+        out.insert(coord, lo);
+        queue.push_back(coord);
+      }
+    }
+    // Spread the sure cells outwards into the unsure ones, breadth-first so
+    // each unsure cell is decided by its closest sure neighbor
+    while let Some(coord) = queue.pop_front() {
+      let level = *out.get(coord).unwrap();
+      for neighbor in coord.neighbors4() {
+        let Some(val) = self.get(neighbor) else {
+          continue;
+        };
+        if out.contains(neighbor) {
+          continue;
+        }
+        let (lo, hi) = range(*val);
+        out.insert(neighbor, level.clamp(lo, hi));
+        queue.push_back(neighbor);
+      }
+    }
+    for (coord, val) in self.iter() {
+      if !out.contains(coord) {
+        out.insert(coord, level_of(*val, levels));
+      }
+    }
+    out
+  }
+}
+
+fn level_of(val: f32, levels: u8) -> u8 {
+  ((val.clamp(0.0, 1.0) * levels as f32) as u8).min(levels - 1)
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, Grid};
+
+  fn row(vals: &[f32]) -> Grid<f32> {
+    let mut grid = Grid::new(vals.len() as u32, 1);
+    for (x, val) in vals.iter().enumerate() {
+      grid.insert(Coord::new(x as u32, 0), *val);
+    }
+    grid
+  }
+
+  fn levels(grid: &Grid<u8>) -> Vec<u8> {
+    grid.iter().map(|(_, l)| *l).collect()
+  }
+
+  #[test]
+  fn quantize() {
+    let grid = row(&[-1.0, 0.0, 0.3, 0.5, 0.99, 1.0, 2.0]);
+    assert_eq!(levels(&grid.quantize(2)), [0, 0, 0, 1, 1, 1, 1]);
+    assert_eq!(levels(&grid.quantize(4)), [0, 0, 1, 2, 3, 3, 3]);
+    assert_eq!(levels(&grid.quantize(1)), [0; 7]);
+
+    let mut holey = grid.clone();
+    holey.remove(Coord::new(1, 0));
+    assert!(!holey.quantize(3).contains(Coord::new(1, 0)));
+  }
+
+  #[test]
+  fn hysteresis() {
+    // A slope with noise right around the threshold at 0.5
+    let grid = row(&[0.1, 0.2, 0.48, 0.52, 0.49, 0.51, 0.49, 0.8, 0.9]);
+    assert_eq!(levels(&grid.quantize(2)), [0, 0, 0, 1, 0, 1, 0, 1, 1]);
+    assert_eq!(
+      levels(&grid.quantize_hysteresis(2, 0.05)),
+      [0, 0, 0, 0, 0, 1, 1, 1, 1]
+    );
+    // With no margin it's the same as plain quantizing
+    assert_eq!(
+      levels(&grid.quantize_hysteresis(2, 0.0)),
+      levels(&grid.quantize(2))
+    );
+    // Nothing sure to go off of
+    let unsure = row(&[0.49, 0.51]);
+    assert_eq!(levels(&unsure.quantize_hysteresis(2, 0.1)), [0, 1]);
+  }
+}
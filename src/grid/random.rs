@@ -0,0 +1,58 @@
+//! Random sampling of cells, with the `rand` feature.
+
+use rand::{seq::IteratorRandom, Rng};
+
+use crate::Coord;
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Pick one of the filled cells uniformly at random, or `None` if the grid
+  /// is empty.
+  pub fn random_filled_cell<R: Rng + ?Sized>(
+    &self,
+    rng: &mut R,
+  ) -> Option<(Coord, &T)> {
+    self.iter().choose(rng)
+  }
+
+  /// Fill each cell with a chance of `density`, calling `f` to make the
+  /// value. Cells that aren't picked are left alone.
+  ///
+  /// This is the usual first step for cave generation with
+  /// [`Self::step_automaton`].
+  pub fn fill_random<R, F>(&mut self, density: f64, rng: &mut R, mut f: F)
+  where
+    R: Rng + ?Sized,
+    F: FnMut(Coord) -> T,
+  {
+    for idx in 0..self.spots.len() {
+      if rng.gen_bool(density.clamp(0.0, 1.0)) {
+        self.spots[idx] = Some(f(self.coord_of(idx)));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use rand::{rngs::StdRng, SeedableRng};
+
+  use crate::{Coord, Grid};
+
+  #[test]
+  fn fill_and_pick() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut grid = Grid::new(20, 20);
+    assert!(grid.random_filled_cell(&mut rng).is_none());
+
+    grid.fill_random(0.25, &mut rng, |c| c);
+    let filled = grid.iter().count();
+    assert!((50..150).contains(&filled), "{} filled", filled);
+    let (coord, val) = grid.random_filled_cell(&mut rng).unwrap();
+    assert_eq!(coord, *val);
+
+    grid.fill_random(1.0, &mut rng, |_| Coord::new(0, 0));
+    assert_eq!(grid.iter().count(), 400);
+  }
+}
@@ -0,0 +1,187 @@
+use crate::{Coord, CoordVec};
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Change the size of the grid, keeping everything where it is relative to
+  /// the top-left corner.
+  ///
+  /// New cells are empty, and anything that no longer fits is dropped.
+  pub fn resize(&mut self, width: u32, height: u32) {
+    let mut old = std::mem::replace(self, Grid::new(width, height));
+    for (coord, val) in old.drain() {
+      self.insert(coord, val);
+    }
+  }
+
+  /// Add an empty row at `y`, pushing the rows from there on down by one.
+  ///
+  /// Panics if `y` is past the bottom of the grid. `y` can be the height, to
+  /// add a row at the bottom.
+  pub fn insert_row(&mut self, y: u32) {
+    assert!(y <= self.height, "row {} is past the bottom", y);
+    let at = (y * self.width) as usize;
+    self.spots.splice(
+      at..at,
+      std::iter::repeat_with(|| None).take(self.width as usize),
+    );
+    self.height += 1;
+  }
+
+  /// Add an empty column at `x`, pushing the columns from there on right by
+  /// one.
+  ///
+  /// Panics if `x` is past the right of the grid. `x` can be the width, to
+  /// add a column on the right.
+  pub fn insert_column(&mut self, x: u32) {
+    assert!(x <= self.width, "column {} is past the right", x);
+    let (width, height) = (self.width, self.height);
+    self.rebuild(width + 1, height, |coord| match coord.x {
+      cx if cx < x => Some(coord),
+      cx if cx > x => Some(Coord::new(cx - 1, coord.y)),
+      _ => None,
+    });
+  }
+
+  /// Take out the row at `y`, pulling the rows below it up by one.
+  ///
+  /// Returns what was in the row, left to right. Panics if `y` is out of
+  /// bounds.
+  pub fn remove_row(&mut self, y: u32) -> Vec<Option<T>> {
+    assert!(y < self.height, "row {} is out of bounds", y);
+    let at = (y * self.width) as usize;
+    let row = self.spots.drain(at..at + self.width as usize).collect();
+    self.height -= 1;
+    row
+  }
+
+  /// Take out the column at `x`, pulling the columns right of it left by one.
+  ///
+  /// Returns what was in the column, top to bottom. Panics if `x` is out of
+  /// bounds.
+  pub fn remove_column(&mut self, x: u32) -> Vec<Option<T>> {
+    assert!(x < self.width, "column {} is out of bounds", x);
+    let column = (0..self.height)
+      .map(|y| self.remove(Coord::new(x, y)))
+      .collect();
+    let (width, height) = (self.width, self.height);
+    self.rebuild(width - 1, height, |coord| {
+      if coord.x < x {
+        Some(coord)
+      } else {
+        Some(Coord::new(coord.x + 1, coord.y))
+      }
+    });
+    column
+  }
+
+  /// Move everything in the grid over by `offset`, keeping the size the
+  /// same.
+  ///
+  /// Anything that moves off the edge is dropped, and the cells it leaves
+  /// behind are empty.
+  pub fn offset_by(&mut self, offset: CoordVec) {
+    let (width, height) = (self.width, self.height);
+    self.rebuild(width, height, |coord| {
+      let axis = |at: u32, by: i32| u32::try_from(at as i64 - by as i64).ok();
+      Some(Coord::new(
+        axis(coord.x, offset.x)?,
+        axis(coord.y, offset.y)?,
+      ))
+    });
+  }
+
+  /// Like [`Self::offset_by`], but anything that moves off one edge comes
+  /// back around the opposite edge.
+  pub fn offset_by_wrapping(&mut self, offset: CoordVec) {
+    let (width, height) = (self.width, self.height);
+    if width == 0 || height == 0 {
+      return;
+    }
+    self.rebuild(width, height, |coord| {
+      let axis = |at: u32, by: i32, len: u32| {
+        (at as i64 - by as i64).rem_euclid(len as i64) as u32
+      };
+      Some(Coord::new(
+        axis(coord.x, offset.x, width),
+        axis(coord.y, offset.y, height),
+      ))
+    });
+  }
+
+  /// Make a new grid of the given size, filling each cell from the cell of
+  /// the old grid that `source` points to, if any.
+  fn rebuild<F>(&mut self, width: u32, height: u32, mut source: F)
+  where
+    F: FnMut(Coord) -> Option<Coord>,
+  {
+    let mut old = std::mem::replace(self, Grid::new(width, height));
+    for idx in 0..self.spots.len() {
+      let from = source(self.coord_of(idx));
+      if let Some(from) = from.and_then(|from| old.idx(from)) {
+        self.spots[idx] = old.spots[from].take();
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{grid::text::char_grid, Coord, CoordVec};
+
+  #[test]
+  fn resize() {
+    let mut map = char_grid("ab\ncd");
+    map.resize(3, 1);
+    assert_eq!(map.to_string(), "ab.");
+    map.resize(1, 3);
+    assert_eq!(map.to_string(), "a\n.\n.");
+    map.resize(0, 0);
+    assert_eq!(map.iter().count(), 0);
+  }
+
+  #[test]
+  fn rows_and_columns() {
+    let mut map = char_grid("abc\ndef");
+    map.insert_row(1);
+    assert_eq!(map.to_string(), "abc\n...\ndef");
+    map.insert_column(3);
+    map.insert_column(0);
+    assert_eq!(map.to_string(), ".abc.\n.....\n.def.");
+    assert_eq!(map.remove_column(2), [Some('b'), None, Some('e')]);
+    assert_eq!(map.to_string(), ".ac.\n....\n.df.");
+    assert_eq!(map.remove_row(0), [None, Some('a'), Some('c'), None]);
+    assert_eq!(map.to_string(), "....\n.df.");
+    assert_eq!((map.width(), map.height()), (4, 2));
+    assert_eq!(map.get(Coord::new(2, 1)), Some(&'f'));
+  }
+
+  #[test]
+  fn offset() {
+    let mut map = char_grid("ab.\ncd.");
+    map.offset_by(CoordVec::new(1, -1));
+    assert_eq!(map.to_string(), ".cd\n...");
+    let mut map = char_grid("ab.\ncd.");
+    map.offset_by_wrapping(CoordVec::new(2, 1));
+    assert_eq!(map.to_string(), "d.c\nb.a");
+  }
+
+  #[test]
+  fn extreme_offsets() {
+    let mut map = char_grid("ab.\ncd.");
+    map.offset_by(CoordVec::new(i32::MIN, 0));
+    assert_eq!(map.to_string(), "...\n...");
+    let mut map = char_grid("ab.\ncd.");
+    map.offset_by(CoordVec::new(i32::MAX, i32::MIN));
+    assert_eq!(map.to_string(), "...\n...");
+
+    // i32::MIN and i32::MAX are both 1 more than a multiple of 3, and
+    // i32::MIN is even
+    let mut map = char_grid("ab.\ncd.");
+    map.offset_by_wrapping(CoordVec::new(i32::MIN, i32::MIN));
+    assert_eq!(map.to_string(), ".ab\n.cd");
+    let mut map = char_grid("ab.\ncd.");
+    map.offset_by_wrapping(CoordVec::new(i32::MAX, i32::MAX));
+    assert_eq!(map.to_string(), ".cd\n.ab");
+  }
+}
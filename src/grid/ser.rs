@@ -0,0 +1,221 @@
+//! Compact serde representation for [`Grid`] and [`Grid3`]; see their docs
+//! for the format.
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Grid, Grid3};
+
+#[derive(Serialize)]
+#[serde(rename = "Grid")]
+struct GridRef<'a, T> {
+  width: u32,
+  height: u32,
+  cells: Vec<RunRef<'a, T>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Grid")]
+struct GridOwned<T> {
+  width: u32,
+  height: u32,
+  cells: Vec<Run<T>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Grid3")]
+struct Grid3Ref<'a, T> {
+  width: u32,
+  height: u32,
+  depth: u32,
+  cells: Vec<RunRef<'a, T>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Grid3")]
+struct Grid3Owned<T> {
+  width: u32,
+  height: u32,
+  depth: u32,
+  cells: Vec<Run<T>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Run", rename_all = "lowercase")]
+enum RunRef<'a, T> {
+  Empty(u32),
+  Filled(Vec<&'a T>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Run<T> {
+  Empty(u32),
+  Filled(Vec<T>),
+}
+
+/// Squash runs of empty and filled cells together.
+fn to_runs<T>(spots: &[Option<T>]) -> Vec<RunRef<'_, T>> {
+  let mut cells = Vec::new();
+  for slot in spots {
+    match (slot, cells.last_mut()) {
+      (None, Some(RunRef::Empty(n))) => *n += 1,
+      (None, _) => cells.push(RunRef::Empty(1)),
+      (Some(it), Some(RunRef::Filled(vals))) => vals.push(it),
+      (Some(it), _) => cells.push(RunRef::Filled(vec![it])),
+    }
+  }
+  cells
+}
+
+/// Unpack runs into exactly `expected` cells, or complain using `what` to
+/// describe how many there should be.
+fn from_runs<T, E: Error>(
+  cells: Vec<Run<T>>,
+  expected: Option<usize>,
+  what: &'static str,
+) -> Result<Vec<Option<T>>, E> {
+  let expected = expected.ok_or_else(|| E::custom("grid is too big"))?;
+
+  let mut spots = Vec::with_capacity(expected);
+  for run in cells {
+    match run {
+      Run::Empty(n) => {
+        // Check before extending, so a bogus huge run can't eat all
+        // the memory
+        if spots.len() + n as usize > expected {
+          return Err(E::invalid_length(spots.len() + n as usize, &what));
+        }
+        spots.extend(std::iter::repeat_with(|| None).take(n as usize));
+      }
+      Run::Filled(vals) => spots.extend(vals.into_iter().map(Some)),
+    }
+  }
+  if spots.len() != expected {
+    return Err(E::invalid_length(spots.len(), &what));
+  }
+  Ok(spots)
+}
+
+impl<T: Serialize> Serialize for Grid<T> {
+  fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+    GridRef {
+      width: self.width,
+      height: self.height,
+      cells: to_runs(&self.spots),
+    }
+    .serialize(ser)
+  }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Grid<T> {
+  fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+    let GridOwned {
+      width,
+      height,
+      cells,
+    } = GridOwned::deserialize(de)?;
+    let expected = (width as usize).checked_mul(height as usize);
+    Ok(Grid {
+      width,
+      height,
+      spots: from_runs(cells, expected, "width * height cells")?,
+    })
+  }
+}
+
+impl<T: Serialize> Serialize for Grid3<T> {
+  fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+    Grid3Ref {
+      width: self.width,
+      height: self.height,
+      depth: self.depth,
+      cells: to_runs(&self.spots),
+    }
+    .serialize(ser)
+  }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Grid3<T> {
+  fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Self, D::Error> {
+    let Grid3Owned {
+      width,
+      height,
+      depth,
+      cells,
+    } = Grid3Owned::deserialize(de)?;
+    let expected = (width as usize)
+      .checked_mul(height as usize)
+      .and_then(|n| n.checked_mul(depth as usize));
+    Ok(Grid3 {
+      width,
+      height,
+      depth,
+      spots: from_runs(cells, expected, "width * height * depth cells")?,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, Coord3, Grid, Grid3};
+
+  #[test]
+  fn round_trip() {
+    let mut grid = Grid::new(3, 2);
+    grid.insert(Coord::new(0, 0), 'a');
+    grid.insert(Coord::new(2, 1), 'b');
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(
+      json,
+      r#"{"width":3,"height":2,"cells":[{"filled":["a"]},{"empty":4},{"filled":["b"]}]}"#
+    );
+    let back: Grid<char> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.to_string(), grid.to_string());
+
+    let full = Grid::from_str_map("ab\ncd", |c, _| Some(c));
+    let json = serde_json::to_string(&full).unwrap();
+    assert!(json.ends_with(r#""cells":[{"filled":["a","b","c","d"]}]}"#));
+    let back: Grid<char> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.to_string(), "ab\ncd");
+
+    let empty: Grid<char> = Grid::new(0, 0);
+    let back: Grid<char> =
+      serde_json::from_str(&serde_json::to_string(&empty).unwrap()).unwrap();
+    assert_eq!((back.width(), back.height()), (0, 0));
+  }
+
+  #[test]
+  fn wrong_length() {
+    let short = r#"{"width":2,"height":2,"cells":[{"empty":3}]}"#;
+    assert!(serde_json::from_str::<Grid<u8>>(short).is_err());
+    let long =
+      r#"{"width":2,"height":2,"cells":[{"filled":[1,2,3]},{"empty":2}]}"#;
+    assert!(serde_json::from_str::<Grid<u8>>(long).is_err());
+    let huge = r#"{"width":2,"height":2,"cells":[{"empty":4294967295}]}"#;
+    assert!(serde_json::from_str::<Grid<u8>>(huge).is_err());
+  }
+
+  #[test]
+  fn grid3() {
+    let mut grid = Grid3::new(2, 2, 2);
+    grid.insert(Coord3::new(1, 0, 0), 'a');
+    grid.insert(Coord3::new(0, 1, 1), 'b');
+    let json = serde_json::to_string(&grid).unwrap();
+    assert_eq!(
+      json,
+      r#"{"width":2,"height":2,"depth":2,"cells":[{"empty":1},{"filled":["a"]},{"empty":4},{"filled":["b"]},{"empty":1}]}"#
+    );
+    let back: Grid3<char> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+      back.iter().collect::<Vec<_>>(),
+      grid.iter().collect::<Vec<_>>()
+    );
+
+    let short = r#"{"width":2,"height":2,"depth":2,"cells":[{"empty":4}]}"#;
+    assert!(serde_json::from_str::<Grid3<u8>>(short).is_err());
+    let huge = r#"{"width":2,"height":2,"depth":2,"cells":[{"empty":9}]}"#;
+    assert!(serde_json::from_str::<Grid3<u8>>(huge).is_err());
+    let overflow = r#"{"width":4294967295,"height":4294967295,"depth":4294967295,"cells":[]}"#;
+    assert!(serde_json::from_str::<Grid3<u8>>(overflow).is_err());
+  }
+}
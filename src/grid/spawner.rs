@@ -0,0 +1,314 @@
+use crate::{coord_hash, Area, Coord, Transform};
+
+use super::Grid;
+
+/// Where a [`Spawner`] put (or could put) its template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Placement {
+  /// Where the template's anchor cell lands on the map.
+  pub anchor: Coord,
+  /// The part of the map the transformed template covers.
+  pub area: Area,
+  /// How the template was turned and flipped.
+  pub transform: Transform,
+}
+
+/// Finds places on a map where a prefab fits, and stamps it there.
+///
+/// The template is a grid of whatever describes the prefab. Its filled cells
+/// are its footprint, and one of them is the anchor. Filled cells don't all
+/// have to be painted onto the map; for example, a room template could
+/// include a cell outside its door that just requires a corridor there.
+///
+/// Placing a template takes two rules. The anchor rule is checked first, at
+/// the cell the anchor would land on, so it's a cheap way to rule out most
+/// of the map before looking at the whole footprint. The footprint rule is
+/// then checked for every filled cell of the template, and gets the map, the
+/// cell on the map, and the template's value for it.
+///
+/// ```
+/// # use aglet::{Coord, Grid, Spawner};
+/// let mut map = Grid::from_str_map("#####\n#...#\n#...#\n#...#\n#####", |c, _| Some(c));
+/// // A statue that needs floor all around it
+/// let statue = Grid::from_str_map("...\n.S.\n...", |c, _| Some(c));
+/// let spawner = Spawner::new(statue, Coord::new(1, 1));
+/// let on_floor = |map: &Grid<char>, c: Coord, _: &char| map.get(c) == Some(&'.');
+///
+/// let spots = spawner.candidates(&map, |_, _| true, on_floor);
+/// assert_eq!(spots.len(), 1);
+/// assert_eq!(spots[0].anchor, Coord::new(2, 2));
+/// let placed = spawner.spawn(&mut map, 5, 0, |_, _| true, on_floor, |t| {
+///     (*t == 'S').then_some('S')
+/// });
+/// assert_eq!(placed, spots);
+/// assert_eq!(map.get(Coord::new(2, 2)), Some(&'S'));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Spawner<T> {
+  /// The template in each allowed orientation, with where its anchor ends up.
+  oriented: Vec<(Transform, Grid<T>, Coord)>,
+}
+
+impl<T: Clone> Spawner<T> {
+  /// Make a spawner that places `template` as-is, with `anchor` being the
+  /// cell of the template the anchor rule checks.
+  ///
+  /// Panics if `anchor` isn't inside the template.
+  pub fn new(template: Grid<T>, anchor: Coord) -> Self {
+    assert!(
+      template.is_coord_valid(anchor),
+      "anchor must be inside the template"
+    );
+    Self {
+      oriented: vec![(Transform::IDENTITY, template, anchor)],
+    }
+  }
+
+  /// Also allow placing the template turned or flipped in these ways.
+  ///
+  /// The template's values themselves aren't changed, so if they point in
+  /// directions, check [`Placement::transform`].
+  pub fn with_transforms(
+    mut self,
+    transforms: impl IntoIterator<Item = Transform>,
+  ) -> Self {
+    let (_, template, anchor) = self.oriented[0].clone();
+    let (width, height) = (template.width(), template.height());
+    for transform in transforms {
+      if self.oriented.iter().any(|(t, _, _)| *t == transform) {
+        continue;
+      }
+      self.oriented.push((
+        transform,
+        template.clone().transformed(transform),
+        transform.apply_coord(anchor, width, height),
+      ));
+    }
+    self
+  }
+
+  /// Does the template fit at this placement?
+  ///
+  /// The placement doesn't have to have come from this spawner, but if it
+  /// uses a transform this spawner doesn't allow, it doesn't fit.
+  pub fn fits<M, A, R>(
+    &self,
+    map: &Grid<M>,
+    placement: &Placement,
+    mut anchor_rule: A,
+    mut footprint_rule: R,
+  ) -> bool
+  where
+    A: FnMut(&Grid<M>, Coord) -> bool,
+    R: FnMut(&Grid<M>, Coord, &T) -> bool,
+  {
+    let Some((_, template, _)) = self
+      .oriented
+      .iter()
+      .find(|(t, _, _)| *t == placement.transform)
+    else {
+      return false;
+    };
+    let map_area = map.area();
+    map_area.intersect(&placement.area) == Some(placement.area)
+      && anchor_rule(map, placement.anchor)
+      && template.iter().all(|(local, val)| {
+        footprint_rule(map, placement.area.corner + local, val)
+      })
+  }
+
+  /// Find every placement where the template fits, in row-major order of
+  /// their anchors. They might overlap each other.
+  pub fn candidates<M, A, R>(
+    &self,
+    map: &Grid<M>,
+    mut anchor_rule: A,
+    mut footprint_rule: R,
+  ) -> Vec<Placement>
+  where
+    A: FnMut(&Grid<M>, Coord) -> bool,
+    R: FnMut(&Grid<M>, Coord, &T) -> bool,
+  {
+    let mut out = Vec::new();
+    for anchor in map.area() {
+      if !anchor_rule(map, anchor) {
+        continue;
+      }
+      for placement in self.placements_at(map, anchor) {
+        if self.fits(map, &placement, |_, _| true, &mut footprint_rule) {
+          out.push(placement);
+        }
+      }
+    }
+    out
+  }
+
+  /// Place the template up to `max` times, painting each one onto the map.
+  ///
+  /// The spots are picked in a shuffled order decided by `seed`. The rules
+  /// are checked against the map as it is after the earlier templates are
+  /// painted, so if the footprint rule refuses cells that have been painted
+  /// over, templates won't overlap. `paint` gets each template value and
+  /// returns what to put on the map, or `None` to leave that cell alone.
+  ///
+  /// Returns where the templates went, in the order they were placed.
+  pub fn spawn<M, A, R, P>(
+    &self,
+    map: &mut Grid<M>,
+    max: usize,
+    seed: u64,
+    mut anchor_rule: A,
+    mut footprint_rule: R,
+    mut paint: P,
+  ) -> Vec<Placement>
+  where
+    A: FnMut(&Grid<M>, Coord) -> bool,
+    R: FnMut(&Grid<M>, Coord, &T) -> bool,
+    P: FnMut(&T) -> Option<M>,
+  {
+    let mut candidates =
+      self.candidates(map, &mut anchor_rule, &mut footprint_rule);
+    candidates.sort_by_cached_key(|p| {
+      let turn = Transform::all().iter().position(|t| *t == p.transform);
+      coord_hash(p.anchor, seed.wrapping_add(turn.unwrap_or(0) as u64))
+    });
+
+    let mut placed = Vec::new();
+    for placement in candidates {
+      if placed.len() >= max {
+        break;
+      }
+      if !self.fits(map, &placement, &mut anchor_rule, &mut footprint_rule) {
+        continue;
+      }
+      self.stamp(map, &placement, &mut paint);
+      placed.push(placement);
+    }
+    placed
+  }
+
+  /// Paint the template onto the map at a placement, without checking any
+  /// rules. Cells falling off the map are skipped.
+  ///
+  /// Panics if the placement uses a transform this spawner doesn't allow.
+  pub fn stamp<M, P>(
+    &self,
+    map: &mut Grid<M>,
+    placement: &Placement,
+    mut paint: P,
+  ) where
+    P: FnMut(&T) -> Option<M>,
+  {
+    let (_, template, _) = self
+      .oriented
+      .iter()
+      .find(|(t, _, _)| *t == placement.transform)
+      .expect("transform isn't allowed by this spawner");
+    for (local, val) in template.iter() {
+      let coord = placement.area.corner + local;
+      if !map.is_coord_valid(coord) {
+        continue;
+      }
+      if let Some(new) = paint(val) {
+        map.insert(coord, new);
+      }
+    }
+  }
+
+  /// Every orientation of the template with its anchor at `anchor` that
+  /// stays on the map.
+  fn placements_at<'a, M>(
+    &'a self,
+    map: &'a Grid<M>,
+    anchor: Coord,
+  ) -> impl Iterator<Item = Placement> + 'a {
+    self.oriented.iter().filter_map(
+      move |(transform, template, local_anchor)| {
+        let corner = Coord::new(
+          anchor.x.checked_sub(local_anchor.x)?,
+          anchor.y.checked_sub(local_anchor.y)?,
+        );
+        let area = Area::new(corner, template.width(), template.height());
+        (map.area().intersect(&area) == Some(area)).then_some(Placement {
+          anchor,
+          area,
+          transform: *transform,
+        })
+      },
+    )
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{grid::text::char_grid, Coord, Grid, Spawner, Transform};
+
+  /// `D` is the door, which must sit in a wall; `c` must be corridor and
+  /// isn't painted; `r` must be rock.
+  fn shrine() -> Spawner<char> {
+    let template = char_grid("c\nD\nr\nr");
+    Spawner::new(template, Coord::new(0, 1)).with_transforms(Transform::all())
+  }
+
+  fn rule(map: &Grid<char>, c: Coord, t: &char) -> bool {
+    let here = map.get(c).copied();
+    match t {
+      'c' => here == Some('+'),
+      _ => here == Some('#'),
+    }
+  }
+
+  #[test]
+  fn entrance_faces_corridor() {
+    let world = char_grid(
+      "#####\n\
+       #####\n\
+       #####\n\
+       +++++\n\
+       #####",
+    );
+    let spawner = shrine();
+    let spots = spawner.candidates(&world, |_, _| true, rule);
+    // It only fits upside down, with the door facing down into the corridor.
+    // Flipped upside down looks the same, so each column fits two ways.
+    assert_eq!(spots.len(), 10);
+    assert!(spots.iter().all(|p| p.anchor.y == 2 && p.area.height == 4));
+    assert!(spots.iter().all(|p| p.transform.quarter_turns() == 2));
+
+    let paint = |t: &char| (*t != 'c').then_some(*t);
+    let mut world = world;
+    let placed = spawner.spawn(&mut world, 10, 7, |_, _| true, rule, paint);
+    // Painting over the rock stops a second shrine going in the same column
+    assert_eq!(placed.len(), 5);
+    for p in &placed {
+      assert_eq!(world.get(p.anchor), Some(&'D'));
+      assert_eq!(world.get(p.area.corner), Some(&'r'));
+    }
+    assert_eq!(world.get(Coord::new(0, 3)), Some(&'+'));
+
+    let mut again = char_grid("#####\n#####\n#####\n+++++\n#####");
+    let placed_again =
+      spawner.spawn(&mut again, 3, 7, |_, _| true, rule, paint);
+    assert_eq!(placed_again, placed[..3]);
+  }
+
+  #[test]
+  fn anchor_rule_and_bounds() {
+    let world = char_grid("....\n....");
+    let block = Spawner::new(char_grid("xx\nxx"), Coord::new(1, 1));
+    let all = block.candidates(&world, |_, _| true, |_, _, _| true);
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0].area.corner, Coord::new(0, 0));
+
+    let right_only = block.candidates(&world, |_, c| c.x == 3, |_, _, _| true);
+    assert_eq!(right_only.len(), 1);
+    assert_eq!(right_only[0].anchor, Coord::new(3, 1));
+    assert!(block.fits(&world, &right_only[0], |_, _| true, |_, _, _| true));
+    assert!(!block.fits(&world, &right_only[0], |_, _| false, |_, _, _| true));
+
+    let mut turned = right_only[0];
+    turned.transform = Transform::rotation(1);
+    assert!(!block.fits(&world, &turned, |_, _| true, |_, _, _| true));
+  }
+}
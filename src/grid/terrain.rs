@@ -0,0 +1,217 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::Coord;
+
+use super::Grid;
+
+/// Rules for drawing the borders between terrains with transition tiles,
+/// using a dual grid.
+///
+/// Instead of picking a tile for each map cell, the dual grid puts a tile on
+/// each *corner* where four cells meet, so every tile only has to blend the
+/// (up to) four terrains at its corners. With two terrains that's only 16
+/// possible tiles, the same as marching squares.
+///
+/// Each terrain gets a priority, in the order they're
+/// [added](Self::add_terrain); higher-priority terrains are drawn spilling
+/// over lower ones. Each pair of terrains that can touch needs a block of 16
+/// transition tiles in a row, indexed by which corners have the higher
+/// terrain:
+///
+/// | corner     | bit |
+/// |------------|-----|
+/// | north-west | 1   |
+/// | north-east | 2   |
+/// | south-west | 4   |
+/// | south-east | 8   |
+///
+/// So a tile with grass over the top half of dirt is `first_tile + 3`. (Tiles
+/// 0 and 15 of each block are never used, since those are just solid
+/// terrain.)
+#[derive(Debug, Clone)]
+pub struct TerrainRules<T> {
+  /// Each terrain's priority and solid tile, lowest priority first.
+  terrains: Vec<(T, u32)>,
+  priorities: HashMap<T, usize>,
+  /// Keyed by (lower priority, higher priority).
+  transitions: HashMap<(usize, usize), u32>,
+}
+
+impl<T: Clone + Eq + Hash> TerrainRules<T> {
+  pub fn new() -> Self {
+    Self {
+      terrains: Vec::new(),
+      priorities: HashMap::new(),
+      transitions: HashMap::new(),
+    }
+  }
+
+  /// Add a terrain, drawn on top of all the terrains before it, with the
+  /// tile to use where it's on all four corners.
+  ///
+  /// Adding a terrain again changes its solid tile but keeps its priority.
+  pub fn add_terrain(&mut self, terrain: T, solid_tile: u32) {
+    match self.priorities.get(&terrain) {
+      Some(&priority) => self.terrains[priority].1 = solid_tile,
+      None => {
+        self.priorities.insert(terrain.clone(), self.terrains.len());
+        self.terrains.push((terrain, solid_tile));
+      }
+    }
+  }
+
+  /// Set the first of the 16 tiles for drawing the border between `a` and
+  /// `b`, in either order.
+  ///
+  /// Panics if either terrain hasn't been added.
+  pub fn add_transition(&mut self, a: &T, b: &T, first_tile: u32) {
+    let key = self.pair(a, b).expect("terrain hasn't been added");
+    self.transitions.insert(key, first_tile);
+  }
+
+  /// Pick the tile for a dual-grid corner, given the terrains of the cells
+  /// around it in the order north-west, north-east, south-west, south-east.
+  ///
+  /// Missing terrains are treated as the lowest-priority terrain present, so
+  /// they never make a border of their own. If more than two terrains meet,
+  /// the highest-priority one is drawn over the next-highest, with the rest
+  /// counted as the lower one.
+  ///
+  /// Returns `None` if there are no terrains at all, one of them hasn't
+  /// been added, or the transition it needs hasn't been added.
+  pub fn resolve(&self, corners: [Option<&T>; 4]) -> Option<u32> {
+    let mut priorities = [None; 4];
+    for (slot, corner) in priorities.iter_mut().zip(corners) {
+      if let Some(terrain) = corner {
+        *slot = Some(*self.priorities.get(terrain)?);
+      }
+    }
+    let top = priorities.iter().flatten().copied().max()?;
+    let Some(second) = priorities
+      .iter()
+      .flatten()
+      .copied()
+      .filter(|p| *p < top)
+      .max()
+    else {
+      return Some(self.terrains[top].1);
+    };
+    let mask = priorities
+      .iter()
+      .enumerate()
+      .filter(|(_, p)| **p == Some(top))
+      .fold(0, |mask, (bit, _)| mask | 1 << bit);
+    let first = self.transitions.get(&(second, top))?;
+    Some(first + mask)
+  }
+
+  /// Work out the tiles for a whole map.
+  ///
+  /// The output is one bigger than `terrain` in each direction. Tile `(x, y)`
+  /// sits on the top-left corner of cell `(x, y)`, so when drawing, shift the
+  /// tiles up and left by half a tile to line them up with the map. Cells off
+  /// the edge copy the nearest cell on the edge, so borders run straight off
+  /// the map instead of curling up. Empty cells count as missing terrain, and
+  /// tiles that can't be [resolved](Self::resolve) are left empty.
+  pub fn apply(&self, terrain: &Grid<T>) -> Grid<u32> {
+    let (width, height) = (terrain.width(), terrain.height());
+    let mut out = Grid::new(width + 1, height + 1);
+    if width == 0 || height == 0 {
+      return out;
+    }
+    for coord in out.area() {
+      let cell = |dx: u32, dy: u32| {
+        let x = (coord.x + dx).saturating_sub(1).min(width - 1);
+        let y = (coord.y + dy).saturating_sub(1).min(height - 1);
+        terrain.get(Coord::new(x, y))
+      };
+      if let Some(tile) =
+        self.resolve([cell(0, 0), cell(1, 0), cell(0, 1), cell(1, 1)])
+      {
+        out.insert(coord, tile);
+      }
+    }
+    out
+  }
+
+  /// Get the priorities of two terrains as (lower, higher).
+  fn pair(&self, a: &T, b: &T) -> Option<(usize, usize)> {
+    let (a, b) = (*self.priorities.get(a)?, *self.priorities.get(b)?);
+    Some((a.min(b), a.max(b)))
+  }
+}
+
+impl<T: Clone + Eq + Hash> Default for TerrainRules<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, Grid, TerrainRules};
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+  enum Ground {
+    Water,
+    Sand,
+    Grass,
+  }
+
+  fn rules() -> TerrainRules<Ground> {
+    let mut rules = TerrainRules::new();
+    rules.add_terrain(Ground::Water, 0);
+    rules.add_terrain(Ground::Sand, 1);
+    rules.add_terrain(Ground::Grass, 2);
+    rules.add_transition(&Ground::Sand, &Ground::Water, 16);
+    rules.add_transition(&Ground::Grass, &Ground::Sand, 32);
+    rules
+  }
+
+  #[test]
+  fn resolve() {
+    let rules = rules();
+    let (w, s, g) = (
+      Some(&Ground::Water),
+      Some(&Ground::Sand),
+      Some(&Ground::Grass),
+    );
+    assert_eq!(rules.resolve([w, w, w, w]), Some(0));
+    assert_eq!(rules.resolve([s, s, w, w]), Some(16 + 3));
+    assert_eq!(rules.resolve([w, s, w, s]), Some(16 + 10));
+    // Grass over sand; the water counts as sand
+    assert_eq!(rules.resolve([g, s, w, s]), Some(32 + 1));
+    // Missing corners don't make a border
+    assert_eq!(rules.resolve([None, g, None, None]), Some(2));
+    assert_eq!(rules.resolve([None, g, s, None]), Some(32 + 2));
+    // No rule for grass straight onto water
+    assert_eq!(rules.resolve([g, w, w, w]), None);
+    assert_eq!(rules.resolve([None; 4]), None);
+  }
+
+  #[test]
+  fn apply() {
+    let map = Grid::from_str_map("~~.\n~..", |c, _| match c {
+      '~' => Some(Ground::Water),
+      '.' => Some(Ground::Sand),
+      _ => None,
+    });
+    let tiles = rules().apply(&map);
+    assert_eq!((tiles.width(), tiles.height()), (4, 3));
+    let row = |y| {
+      (0..4)
+        .map(|x| *tiles.get(Coord::new(x, y)).unwrap())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(row(0), [0, 0, 16 + 10, 1]);
+    assert_eq!(row(1), [0, 16 + 8, 16 + 14, 1]);
+    assert_eq!(row(2), [0, 16 + 10, 1, 1]);
+
+    let mut rules = rules();
+    rules.add_terrain(Ground::Sand, 7);
+    assert_eq!(rules.apply(&map).get(Coord::new(3, 0)), Some(&7));
+
+    let nothing: Grid<Ground> = Grid::new(0, 0);
+    assert_eq!(rules.apply(&nothing).iter().count(), 0);
+  }
+}
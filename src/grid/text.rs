@@ -0,0 +1,116 @@
+use std::fmt::{Display, Write};
+
+use crate::Coord;
+
+use super::Grid;
+
+impl<T> Grid<T> {
+  /// Build a grid out of ASCII art, one row per line.
+  ///
+  /// The grid is as wide as the longest line; shorter lines are padded with
+  /// empty cells. `cell` is called for every character, and returning `None`
+  /// leaves that cell empty.
+  ///
+  /// ```
+  /// # use aglet::{Coord, Grid};
+  /// let grid = Grid::from_str_map("#.#\n.#.", |c, _| (c == '#').then_some(()));
+  /// assert_eq!(grid.width(), 3);
+  /// assert!(grid.contains(Coord::new(1, 1)));
+  /// assert!(!grid.contains(Coord::new(1, 0)));
+  /// ```
+  pub fn from_str_map<F: Fn(char, Coord) -> Option<T>>(
+    s: &str,
+    cell: F,
+  ) -> Grid<T> {
+    let width = s.lines().map(|line| line.chars().count()).max();
+    let height = s.lines().count();
+    let mut grid = Grid::new(width.unwrap_or(0) as u32, height as u32);
+    for (y, line) in s.lines().enumerate() {
+      for (x, c) in line.chars().enumerate() {
+        let coord = Coord::new(x as u32, y as u32);
+        if let Some(val) = cell(c, coord) {
+          grid.insert(coord, val);
+        }
+      }
+    }
+    grid
+  }
+
+  /// Draw this grid as ASCII art, one row per line.
+  ///
+  /// This is the inverse of [`Self::from_str_map`]. There is no trailing
+  /// newline.
+  pub fn render<F: Fn(Option<&T>) -> char>(&self, cell: F) -> String {
+    let mut out = String::new();
+    for y in 0..self.height {
+      if y > 0 {
+        out.push('\n');
+      }
+      for x in 0..self.width {
+        out.push(cell(self.get(Coord::new(x, y))));
+      }
+    }
+    out
+  }
+}
+
+/// Parse ASCII art into a grid for tests, with `.` as an empty cell and any
+/// other character as itself. This is the inverse of the `Display` impl.
+#[cfg(test)]
+pub(crate) fn char_grid(map: &str) -> Grid<char> {
+  Grid::from_str_map(map, |c, _| (c != '.').then_some(c))
+}
+
+/// Draws the grid as rows of characters, with `.` for empty cells.
+impl Display for Grid<char> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for y in 0..self.height {
+      if y > 0 {
+        f.write_char('\n')?;
+      }
+      for x in 0..self.width {
+        f.write_char(self.get(Coord::new(x, y)).copied().unwrap_or('.'))?;
+      }
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::char_grid;
+  use crate::{Coord, Grid};
+
+  #[test]
+  fn round_trip() {
+    let map = "#####\n#..@#\n#.#.#\n#####";
+    let grid = char_grid(map);
+    assert_eq!(grid.width(), 5);
+    assert_eq!(grid.height(), 4);
+    assert_eq!(grid.get(Coord::new(3, 1)), Some(&'@'));
+    assert_eq!(grid.get(Coord::new(1, 1)), None);
+    assert_eq!(grid.to_string(), map);
+    assert_eq!(
+      grid.render(|c| c.map_or(' ', |_| 'x')),
+      "xxxxx\nx  xx\nx x x\nxxxxx"
+    );
+
+    let walls = Grid::from_str_map(map, |c, _| (c == '#').then_some(()));
+    let back = walls.render(|c| if c.is_some() { '#' } else { '.' });
+    assert_eq!(back, map.replace('@', "."));
+  }
+
+  #[test]
+  fn ragged_and_empty() {
+    let grid =
+      Grid::from_str_map("ab\nc\n", |c, coord| Some((c, coord.x + coord.y)));
+    assert_eq!((grid.width(), grid.height()), (2, 2));
+    assert_eq!(grid.get(Coord::new(1, 0)), Some(&('b', 1)));
+    assert_eq!(grid.get(Coord::new(1, 1)), None);
+    assert_eq!(grid.render(|c| c.map_or('.', |(c, _)| *c)), "ab\nc.");
+
+    let empty: Grid<char> = Grid::from_str_map("", |c, _| Some(c));
+    assert_eq!((empty.width(), empty.height()), (0, 0));
+    assert_eq!(empty.to_string(), "");
+  }
+}
@@ -0,0 +1,240 @@
+use crate::{Coord, CoordVec, Direction4, Direction8, LineIter};
+
+use super::{Grid, GridIter, Neighborhood};
+
+/// A [`Grid`] where the edges wrap around, like a Pac-Man level or the surface
+/// of a donut.
+///
+/// Every coordinate is valid; they're taken modulo the width and height.
+/// Lookups take anything that turns into a [`CoordVec`], so you can use
+/// negative coordinates to reach around the top-left edges.
+///
+/// Making a torus grid with a width or height of zero is allowed, but
+/// everything that takes a coordinate will panic.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TorusGrid<T> {
+  grid: Grid<T>,
+}
+
+impl<T> TorusGrid<T> {
+  pub fn new(width: u32, height: u32) -> Self {
+    Self {
+      grid: Grid::new(width, height),
+    }
+  }
+
+  pub fn width(&self) -> u32 {
+    self.grid.width()
+  }
+
+  pub fn height(&self) -> u32 {
+    self.grid.height()
+  }
+
+  /// Get the regular grid underneath, which doesn't wrap.
+  pub fn as_grid(&self) -> &Grid<T> {
+    &self.grid
+  }
+
+  pub fn into_grid(self) -> Grid<T> {
+    self.grid
+  }
+
+  /// Wrap any coordinate onto the grid.
+  pub fn wrap(&self, coord: impl Into<CoordVec>) -> Coord {
+    wrap(coord.into(), self.width(), self.height())
+  }
+
+  pub fn get(&self, coord: impl Into<CoordVec>) -> Option<&T> {
+    self.grid.get(self.wrap(coord))
+  }
+
+  pub fn get_mut(&mut self, coord: impl Into<CoordVec>) -> Option<&mut T> {
+    let coord = self.wrap(coord);
+    self.grid.get_mut(coord)
+  }
+
+  /// Returns the old value
+  pub fn insert(&mut self, coord: impl Into<CoordVec>, val: T) -> Option<T> {
+    let coord = self.wrap(coord);
+    self.grid.insert(coord, val)
+  }
+
+  pub fn remove(&mut self, coord: impl Into<CoordVec>) -> Option<T> {
+    let coord = self.wrap(coord);
+    self.grid.remove(coord)
+  }
+
+  pub fn contains(&self, coord: impl Into<CoordVec>) -> bool {
+    self.get(coord).is_some()
+  }
+
+  pub fn iter(&self) -> GridIter<'_, T> {
+    self.grid.iter()
+  }
+
+  /// Get the orthagonal neighbors of a cell, wrapping around the edges.
+  ///
+  /// They are in the order of [`Direction4::DIRECTIONS`]. On very small grids
+  /// some of them might be the same cell, or `coord` itself.
+  pub fn neighbors4(&self, coord: impl Into<CoordVec>) -> [Coord; 4] {
+    let coord = coord.into();
+    Direction4::DIRECTIONS.map(|dir| self.wrap(coord + dir))
+  }
+
+  /// Get all eight neighbors of a cell, wrapping around the edges.
+  ///
+  /// They are in the order of [`Direction8::DIRECTIONS`].
+  pub fn neighbors8(&self, coord: impl Into<CoordVec>) -> [Coord; 8] {
+    let coord = coord.into();
+    Direction8::DIRECTIONS.map(|dir| self.wrap(coord + dir))
+  }
+
+  /// Get the shortest offset from `from` to `to`, which might go around the
+  /// edges.
+  ///
+  /// If two ways around are equally short, this picks the positive one.
+  pub fn delta(
+    &self,
+    from: impl Into<CoordVec>,
+    to: impl Into<CoordVec>,
+  ) -> CoordVec {
+    let (from, to) = (self.wrap(from), self.wrap(to));
+    let shortest = |a: u32, b: u32, size: u32| {
+      let (a, b, size) = (a as i64, b as i64, size as i64);
+      let d = (b - a).rem_euclid(size);
+      (if d > size / 2 { d - size } else { d }) as i32
+    };
+    CoordVec::new(
+      shortest(from.x, to.x, self.width()),
+      shortest(from.y, to.y, self.height()),
+    )
+  }
+
+  /// Get the taxicab distance between two cells, going around the edges if
+  /// that's shorter.
+  pub fn manhattan_distance(
+    &self,
+    a: impl Into<CoordVec>,
+    b: impl Into<CoordVec>,
+  ) -> u32 {
+    self.delta(a, b).manhattan_distance(CoordVec::new(0, 0))
+  }
+
+  /// Get the chessboard distance between two cells, going around the edges
+  /// if that's shorter.
+  pub fn chebyshev_distance(
+    &self,
+    a: impl Into<CoordVec>,
+    b: impl Into<CoordVec>,
+  ) -> u32 {
+    self.delta(a, b).chebyshev_distance(CoordVec::new(0, 0))
+  }
+
+  /// Draw the shortest line from `start` to `end`, wrapping around the edges.
+  ///
+  /// Like [`LineIter::new`], this includes `start` but not `end`.
+  pub fn line(
+    &self,
+    start: impl Into<CoordVec>,
+    end: impl Into<CoordVec>,
+  ) -> impl Iterator<Item = Coord> {
+    let start = start.into();
+    let delta = self.delta(start, end);
+    let (width, height) = (self.width(), self.height());
+    // Shift over by a whole grid so the line stays in positive space
+    let start = self.wrap(start) + Coord::new(width, height);
+    let end = (start.to_icoord() + delta)
+      .to_coord()
+      .expect("delta is at most half the grid");
+    LineIter::new(start, end).map(move |c| wrap(c.to_icoord(), width, height))
+  }
+
+  /// Like [`Grid::step_automaton`], but neighbors wrap around the edges.
+  pub fn step_automaton<F>(&mut self, mut rule: F)
+  where
+    F: FnMut(&Neighborhood<'_, T>) -> Option<T>,
+  {
+    let grid = &self.grid;
+    let next = (0..grid.spots.len())
+      .map(|idx| rule(&Neighborhood::new_wrapping(grid, grid.coord_of(idx))))
+      .collect();
+    self.grid.spots = next;
+  }
+}
+
+impl<T> From<Grid<T>> for TorusGrid<T> {
+  fn from(grid: Grid<T>) -> Self {
+    Self { grid }
+  }
+}
+
+/// Panics if the width or height is zero.
+pub(crate) fn wrap(coord: CoordVec, width: u32, height: u32) -> Coord {
+  coord.to_coord_wrapping(Coord::new(width, height))
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{Coord, CoordVec, TorusGrid};
+
+  #[test]
+  fn wrapping() {
+    let mut grid = TorusGrid::new(4, 3);
+    grid.insert(CoordVec::new(-1, -1), 'a');
+    assert_eq!(grid.get(Coord::new(3, 2)), Some(&'a'));
+    assert_eq!(grid.get(CoordVec::new(7, 5)), Some(&'a'));
+    assert_eq!(
+      grid.neighbors4(Coord::new(0, 0)),
+      [
+        Coord::new(0, 2),
+        Coord::new(1, 0),
+        Coord::new(0, 1),
+        Coord::new(3, 0)
+      ]
+    );
+    assert!(grid
+      .neighbors8(Coord::new(0, 0))
+      .contains(&Coord::new(3, 2)));
+  }
+
+  #[test]
+  fn distances() {
+    let grid: TorusGrid<()> = TorusGrid::new(10, 6);
+    assert_eq!(
+      grid.delta(Coord::new(1, 1), Coord::new(8, 2)),
+      CoordVec::new(-3, 1)
+    );
+    assert_eq!(
+      grid.manhattan_distance(Coord::new(1, 1), Coord::new(8, 5)),
+      5
+    );
+    assert_eq!(
+      grid.chebyshev_distance(Coord::new(0, 0), Coord::new(5, 3)),
+      5
+    );
+
+    let line: Vec<_> = grid.line(Coord::new(8, 0), Coord::new(1, 0)).collect();
+    assert_eq!(line, [Coord::new(8, 0), Coord::new(9, 0), Coord::new(0, 0)]);
+  }
+
+  #[test]
+  fn glider_wraps() {
+    let mut grid = TorusGrid::new(5, 5);
+    for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+      grid.insert(Coord::new(x, y), ());
+    }
+    let start: Vec<_> = grid.iter().map(|(c, _)| c).collect();
+    // A glider moves one cell diagonally every four generations, so after
+    // going all the way around it's back where it started
+    for _ in 0..20 {
+      grid.step_automaton(|nb| match (nb.value(), nb.count_filled()) {
+        (Some(()), 2 | 3) | (None, 3) => Some(()),
+        _ => None,
+      });
+    }
+    let end: Vec<_> = grid.iter().map(|(c, _)| c).collect();
+    assert_eq!(start, end);
+  }
+}
@@ -0,0 +1,203 @@
+use crate::{Area, Coord, Rotation, Transform};
+
+use super::Grid;
+
+/// What to do when pasting one grid onto another with [`Grid::paste`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PastePolicy {
+  /// Filled slots in the source overwrite the destination.
+  /// Empty slots in the source leave the destination alone.
+  #[default]
+  Overwrite,
+  /// Only fill in slots that are empty in the destination.
+  KeepExisting,
+  /// Copy every slot verbatim, so empty slots in the source
+  /// clear the destination.
+  Replace,
+}
+
+impl<T> Grid<T> {
+  /// Rotate this grid by a quarter turn in the given direction.
+  ///
+  /// The width and height are swapped.
+  pub fn rotated(self, rot: Rotation) -> Grid<T> {
+    let (width, height) = (self.width, self.height);
+    match rot {
+      Rotation::Clockwise => {
+        self.remap(height, width, |c| Coord::new(height - 1 - c.y, c.x))
+      }
+      Rotation::CounterClockwise => {
+        self.remap(height, width, |c| Coord::new(c.y, width - 1 - c.x))
+      }
+    }
+  }
+
+  /// Apply any combination of rotation and mirroring to this grid.
+  ///
+  /// The width and height are swapped if [`Transform::swaps_axes`].
+  pub fn transformed(self, transform: Transform) -> Grid<T> {
+    let (width, height) = (self.width, self.height);
+    let (new_width, new_height) = transform.apply_size(width, height);
+    self.remap(new_width, new_height, |c| {
+      transform.apply_coord(c, width, height)
+    })
+  }
+
+  /// Mirror this grid left-to-right.
+  pub fn flip_horizontal(&mut self) {
+    let width = self.width as usize;
+    if width == 0 {
+      return;
+    }
+    for row in self.spots.chunks_mut(width) {
+      row.reverse();
+    }
+  }
+
+  /// Mirror this grid top-to-bottom.
+  pub fn flip_vertical(&mut self) {
+    let (width, height) = (self.width as usize, self.height as usize);
+    for y in 0..height / 2 {
+      let (top, bottom) = self.spots.split_at_mut((height - 1 - y) * width);
+      top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
+    }
+  }
+
+  /// Swap the X and Y axes of this grid, mirroring it across the diagonal.
+  pub fn transpose(&mut self) {
+    let old = self.take();
+    *self = old.remap(self.height, self.width, |c| Coord::new(c.y, c.x));
+  }
+
+  /// Paste `other` onto this grid, with its top-left corner at `at`.
+  ///
+  /// What happens to overlapping slots is controlled by `policy`.
+  /// Any parts of `other` that stick out of this grid are ignored.
+  pub fn paste(&mut self, other: &Grid<T>, at: Coord, policy: PastePolicy)
+  where
+    T: Clone,
+  {
+    for (idx, slot) in other.spots.iter().enumerate() {
+      let coord = at + other.coord_of(idx);
+      let dst = match self.idx(coord) {
+        Some(it) => &mut self.spots[it],
+        None => continue,
+      };
+      match (policy, slot) {
+        (PastePolicy::Replace, _) => *dst = slot.clone(),
+        (PastePolicy::Overwrite, Some(it)) => *dst = Some(it.clone()),
+        (PastePolicy::KeepExisting, Some(it)) if dst.is_none() => {
+          *dst = Some(it.clone())
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// Copy out the given area of this grid into a new grid.
+  ///
+  /// The new grid is the same size as the area; any part of the area that's
+  /// out of bounds of this grid is empty.
+  pub fn crop(&self, area: Area) -> Grid<T>
+  where
+    T: Clone,
+  {
+    let mut out = Grid::new(area.width, area.height);
+    for (idx, slot) in out.spots.iter_mut().enumerate() {
+      let idx = idx as u32;
+      let coord = area.corner + Coord::new(idx % area.width, idx / area.width);
+      *slot = self.get(coord).cloned();
+    }
+    out
+  }
+
+  /// Move every value into a new grid of the given size,
+  /// at the position given by `f`.
+  fn remap<F: Fn(Coord) -> Coord>(
+    self,
+    width: u32,
+    height: u32,
+    f: F,
+  ) -> Grid<T> {
+    let mut out = Grid::new(width, height);
+    for (coord, val) in self {
+      out.insert(f(coord), val);
+    }
+    out
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    grid::text::char_grid, Area, Coord, PastePolicy, Rotation, Transform,
+  };
+
+  #[test]
+  fn rotate() {
+    let grid = char_grid("ab.\n..c");
+    let cw = grid.clone().rotated(Rotation::Clockwise);
+    assert_eq!(cw.to_string(), ".a\n.b\nc.");
+    let ccw = grid.clone().rotated(Rotation::CounterClockwise);
+    assert_eq!(ccw.to_string(), ".c\nb.\na.");
+    assert_eq!(
+      cw.rotated(Rotation::CounterClockwise).to_string(),
+      grid.to_string()
+    );
+  }
+
+  #[test]
+  fn transformed() {
+    let grid = char_grid("ab.\n..c");
+    let rotated = grid.clone().transformed(Transform::rotation(1));
+    assert_eq!(
+      rotated.to_string(),
+      grid.clone().rotated(Rotation::Clockwise).to_string()
+    );
+    let mut flipped = grid.clone();
+    flipped.flip_horizontal();
+    assert_eq!(
+      grid.clone().transformed(Transform::MIRROR).to_string(),
+      flipped.to_string()
+    );
+    for t in Transform::all() {
+      let there = grid.clone().transformed(t);
+      assert_eq!(there.transformed(t.inverse()).to_string(), grid.to_string());
+    }
+  }
+
+  #[test]
+  fn flip_and_transpose() {
+    let mut grid = char_grid("ab.\n..c\nd..");
+    grid.flip_horizontal();
+    assert_eq!(grid.to_string(), ".ba\nc..\n..d");
+    grid.flip_vertical();
+    assert_eq!(grid.to_string(), "..d\nc..\n.ba");
+
+    let mut grid = char_grid("ab.\n..c");
+    grid.transpose();
+    assert_eq!(grid.to_string(), "a.\nb.\n.c");
+  }
+
+  #[test]
+  fn crop_and_paste() {
+    let grid = char_grid("ab.\n..c\nd..");
+    let cropped = grid.crop(Area::new(Coord::new(1, 0), 3, 2));
+    assert_eq!(cropped.to_string(), "b..\n.c.");
+
+    let base = char_grid("xxx\n...\n...");
+    let stamp = char_grid("o.\noo");
+
+    let mut g = base.clone();
+    g.paste(&stamp, Coord::new(0, 0), PastePolicy::Overwrite);
+    assert_eq!(g.to_string(), "oxx\noo.\n...");
+
+    let mut g = base.clone();
+    g.paste(&stamp, Coord::new(0, 0), PastePolicy::KeepExisting);
+    assert_eq!(g.to_string(), "xxx\noo.\n...");
+
+    let mut g = base.clone();
+    g.paste(&stamp, Coord::new(2, 0), PastePolicy::Replace);
+    assert_eq!(g.to_string(), "xxo\n..o\n...");
+  }
+}
@@ -0,0 +1,247 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::{Connectivity, Coord};
+
+use super::Grid;
+
+/// A seed for [`Grid::voronoi`] and [`Grid::voronoi_path`].
+///
+/// The weight is a head start, measured in cells: a seed with weight 3
+/// claims cells as if they were 3 steps closer to it than they are. So
+/// heavier seeds end up with bigger regions. A weight of 0 gives an ordinary
+/// Voronoi partition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VoronoiSeed {
+  pub coord: Coord,
+  pub weight: f32,
+}
+
+impl VoronoiSeed {
+  /// A seed with no weight.
+  pub fn new(coord: Coord) -> Self {
+    Self::weighted(coord, 0.0)
+  }
+
+  pub fn weighted(coord: Coord, weight: f32) -> Self {
+    Self { coord, weight }
+  }
+}
+
+impl From<Coord> for VoronoiSeed {
+  fn from(coord: Coord) -> Self {
+    Self::new(coord)
+  }
+}
+
+/// How [`Grid::voronoi`] measures the distance from a cell to a seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VoronoiMetric {
+  /// Straight-line distance.
+  ///
+  /// Weighted seeds make a power diagram: a cell goes to the seed with the
+  /// smallest `distance² - weight²`, so region borders stay straight lines.
+  #[default]
+  Euclidean,
+  /// Taxicab distance, minus the weight.
+  Manhattan,
+  /// Chessboard distance, minus the weight.
+  Chebyshev,
+}
+
+impl VoronoiMetric {
+  fn score(self, cell: Coord, seed: &VoronoiSeed) -> f32 {
+    match self {
+      VoronoiMetric::Euclidean => {
+        let dx = cell.x as f32 - seed.coord.x as f32;
+        let dy = cell.y as f32 - seed.coord.y as f32;
+        dx * dx + dy * dy - seed.weight * seed.weight
+      }
+      VoronoiMetric::Manhattan => {
+        cell.manhattan_distance(seed.coord) as f32 - seed.weight
+      }
+      VoronoiMetric::Chebyshev => {
+        cell.chebyshev_distance(seed.coord) as f32 - seed.weight
+      }
+    }
+  }
+}
+
+impl Grid<usize> {
+  /// Split a `width` by `height` grid into regions around each seed.
+  ///
+  /// Each cell stores the index of the seed it's closest to. Ties go to the
+  /// seed that comes first. With no seeds, every cell is empty.
+  ///
+  /// ```
+  /// # use aglet::{Coord, Grid, VoronoiMetric, VoronoiSeed};
+  /// let seeds = [
+  ///   VoronoiSeed::new(Coord::new(0, 0)),
+  ///   VoronoiSeed::weighted(Coord::new(6, 0), 2.0),
+  /// ];
+  /// let regions = Grid::voronoi(7, 1, &seeds, VoronoiMetric::Manhattan);
+  /// let row = regions.render(|seed| char::from(b'a' + *seed.unwrap() as u8));
+  /// assert_eq!(row, "aaabbbb");
+  /// ```
+  pub fn voronoi(
+    width: u32,
+    height: u32,
+    seeds: &[VoronoiSeed],
+    metric: VoronoiMetric,
+  ) -> Grid<usize> {
+    let mut out = Grid::new(width, height);
+    for (idx, spot) in out.spots.iter_mut().enumerate() {
+      let cell = Coord::new(idx as u32 % width, idx as u32 / width);
+      *spot = seeds
+        .iter()
+        .map(|seed| metric.score(cell, seed))
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(seed, _)| seed);
+    }
+    out
+  }
+
+  /// Split a `width` by `height` grid into regions around each seed, going
+  /// by the length of the shortest path through passable cells instead of
+  /// straight-line distance.
+  ///
+  /// Regions grow out from the seeds one step at a time, moving to
+  /// neighbors as given by `connectivity`, and the weights are subtracted
+  /// from the path lengths. So regions wrap around walls instead of leaking
+  /// through them. Cells that aren't `passable`, or that no seed can reach,
+  /// are left empty, as are seeds that aren't on passable cells.
+  pub fn voronoi_path<F: FnMut(Coord) -> bool>(
+    width: u32,
+    height: u32,
+    seeds: &[VoronoiSeed],
+    connectivity: Connectivity,
+    mut passable: F,
+  ) -> Grid<usize> {
+    let mut out = Grid::new(width, height);
+    let area = out.area();
+    let mut costs = vec![f32::INFINITY; out.spots.len()];
+    let mut open = BinaryHeap::new();
+    for (seed, VoronoiSeed { coord, weight }) in seeds.iter().enumerate() {
+      if out.idx(*coord).is_some() && passable(*coord) {
+        open.push(Frontier {
+          cost: -weight,
+          seed,
+          coord: *coord,
+        });
+      }
+    }
+
+    while let Some(Frontier { cost, seed, coord }) = open.pop() {
+      let idx = out.idx(coord).unwrap();
+      if out.spots[idx].is_some() {
+        // Someone else got here first
+        continue;
+      }
+      out.spots[idx] = Some(seed);
+      costs[idx] = cost;
+
+      for next in connectivity.neighbors_within(coord, area) {
+        let next_idx = out.idx(next).unwrap();
+        if out.spots[next_idx].is_some() || cost + 1.0 >= costs[next_idx] {
+          continue;
+        }
+        if !passable(next) {
+          continue;
+        }
+        costs[next_idx] = cost + 1.0;
+        open.push(Frontier {
+          cost: cost + 1.0,
+          seed,
+          coord: next,
+        });
+      }
+    }
+    out
+  }
+}
+
+/// An entry in the open set of [`Grid::voronoi_path`].
+///
+/// Ordered backwards so the max-heap pops the cheapest first, with ties going
+/// to the earlier seed.
+#[derive(Debug, Clone, Copy)]
+struct Frontier {
+  cost: f32,
+  seed: usize,
+  coord: Coord,
+}
+
+impl Ord for Frontier {
+  fn cmp(&self, other: &Self) -> Ordering {
+    other
+      .cost
+      .total_cmp(&self.cost)
+      .then_with(|| other.seed.cmp(&self.seed))
+  }
+}
+
+impl PartialOrd for Frontier {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl PartialEq for Frontier {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for Frontier {}
+
+#[cfg(test)]
+mod test {
+  use crate::{Connectivity, Coord, Grid, VoronoiMetric, VoronoiSeed};
+
+  fn draw(grid: &Grid<usize>) -> String {
+    grid.render(|seed| seed.map_or('#', |s| char::from(b'a' + *s as u8)))
+  }
+
+  #[test]
+  fn plain_and_weighted() {
+    let seeds = [Coord::new(0, 0), Coord::new(4, 2)].map(VoronoiSeed::from);
+    let regions = Grid::voronoi(5, 3, &seeds, VoronoiMetric::Euclidean);
+    assert_eq!(draw(&regions), "aaabb\naaabb\naabbb");
+
+    let heavy = [seeds[0], VoronoiSeed::weighted(Coord::new(4, 2), 3.0)];
+    let regions = Grid::voronoi(5, 3, &heavy, VoronoiMetric::Euclidean);
+    assert_eq!(draw(&regions), "aabbb\nabbbb\nabbbb");
+
+    let regions = Grid::voronoi(5, 3, &seeds, VoronoiMetric::Chebyshev);
+    assert_eq!(draw(&regions), "aaabb\naaabb\naaabb");
+
+    let nobody: Grid<usize> =
+      Grid::voronoi(3, 3, &[], VoronoiMetric::Manhattan);
+    assert_eq!(nobody.iter().count(), 0);
+  }
+
+  #[test]
+  fn around_walls() {
+    let map = Grid::from_str_map(
+      ".....\n\
+       .###.\n\
+       .#...",
+      |c, _| (c == '#').then_some(()),
+    );
+    let seeds = [Coord::new(0, 2), Coord::new(2, 2)].map(VoronoiSeed::from);
+    let regions = Grid::voronoi_path(5, 3, &seeds, Connectivity::Four, |c| {
+      !map.contains(c)
+    });
+    // Straight-line, b would own everything right of the wall; going around,
+    // a gets most of the top row too.
+    assert_eq!(draw(&regions), "aaaab\na###b\na#bbb");
+
+    let heavy = [seeds[0], VoronoiSeed::weighted(Coord::new(2, 2), 4.0)];
+    let regions = Grid::voronoi_path(5, 3, &heavy, Connectivity::Four, |c| {
+      !map.contains(c)
+    });
+    assert_eq!(draw(&regions), "aabbb\na###b\na#bbb");
+  }
+}
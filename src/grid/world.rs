@@ -0,0 +1,186 @@
+use crate::{Coord, CoordVec, IArea};
+
+use super::Grid;
+
+/// A bunch of [`Grid`]s placed at offsets in one big signed coordinate space.
+///
+/// Grids can overlap. Looking up a cell goes through the grids from the most
+/// recently placed down, and the first one with something in that cell wins;
+/// empty cells let the grids underneath show through. This is handy for
+/// stitching chunks together, or laying a building's interior over the
+/// outdoors.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct World<T> {
+  /// Bottom first.
+  placements: Vec<(CoordVec, Grid<T>)>,
+}
+
+impl<T> World<T> {
+  /// Make a world with nothing in it.
+  pub fn new() -> Self {
+    Self {
+      placements: Vec::new(),
+    }
+  }
+
+  /// Place a grid with its top-left corner at `offset`, on top of all the
+  /// others.
+  ///
+  /// Returns the index of the placement.
+  pub fn place(&mut self, offset: CoordVec, grid: Grid<T>) -> usize {
+    self.placements.push((offset, grid));
+    self.placements.len() - 1
+  }
+
+  /// Get the offset and grid of a placement.
+  pub fn placement(&self, idx: usize) -> Option<(CoordVec, &Grid<T>)> {
+    self
+      .placements
+      .get(idx)
+      .map(|(offset, grid)| (*offset, grid))
+  }
+
+  pub fn placement_mut(
+    &mut self,
+    idx: usize,
+  ) -> Option<(CoordVec, &mut Grid<T>)> {
+    self
+      .placements
+      .get_mut(idx)
+      .map(|(offset, grid)| (*offset, grid))
+  }
+
+  /// Take a grid back out of the world.
+  ///
+  /// Like [`Vec::remove`], this shifts down the index of everything placed
+  /// after it. Panics if the placement doesn't exist.
+  pub fn unplace(&mut self, idx: usize) -> (CoordVec, Grid<T>) {
+    self.placements.remove(idx)
+  }
+
+  /// The number of placed grids.
+  pub fn len(&self) -> usize {
+    self.placements.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.placements.is_empty()
+  }
+
+  /// Get the area a placement covers, in world space.
+  pub fn placement_area(&self, idx: usize) -> Option<IArea> {
+    let (offset, grid) = self.placement(idx)?;
+    Some(IArea::new(offset, grid.width(), grid.height()))
+  }
+
+  /// Get the smallest area covering every placed grid, or `None` if there
+  /// aren't any.
+  pub fn bounds(&self) -> Option<IArea> {
+    (0..self.len())
+      .filter_map(|idx| self.placement_area(idx))
+      .reduce(|a, b| a.union_bounds(&b))
+  }
+
+  /// Find which placement the value at `coord` comes from.
+  pub fn placement_at(&self, coord: impl Into<CoordVec>) -> Option<usize> {
+    let coord = coord.into();
+    self.placements.iter().rposition(|(offset, grid)| {
+      local(*offset, coord).is_some_and(|c| grid.contains(c))
+    })
+  }
+
+  pub fn get(&self, coord: impl Into<CoordVec>) -> Option<&T> {
+    let coord = coord.into();
+    let (offset, grid) = &self.placements[self.placement_at(coord)?];
+    grid.get(local(*offset, coord)?)
+  }
+
+  pub fn get_mut(&mut self, coord: impl Into<CoordVec>) -> Option<&mut T> {
+    let coord = coord.into();
+    let idx = self.placement_at(coord)?;
+    let (offset, grid) = &mut self.placements[idx];
+    grid.get_mut(local(*offset, coord)?)
+  }
+
+  pub fn contains(&self, coord: impl Into<CoordVec>) -> bool {
+    self.placement_at(coord).is_some()
+  }
+
+  /// Iterate over every visible cell in world space.
+  ///
+  /// Cells hidden under a filled cell of a later placement are skipped, so
+  /// each coordinate comes up at most once. The cells come bottom placement
+  /// first, and in row-major order within each placement.
+  pub fn iter(&self) -> impl Iterator<Item = (CoordVec, &T)> + '_ {
+    self
+      .placements
+      .iter()
+      .enumerate()
+      .flat_map(move |(idx, (offset, grid))| {
+        grid
+          .iter()
+          .map(move |(coord, val)| (*offset + coord.to_icoord(), val))
+          .filter(move |(coord, _)| self.placement_at(*coord) == Some(idx))
+      })
+  }
+}
+
+impl<T> Default for World<T> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Turn a world coordinate into one local to a grid at `offset`.
+fn local(offset: CoordVec, coord: CoordVec) -> Option<Coord> {
+  (coord - offset).to_coord()
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{grid::text::char_grid, CoordVec, IArea, World};
+
+  #[test]
+  fn overlapping() {
+    let mut world = World::new();
+    world.place(CoordVec::new(-2, -1), char_grid("aaa\naaa"));
+    let top = world.place(CoordVec::new(0, 0), char_grid("b.\n.b"));
+    assert_eq!(world.get(CoordVec::new(-2, -1)), Some(&'a'));
+    assert_eq!(world.get(CoordVec::new(0, 0)), Some(&'b'));
+    assert_eq!(world.get(CoordVec::new(-1, 0)), Some(&'a'));
+    // Empty cells on top let the bottom grid show through
+    assert_eq!(world.get(CoordVec::new(0, -1)), Some(&'a'));
+    assert_eq!(world.placement_at(CoordVec::new(0, 0)), Some(top));
+    assert_eq!(world.get(CoordVec::new(1, 0)), None);
+    assert_eq!(world.get(CoordVec::new(-3, 0)), None);
+    assert_eq!(
+      world.bounds(),
+      Some(IArea::new(CoordVec::new(-2, -1), 4, 3))
+    );
+
+    *world.get_mut(CoordVec::new(0, 0)).unwrap() = 'c';
+    assert_eq!(world.placement(top).unwrap().1.to_string(), "c.\n.b");
+  }
+
+  #[test]
+  fn iteration() {
+    let mut world = World::new();
+    world.place(CoordVec::new(0, 0), char_grid("ab"));
+    world.place(CoordVec::new(1, 0), char_grid("cd"));
+    let cells: Vec<_> = world.iter().collect();
+    assert_eq!(
+      cells,
+      [
+        (CoordVec::new(0, 0), &'a'),
+        (CoordVec::new(1, 0), &'c'),
+        (CoordVec::new(2, 0), &'d')
+      ]
+    );
+
+    let (_, under) = world.unplace(1);
+    assert_eq!(under.to_string(), "cd");
+    assert_eq!(world.get(CoordVec::new(1, 0)), Some(&'b'));
+    assert!(World::<()>::new().bounds().is_none());
+  }
+}
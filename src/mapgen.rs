@@ -0,0 +1,672 @@
+//! Procedural map generation.
+
+use crate::{coord_hash, geom::mix64, Area, Coord, CoordVec, Grid, LineEndMode, LineIter};
+
+/// A tile of a generated map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Tile {
+    #[default]
+    Wall,
+    Floor,
+}
+
+/// Settings for [`bsp_dungeon`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BspConfig {
+    /// The smallest width or height a room can have.
+    pub min_room_size: u32,
+    /// The largest width or height a room can have.
+    pub max_room_size: u32,
+    /// The same seed and size always make the same dungeon.
+    pub seed: u64,
+}
+
+impl Default for BspConfig {
+    fn default() -> Self {
+        Self {
+            min_room_size: 4,
+            max_room_size: 10,
+            seed: 0,
+        }
+    }
+}
+
+/// A map made by [`bsp_dungeon`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dungeon {
+    /// Every cell is filled, with either a wall or a floor.
+    pub grid: Grid<Tile>,
+    /// The rooms that were carved out, not counting corridors.
+    pub rooms: Vec<Area>,
+}
+
+/// Make a classic roguelike dungeon of rooms joined by corridors.
+///
+/// The map is recursively split in two until the pieces are about room-sized,
+/// using binary space partitioning, and a room is carved in each piece. Then
+/// each pair of pieces that were split apart is joined with an L-shaped
+/// corridor, so every room can reach every other one.
+///
+/// Rooms never touch each other or the edge of the map. If the map is too
+/// small to fit even one room, it's all wall.
+///
+/// Panics if `min_room_size` is zero or bigger than `max_room_size`.
+pub fn bsp_dungeon(width: u32, height: u32, config: &BspConfig) -> Dungeon {
+    assert!(
+        0 < config.min_room_size && config.min_room_size <= config.max_room_size,
+        "room sizes must be positive and in order"
+    );
+    let mut bsp = Bsp {
+        config,
+        rng: SplitMix64(config.seed),
+        grid: Grid::new(width, height),
+    };
+    for coord in bsp.grid.area() {
+        bsp.grid.insert(coord, Tile::Wall);
+    }
+    let rooms = bsp.split(Area::new(Coord::new(0, 0), width, height));
+    Dungeon {
+        grid: bsp.grid,
+        rooms,
+    }
+}
+
+struct Bsp<'a> {
+    config: &'a BspConfig,
+    rng: SplitMix64,
+    grid: Grid<Tile>,
+}
+
+impl Bsp<'_> {
+    /// Split up a leaf and carve out rooms in it, returning those rooms.
+    fn split(&mut self, leaf: Area) -> Vec<Area> {
+        // Leave a wall on each side of a room so rooms in neighboring leaves
+        // don't merge together
+        let min_leaf = self.config.min_room_size + 2;
+        let max_leaf = self.config.max_room_size + 2;
+
+        let too_big = leaf.width > max_leaf || leaf.height > max_leaf;
+        let can_split_vertical = leaf.width >= min_leaf * 2;
+        let can_split_horizontal = leaf.height >= min_leaf * 2;
+        let vertical = match (can_split_vertical, can_split_horizontal) {
+            _ if !too_big => None,
+            (false, false) => None,
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            // Prefer cutting the long way, so leaves don't get too skinny
+            (true, true) if leaf.width > leaf.height * 5 / 4 => Some(true),
+            (true, true) if leaf.height > leaf.width * 5 / 4 => Some(false),
+            (true, true) => Some(self.rng.coin()),
+        };
+
+        let Some(vertical) = vertical else {
+            return self.carve_room(leaf).into_iter().collect();
+        };
+        let (a, b) = if vertical {
+            leaf.split_vertical(self.rng.range(min_leaf, leaf.width - min_leaf))
+        } else {
+            leaf.split_horizontal(self.rng.range(min_leaf, leaf.height - min_leaf))
+        };
+        let mut rooms = self.split(a);
+        let others = self.split(b);
+        if !rooms.is_empty() && !others.is_empty() {
+            let from = rooms[self.rng.range(0, rooms.len() as u32 - 1) as usize];
+            let to = others[self.rng.range(0, others.len() as u32 - 1) as usize];
+            self.carve_corridor(from.center(), to.center());
+        }
+        rooms.extend(others);
+        rooms
+    }
+
+    fn carve_room(&mut self, leaf: Area) -> Option<Area> {
+        let min = self.config.min_room_size;
+        if leaf.width < min + 2 || leaf.height < min + 2 {
+            return None;
+        }
+        let width = self
+            .rng
+            .range(min, self.config.max_room_size.min(leaf.width - 2));
+        let height = self
+            .rng
+            .range(min, self.config.max_room_size.min(leaf.height - 2));
+        let corner = Coord::new(
+            leaf.x1() + self.rng.range(1, leaf.width - width - 1),
+            leaf.y1() + self.rng.range(1, leaf.height - height - 1),
+        );
+        let room = Area::new(corner, width, height);
+        for coord in room {
+            self.grid.insert(coord, Tile::Floor);
+        }
+        Some(room)
+    }
+
+    fn carve_corridor(&mut self, from: Coord, to: Coord) {
+        let bend = if self.rng.coin() {
+            Coord::new(to.x, from.y)
+        } else {
+            Coord::new(from.x, to.y)
+        };
+        let first = LineIter::new(from, bend);
+        let second = LineIter::new_with_end_mode(bend, to, LineEndMode::StopAt);
+        for coord in first.chain(second) {
+            self.grid.insert(coord, Tile::Floor);
+        }
+    }
+}
+
+/// Make a Bayer matrix for ordered dithering, `2^order` cells on a side.
+///
+/// Each cell gets a different threshold from 0 to 1, spread out in a
+/// regular crosshatch. Tile it across the screen or map and compare against
+/// it: `value < threshold` turns a smooth gradient into a dithered pattern.
+/// See [`blue_noise_matrix`] for a less regular-looking alternative.
+///
+/// Panics if `order` is over 15.
+pub fn bayer_matrix(order: u32) -> Grid<f32> {
+    assert!(order <= 15, "bayer matrix is too big");
+    let mut ranks = vec![0u32];
+    let mut size = 1;
+    for _ in 0..order {
+        let next_size = size * 2;
+        let mut next = vec![0; next_size * next_size];
+        for y in 0..next_size {
+            for x in 0..next_size {
+                let offset = match (x / size, y / size) {
+                    (0, 0) => 0,
+                    (1, 0) => 2,
+                    (0, 1) => 3,
+                    _ => 1,
+                };
+                next[y * next_size + x] = ranks[(y % size) * size + x % size] * 4 + offset;
+            }
+        }
+        ranks = next;
+        size = next_size;
+    }
+    ranks_to_grid(size as u32, size as u32, &ranks)
+}
+
+/// Make a blue noise threshold matrix for dithering, using Ulichney's
+/// void-and-cluster method.
+///
+/// Like [`bayer_matrix`], each cell gets a different threshold from 0 to 1,
+/// but cells with similar thresholds are spread apart without any visible
+/// pattern. So picking every cell where `threshold < density` scatters
+/// things about evenly, which looks nice for grass or stars. The matrix
+/// wraps around seamlessly, so it can be tiled.
+///
+/// This takes time proportional to the square of the number of cells, so it's
+/// meant for small tiles like 16x16 or 64x64. The same seed always makes the
+/// same matrix.
+pub fn blue_noise_matrix(width: u32, height: u32, seed: u64) -> Grid<f32> {
+    let count = (width * height) as usize;
+    if count == 0 {
+        return Grid::new(width, height);
+    }
+    let mut rng = SplitMix64(seed);
+    let mut field = EnergyField::new(width, height);
+    let initial = (count / 10).max(1);
+    while field.filled < initial {
+        field.set(rng.range(0, count as u32 - 1) as usize, true);
+    }
+    // Move points from clusters into voids until that stops changing anything
+    for _ in 0..count {
+        let cluster = field.tightest_cluster();
+        field.set(cluster, false);
+        let void = field.largest_void();
+        field.set(void, true);
+        if void == cluster {
+            break;
+        }
+    }
+
+    let mut ranks = vec![0; count];
+    let prototype = field.clone();
+    // Take points out of the tightest clusters, giving them the low ranks...
+    for rank in (0..initial).rev() {
+        let cluster = field.tightest_cluster();
+        field.set(cluster, false);
+        ranks[cluster] = rank as u32;
+    }
+    // ...then fill in the largest voids, giving them the high ranks
+    let mut field = prototype;
+    for rank in initial..count {
+        let void = field.largest_void();
+        field.set(void, true);
+        ranks[void] = rank as u32;
+    }
+    ranks_to_grid(width, height, &ranks)
+}
+
+/// What [`worley_noise`] measures at each cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorleyOutput {
+    /// The distance to the nearest feature point. This makes round blobs,
+    /// low in the middle and high at the edges, like stones or cells.
+    #[default]
+    Nearest,
+    /// The distance to the second-nearest feature point.
+    SecondNearest,
+    /// The second-nearest distance minus the nearest. This is near zero
+    /// along the borders between blobs, so thresholding it draws a network of
+    /// cracks, like dried mud.
+    Edges,
+}
+
+/// Make Worley (cellular) noise covering `area`.
+///
+/// One feature point is scattered somewhere in each `cell_size` by
+/// `cell_size` square, and each cell gets its distance to the nearby points
+/// as picked by `output`. Distances are measured in units of `cell_size`, so
+/// [`WorleyOutput::Nearest`] is mostly between 0 and 1 whatever the scale.
+///
+/// The grid's `(0, 0)` is the area's corner, but the noise goes by absolute
+/// coordinates, so neighboring areas line up seamlessly, which is handy for
+/// chunks. The same seed always makes the same noise.
+///
+/// Panics if `cell_size` is 0.
+pub fn worley_noise(area: Area, cell_size: u32, seed: u64, output: WorleyOutput) -> Grid<f32> {
+    assert!(cell_size > 0, "cell size must be positive");
+    let size = cell_size as f32;
+    let mut grid = Grid::new(area.width, area.height);
+    for coord in area {
+        let (px, py) = ((coord.x as f32 + 0.5) / size, (coord.y as f32 + 0.5) / size);
+        let home = CoordVec::new((coord.x / cell_size) as i32, (coord.y / cell_size) as i32);
+        let (mut nearest, mut second) = (f32::INFINITY, f32::INFINITY);
+        // The second-nearest point can be two squares away, so look at a 5x5
+        // block of squares
+        for dy in -2..=2 {
+            for dx in -2..=2 {
+                let square = home + CoordVec::new(dx, dy);
+                let hash = coord_hash(square, seed);
+                let jitter_x = (hash >> 40) as f32 / (1u32 << 24) as f32;
+                let jitter_y = (hash & 0xffffff) as f32 / (1u32 << 24) as f32;
+                let dist = (square.x as f32 + jitter_x - px).hypot(square.y as f32 + jitter_y - py);
+                if dist < nearest {
+                    second = nearest;
+                    nearest = dist;
+                } else if dist < second {
+                    second = dist;
+                }
+            }
+        }
+        let val = match output {
+            WorleyOutput::Nearest => nearest,
+            WorleyOutput::SecondNearest => second,
+            WorleyOutput::Edges => second - nearest,
+        };
+        grid.insert(coord - area.corner, val);
+    }
+    grid
+}
+
+/// How much loot [`scatter_loot`] puts in one region, and what kinds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LootBudget {
+    /// How many things to place in the region.
+    pub count: u32,
+    /// How likely each kind of item is, relative to the others. The
+    /// position in this list is the item index handed back.
+    pub weights: Vec<u32>,
+}
+
+/// Scatter items around a labelled map, with a separate budget for each
+/// region.
+///
+/// Each cell of `labels` says which region it's in, as an index into
+/// `budgets`; empty cells, and cells with labels past the end of `budgets`,
+/// never get anything. [`Grid::voronoi`] makes a good set of labels.
+///
+/// Items only go on cells that are `passable`, and are always at least
+/// `min_spacing` apart in straight-line distance, even across regions. If a
+/// region is too small or crowded to fit its whole budget, it gets as many
+/// as fit. Returns each item's position and which item it is, by its index
+/// in the budget's weights, going region by region. The same seed always
+/// gives the same loot.
+///
+/// Panics if a region with a nonzero count has no positive weights.
+pub fn scatter_loot<F: FnMut(Coord) -> bool>(
+    labels: &Grid<usize>,
+    budgets: &[LootBudget],
+    min_spacing: u32,
+    mut passable: F,
+    seed: u64,
+) -> Vec<(Coord, usize)> {
+    let mut rng = SplitMix64(seed);
+    let mut taken = Grid::new(labels.width(), labels.height());
+    let reach = min_spacing.saturating_sub(1);
+    let mut out = Vec::new();
+
+    for (label, budget) in budgets.iter().enumerate() {
+        if budget.count == 0 {
+            continue;
+        }
+        let total: u32 = budget.weights.iter().sum();
+        assert!(total > 0, "region {} has no items to pick from", label);
+
+        let mut cells: Vec<_> = labels
+            .iter()
+            .filter(|(coord, l)| **l == label && passable(*coord))
+            .map(|(coord, _)| coord)
+            .collect();
+        // Fisher-Yates
+        for i in (1..cells.len()).rev() {
+            let j = rng.range(0, i as u32) as usize;
+            cells.swap(i, j);
+        }
+
+        let mut placed = 0;
+        for cell in cells {
+            if placed == budget.count {
+                break;
+            }
+            let window = Area::new(
+                Coord::new(cell.x.saturating_sub(reach), cell.y.saturating_sub(reach)),
+                reach * 2 + 1,
+                reach * 2 + 1,
+            );
+            let crowded = window.into_iter().any(|other| {
+                taken.contains(other)
+                    && other.euclidean_distance_sq(cell) < min_spacing * min_spacing
+            });
+            if crowded {
+                continue;
+            }
+
+            let mut roll = rng.range(0, total - 1);
+            let item = budget
+                .weights
+                .iter()
+                .position(|weight| {
+                    if roll < *weight {
+                        true
+                    } else {
+                        roll -= weight;
+                        false
+                    }
+                })
+                .unwrap();
+            taken.insert(cell, ());
+            out.push((cell, item));
+            placed += 1;
+        }
+    }
+    out
+}
+
+/// Turn a permutation of `0..n` into thresholds evenly spaced between 0
+/// and 1.
+fn ranks_to_grid(width: u32, height: u32, ranks: &[u32]) -> Grid<f32> {
+    let count = ranks.len() as f32;
+    let mut grid = Grid::new(width, height);
+    for (coord, rank) in grid.area().into_iter().zip(ranks) {
+        grid.insert(coord, (*rank as f32 + 0.5) / count);
+    }
+    grid
+}
+
+/// A binary pattern on a torus, with how crowded each cell is by the filled
+/// cells around it.
+#[derive(Debug, Clone)]
+struct EnergyField {
+    width: usize,
+    height: usize,
+    on: Vec<bool>,
+    energy: Vec<f32>,
+    /// Gaussian falloff for each offset, wrapping around.
+    kernel: Vec<f32>,
+    filled: usize,
+}
+
+impl EnergyField {
+    const SIGMA: f32 = 1.5;
+
+    fn new(width: u32, height: u32) -> Self {
+        let (width, height) = (width as usize, height as usize);
+        let wrapped = |d: usize, size: usize| d.min(size - d) as f32;
+        let kernel = (0..width * height)
+            .map(|idx| {
+                let dx = wrapped(idx % width, width);
+                let dy = wrapped(idx / width, height);
+                (-(dx * dx + dy * dy) / (2.0 * Self::SIGMA * Self::SIGMA)).exp()
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            on: vec![false; width * height],
+            energy: vec![0.0; width * height],
+            kernel,
+            filled: 0,
+        }
+    }
+
+    fn set(&mut self, idx: usize, on: bool) {
+        if self.on[idx] == on {
+            return;
+        }
+        self.on[idx] = on;
+        let sign = if on { 1.0 } else { -1.0 };
+        if on {
+            self.filled += 1;
+        } else {
+            self.filled -= 1;
+        }
+        let (x, y) = (idx % self.width, idx / self.width);
+        for (other, energy) in self.energy.iter_mut().enumerate() {
+            let dx = (other % self.width + self.width - x) % self.width;
+            let dy = (other / self.width + self.height - y) % self.height;
+            *energy += sign * self.kernel[dy * self.width + dx];
+        }
+    }
+
+    /// The filled cell with the most filled cells around it.
+    fn tightest_cluster(&self) -> usize {
+        self.extreme(true, |a, b| a > b)
+    }
+
+    /// The empty cell with the fewest filled cells around it.
+    fn largest_void(&self) -> usize {
+        self.extreme(false, |a, b| a < b)
+    }
+
+    fn extreme(&self, on: bool, better: impl Fn(f32, f32) -> bool) -> usize {
+        let mut best: Option<usize> = None;
+        for idx in 0..self.on.len() {
+            if self.on[idx] == on && best.is_none_or(|b| better(self.energy[idx], self.energy[b])) {
+                best = Some(idx);
+            }
+        }
+        best.expect("field has cells of both kinds")
+    }
+}
+
+/// A tiny seedable random number generator, so map generation doesn't need
+/// a dependency and gives the same results everywhere.
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        let out = mix64(self.0);
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        out
+    }
+
+    /// Pick a number from `lo` to `hi`, inclusive.
+    fn range(&mut self, lo: u32, hi: u32) -> u32 {
+        let span = (hi - lo) as u64 + 1;
+        lo + (self.next_u64() % span) as u32
+    }
+
+    fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Region;
+
+    #[test]
+    fn bsp_dungeon_is_connected() {
+        for seed in 0..20 {
+            let config = BspConfig {
+                min_room_size: 3,
+                max_room_size: 8,
+                seed,
+            };
+            let dungeon = bsp_dungeon(60, 40, &config);
+            let bounds = dungeon.grid.area();
+            assert!(dungeon.rooms.len() >= 4);
+            for (i, room) in dungeon.rooms.iter().enumerate() {
+                assert!((3..=8).contains(&room.width) && (3..=8).contains(&room.height));
+                let inner = bounds.shrink(1).unwrap();
+                assert_eq!(inner.intersect(room), Some(*room));
+                for other in &dungeon.rooms[i + 1..] {
+                    assert!(!room.expand(1).overlaps(other));
+                }
+            }
+            for coord in bounds.edges() {
+                assert_eq!(dungeon.grid.get(coord), Some(&Tile::Wall));
+            }
+
+            let is_floor = |c| dungeon.grid.get(c) == Some(&Tile::Floor);
+            let reachable = Region::flood_fill(dungeon.rooms[0].center(), bounds, is_floor);
+            let floors = bounds.into_iter().filter(|c| is_floor(*c)).count();
+            assert_eq!(reachable.len(), floors);
+
+            let again = bsp_dungeon(60, 40, &config);
+            assert_eq!(again.rooms, dungeon.rooms);
+        }
+    }
+
+    #[test]
+    fn bayer() {
+        let matrix = bayer_matrix(2);
+        let ranks: Vec<_> = matrix
+            .iter()
+            .map(|(_, v)| (v * 16.0 - 0.5).round() as u32)
+            .collect();
+        assert_eq!(
+            ranks,
+            [0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5]
+        );
+        assert_eq!(bayer_matrix(0).get(Coord::new(0, 0)), Some(&0.5));
+    }
+
+    #[test]
+    fn blue_noise() {
+        let matrix = blue_noise_matrix(16, 16, 3);
+        let mut ranks: Vec<_> = matrix
+            .iter()
+            .map(|(_, v)| (v * 256.0 - 0.5).round() as u32)
+            .collect();
+        ranks.sort();
+        assert_eq!(ranks, (0..256).collect::<Vec<_>>());
+
+        // The lowest thresholds should be spread out, with none touching
+        let sparse: Vec<_> = matrix
+            .iter()
+            .filter(|(_, v)| **v < 1.0 / 16.0)
+            .map(|(c, _)| c)
+            .collect();
+        assert_eq!(sparse.len(), 16);
+        for (i, a) in sparse.iter().enumerate() {
+            for b in &sparse[i + 1..] {
+                let dx = a.x.abs_diff(b.x).min(16 - a.x.abs_diff(b.x));
+                let dy = a.y.abs_diff(b.y).min(16 - a.y.abs_diff(b.y));
+                assert!(dx.max(dy) > 1, "{} and {} touch", a, b);
+            }
+        }
+
+        assert_eq!(
+            blue_noise_matrix(16, 16, 3).iter().collect::<Vec<_>>(),
+            matrix.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(blue_noise_matrix(1, 1, 0).get(Coord::new(0, 0)), Some(&0.5));
+    }
+
+    #[test]
+    fn worley() {
+        let at = |grid: &Grid<f32>, coord| *grid.get(coord).unwrap();
+        let area = Area::new(Coord::ZERO, 32, 32);
+        let near = worley_noise(area, 8, 5, WorleyOutput::Nearest);
+        let far = worley_noise(area, 8, 5, WorleyOutput::SecondNearest);
+        let edges = worley_noise(area, 8, 5, WorleyOutput::Edges);
+        for coord in area {
+            let (n, f, e) = (at(&near, coord), at(&far, coord), at(&edges, coord));
+            assert!((0.0..1.5).contains(&n), "{} at {}", n, coord);
+            assert!(n <= f);
+            assert!((e - (f - n)).abs() < 1e-6);
+        }
+        // There are 16 squares, each with a feature point near some cells
+        let lows = near.iter().filter(|(_, v)| **v < 0.1).count();
+        assert!(lows >= 16, "only {} cells near a point", lows);
+
+        let piece = Area::new(Coord::new(10, 12), 8, 8);
+        let piece_noise = worley_noise(piece, 8, 5, WorleyOutput::Nearest);
+        assert_eq!((piece_noise.width(), piece_noise.height()), (8, 8));
+        for coord in piece {
+            assert_eq!(at(&piece_noise, coord - piece.corner), at(&near, coord));
+        }
+
+        let other = worley_noise(area, 8, 6, WorleyOutput::Nearest);
+        assert!(area.into_iter().any(|c| at(&other, c) != at(&near, c)));
+    }
+
+    #[test]
+    fn loot() {
+        // Two regions side by side, with a wall down the middle of the left
+        let mut labels = Grid::new(20, 10);
+        for coord in labels.area() {
+            labels.insert(coord, (coord.x >= 10) as usize);
+        }
+        let budgets = [
+            LootBudget {
+                count: 4,
+                weights: vec![1, 0, 3],
+            },
+            LootBudget {
+                count: 100,
+                weights: vec![0, 1],
+            },
+        ];
+        let passable = |c: Coord| c.x != 5;
+        let loot = scatter_loot(&labels, &budgets, 3, passable, 9);
+
+        let (left, right): (Vec<_>, Vec<_>) = loot.iter().partition(|(c, _)| c.x < 10);
+        assert_eq!(left.len(), 4);
+        assert!(left.iter().all(|(_, item)| *item != 1));
+        // Not nearly 100 fit, but it should be packed fairly tightly
+        assert!((8..30).contains(&right.len()), "{}", right.len());
+        assert!(right.iter().all(|(_, item)| *item == 1));
+        for (i, (a, _)) in loot.iter().enumerate() {
+            assert!(passable(*a));
+            for (b, _) in &loot[i + 1..] {
+                assert!(a.euclidean_distance_sq(*b) >= 9, "{} and {}", a, b);
+            }
+        }
+
+        assert_eq!(scatter_loot(&labels, &budgets, 3, passable, 9), loot);
+        assert_ne!(scatter_loot(&labels, &budgets, 3, passable, 10), loot);
+        // With the second budget on its own, it's for the left region, which
+        // has exactly 100 cells
+        let unspaced = scatter_loot(&labels, &budgets[1..], 0, |_| true, 9);
+        assert_eq!(unspaced.len(), 100);
+    }
+
+    #[test]
+    fn bsp_dungeon_too_small() {
+        let dungeon = bsp_dungeon(5, 20, &BspConfig::default());
+        assert!(dungeon.rooms.is_empty());
+        assert_eq!(dungeon.grid.iter().count(), 100);
+        assert!(dungeon.grid.iter().all(|(_, tile)| *tile == Tile::Wall));
+    }
+}
@@ -0,0 +1,246 @@
+//! Pathfinding over grids.
+
+use std::{
+  cmp::Reverse,
+  collections::{BinaryHeap, HashMap},
+};
+
+use crate::{Area, BitGrid, Coord, EdgeCoord, EdgeGrid};
+
+/// Something that can say whether movement across an edge between two cells
+/// is forbidden, like a thin wall.
+pub trait EdgeBlocker {
+  fn blocks(&self, edge: EdgeCoord) -> bool;
+}
+
+/// Any filled edge blocks movement.
+impl<T> EdgeBlocker for EdgeGrid<T> {
+  fn blocks(&self, edge: EdgeCoord) -> bool {
+    self.contains(edge)
+  }
+}
+
+impl<T> EdgeBlocker for &EdgeGrid<T> {
+  fn blocks(&self, edge: EdgeCoord) -> bool {
+    self.contains(edge)
+  }
+}
+
+impl<F: Fn(EdgeCoord) -> bool> EdgeBlocker for F {
+  fn blocks(&self, edge: EdgeCoord) -> bool {
+    self(edge)
+  }
+}
+
+/// Something that can say whether a cell can be walked into.
+pub trait Passable {
+  fn passable(&self, coord: Coord) -> bool;
+}
+
+/// Set bits are passable.
+impl Passable for BitGrid {
+  fn passable(&self, coord: Coord) -> bool {
+    self.get(coord)
+  }
+}
+
+impl Passable for &BitGrid {
+  fn passable(&self, coord: Coord) -> bool {
+    self.get(coord)
+  }
+}
+
+impl<F: Fn(Coord) -> bool> Passable for F {
+  fn passable(&self, coord: Coord) -> bool {
+    self(coord)
+  }
+}
+
+/// An [`EdgeBlocker`] that never blocks anything.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoWalls;
+
+impl EdgeBlocker for NoWalls {
+  fn blocks(&self, _edge: EdgeCoord) -> bool {
+    false
+  }
+}
+
+/// Finds shortest orthagonal paths between cells with A*.
+///
+/// Cells are only entered if they're inside the bounds and `passable` says so;
+/// it's usually a closure, or a [`BitGrid`] of the passable cells.
+/// Optionally, [`Self::with_walls`] forbids moving between two passable cells
+/// if there's a wall on the edge between them.
+#[derive(Debug, Clone)]
+pub struct Pathfinder<P, W = NoWalls> {
+  bounds: Area,
+  passable: P,
+  walls: W,
+}
+
+impl<P: Fn(Coord) -> bool> Pathfinder<P> {
+  pub fn new(bounds: Area, passable: P) -> Self {
+    Self {
+      bounds,
+      passable,
+      walls: NoWalls,
+    }
+  }
+}
+
+impl<'a> Pathfinder<&'a BitGrid> {
+  /// Find paths through the set bits of `passable`, which also sets the
+  /// bounds.
+  pub fn from_bits(passable: &'a BitGrid) -> Self {
+    Self {
+      bounds: Area::new(Coord::ZERO, passable.width(), passable.height()),
+      passable,
+      walls: NoWalls,
+    }
+  }
+}
+
+impl<P: Passable, W: EdgeBlocker> Pathfinder<P, W> {
+  /// Also consult the given walls when moving between cells.
+  pub fn with_walls<W2: EdgeBlocker>(self, walls: W2) -> Pathfinder<P, W2> {
+    Pathfinder {
+      bounds: self.bounds,
+      passable: self.passable,
+      walls,
+    }
+  }
+
+  /// Can something step directly from `from` to the adjacent cell `to`?
+  pub fn can_step(&self, from: Coord, to: Coord) -> bool {
+    self.bounds.contains(to)
+      && self.passable.passable(to)
+      && EdgeCoord::between(from, to)
+        .is_some_and(|edge| !self.walls.blocks(edge))
+  }
+
+  /// Find the shortest path from `start` to `goal`, including both ends.
+  ///
+  /// Returns `None` if there's no path. The start cell doesn't have to be
+  /// passable.
+  pub fn find(&self, start: Coord, goal: Coord) -> Option<Vec<Coord>> {
+    let mut open = BinaryHeap::<Reverse<(u32, u32, (u32, u32))>>::new();
+    let mut came_from = HashMap::new();
+    let mut costs = HashMap::new();
+
+    costs.insert(start, 0u32);
+    // Coords aren't Ord, so store them as tuples
+    open.push(Reverse((
+      start.manhattan_distance(goal),
+      0u32,
+      start.into(),
+    )));
+
+    while let Some(Reverse((_, cost, here))) = open.pop() {
+      let here = Coord::from(here);
+      if here == goal {
+        let mut path = vec![here];
+        let mut cursor = here;
+        while let Some(&prev) = came_from.get(&cursor) {
+          path.push(prev);
+          cursor = prev;
+        }
+        path.reverse();
+        return Some(path);
+      }
+      if costs.get(&here).is_some_and(|&best| cost > best) {
+        // stale entry
+        continue;
+      }
+
+      for next in here.neighbors4_iter() {
+        if !self.can_step(here, next) {
+          continue;
+        }
+        let next_cost = cost + 1;
+        if costs.get(&next).is_some_and(|&best| best <= next_cost) {
+          continue;
+        }
+        costs.insert(next, next_cost);
+        came_from.insert(next, here);
+        open.push(Reverse((
+          next_cost + next.manhattan_distance(goal),
+          next_cost,
+          next.into(),
+        )));
+      }
+    }
+    None
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::Direction4;
+
+  #[test]
+  fn open_field() {
+    let finder = Pathfinder::new(Area::new(Coord::ZERO, 5, 5), |_| true);
+    let path = finder.find(Coord::new(0, 0), Coord::new(3, 2)).unwrap();
+    assert_eq!(path.len(), 6);
+    assert_eq!(path.first(), Some(&Coord::new(0, 0)));
+    assert_eq!(path.last(), Some(&Coord::new(3, 2)));
+    for pair in path.windows(2) {
+      assert_eq!(pair[0].manhattan_distance(pair[1]), 1);
+    }
+  }
+
+  #[test]
+  fn thin_walls() {
+    // A 3x1 corridor with a wall between the first two cells
+    let mut walls = EdgeGrid::new(3, 1);
+    walls.insert(EdgeCoord::new(Coord::new(0, 0), Direction4::East), ());
+
+    let bounds = Area::new(Coord::ZERO, 3, 1);
+    let finder = Pathfinder::new(bounds, |_| true);
+    assert!(finder.find(Coord::new(0, 0), Coord::new(2, 0)).is_some());
+
+    let finder = finder.with_walls(&walls);
+    assert!(!finder.can_step(Coord::new(0, 0), Coord::new(1, 0)));
+    assert!(!finder.can_step(Coord::new(1, 0), Coord::new(0, 0)));
+    assert_eq!(finder.find(Coord::new(0, 0), Coord::new(2, 0)), None);
+    assert_eq!(
+      finder.find(Coord::new(1, 0), Coord::new(2, 0)),
+      Some(vec![Coord::new(1, 0), Coord::new(2, 0)])
+    );
+
+    // Now let it go around
+    let bounds = Area::new(Coord::ZERO, 3, 2);
+    let finder = Pathfinder::new(bounds, |_| true).with_walls(&walls);
+    let path = finder.find(Coord::new(0, 0), Coord::new(1, 0)).unwrap();
+    assert_eq!(
+      path,
+      [
+        Coord::new(0, 0),
+        Coord::new(0, 1),
+        Coord::new(1, 1),
+        Coord::new(1, 0)
+      ]
+    );
+  }
+
+  #[test]
+  fn blocked_cells() {
+    let bounds = Area::new(Coord::ZERO, 3, 3);
+    let finder = Pathfinder::new(bounds, |c: Coord| c.x != 1);
+    assert_eq!(finder.find(Coord::new(0, 0), Coord::new(2, 2)), None);
+  }
+
+  #[test]
+  fn bit_grid() {
+    let mut floor = BitGrid::new(3, 3);
+    for (x, y) in [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)] {
+      floor.set(Coord::new(x, y), true);
+    }
+    let finder = Pathfinder::from_bits(&floor);
+    let path = finder.find(Coord::new(0, 0), Coord::new(2, 2)).unwrap();
+    assert_eq!(path.len(), 5);
+    assert_eq!(finder.find(Coord::new(0, 0), Coord::new(2, 0)), None);
+  }
+}
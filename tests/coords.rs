@@ -0,0 +1,110 @@
+use aglet::{Coord, CoordVec, ParseCoordError};
+
+#[test]
+fn test_parse() {
+  for s in ["(3, 4)", "3,4", "3 4", "  ( 3 ,4 ) ", "3\t 4"] {
+    assert_eq!(s.parse::<Coord>(), Ok(Coord::new(3, 4)), "{:?}", s);
+  }
+  assert_eq!("(-3, 4)".parse::<CoordVec>(), Ok(CoordVec::new(-3, 4)));
+  assert_eq!("10,20".parse::<Coord<u8>>(), Ok(Coord { x: 10, y: 20 }));
+
+  assert_eq!(
+    "(3, 4".parse::<Coord>(),
+    Err(ParseCoordError::UnbalancedParens)
+  );
+  assert_eq!(
+    "3, 4, 5".parse::<Coord>(),
+    Err(ParseCoordError::WrongComponentCount)
+  );
+  assert_eq!("3".parse::<Coord>(), Err(ParseCoordError::WrongComponentCount));
+  assert!(matches!(
+    "-3, 4".parse::<Coord>(),
+    Err(ParseCoordError::BadComponent(_))
+  ));
+}
+
+#[test]
+fn test_display() {
+  let c = Coord::new(3, 4);
+  assert_eq!(c.to_string(), "(3, 4)");
+  assert_eq!(format!("{:3}", c), "(  3,   4)");
+  assert_eq!(format!("{:<3}", c), "(3  , 4  )");
+  assert_eq!(format!("{:03}", c), "(003, 004)");
+  assert_eq!(format!("{:#}", c), "3,4");
+  assert_eq!(format!("{:+}", CoordVec::new(-1, 2)), "(-1, +2)");
+}
+
+#[test]
+fn test_roundtrip() {
+  for v in [CoordVec::new(0, 0), CoordVec::new(-17, 4), CoordVec::new(i32::MIN, i32::MAX)] {
+    assert_eq!(v.to_string().parse::<CoordVec>(), Ok(v));
+    assert_eq!(format!("{:#}", v).parse::<CoordVec>(), Ok(v));
+  }
+}
+
+#[test]
+fn test_distances() {
+  let a = Coord::new(1, 2);
+  let b = Coord::new(4, 6);
+  assert_eq!(a.abs_diff(b), Coord::new(3, 4));
+  assert_eq!(a.manhattan_distance(b), 7);
+  assert_eq!(b.manhattan_distance(a), 7);
+  assert_eq!(a.chebyshev_distance(b), 4);
+  assert_eq!(a.euclidean_distance_sq(b), 25);
+  assert_eq!(a.abs_diff(b).magnitude(), 5.0);
+  assert_eq!(a.dot(b), 16);
+
+  let v = CoordVec::new(-3, 4);
+  let w = CoordVec::new(2, -1);
+  assert_eq!(v.abs(), CoordVec::new(3, 4));
+  assert_eq!(v.magnitude(), 5.0);
+  assert_eq!(v.manhattan_distance(w), 10);
+  assert_eq!(v.chebyshev_distance(w), 5);
+  assert_eq!(v.euclidean_distance_sq(w), 50);
+  assert_eq!(v.dot(w), -10);
+}
+
+#[test]
+fn test_distance_overflow() {
+  let zero = Coord::ZERO;
+  let max = Coord::new(u32::MAX, u32::MAX);
+  assert_eq!(zero.chebyshev_distance(max), u32::MAX);
+  assert_eq!(zero.manhattan_distance(max), u32::MAX);
+  assert_eq!(max.manhattan_distance(zero), u32::MAX);
+  assert_eq!(zero.euclidean_distance_sq(max), u32::MAX);
+  assert_eq!(max.dot(max), u32::MAX);
+  assert_eq!(
+    Coord::new(u32::MAX, 0).manhattan_distance(Coord::new(0, 0)),
+    u32::MAX
+  );
+
+  let min = CoordVec::new(i32::MIN, i32::MIN);
+  let top = CoordVec::new(i32::MAX, i32::MAX);
+  assert_eq!(min.chebyshev_distance(top), u32::MAX);
+  assert_eq!(min.manhattan_distance(top), u32::MAX);
+  assert_eq!(min.euclidean_distance_sq(top), u32::MAX);
+  assert_eq!(min.abs(), top);
+  assert_eq!(min.dot(top), i32::MIN);
+  assert_eq!(min.dot(min), i32::MAX);
+}
+
+#[test]
+fn test_basis() {
+  use aglet::{Direction4, Rotation};
+
+  // 2 ahead, 1 to the right
+  let local = CoordVec::new(1, -2);
+  assert_eq!(local.in_basis(Direction4::North), CoordVec::new(1, -2));
+  assert_eq!(local.in_basis(Direction4::East), CoordVec::new(2, 1));
+  assert_eq!(local.in_basis(Direction4::South), CoordVec::new(-1, 2));
+  assert_eq!(local * Direction4::West, CoordVec::new(-2, -1));
+
+  for dir in Direction4::DIRECTIONS {
+    assert_eq!(CoordVec::new(0, -1).in_basis(dir), dir.deltas());
+    assert_eq!(local.in_basis(dir).from_basis(dir), local);
+  }
+
+  let v = CoordVec::new(3, 1);
+  assert_eq!(v.rotate(Rotation::Clockwise).rotate_by(-1), v);
+  assert_eq!(v.rotate_by(4), v);
+}
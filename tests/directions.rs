@@ -1,4 +1,4 @@
-use aglet::{Direction4, Direction8};
+use aglet::{CoordVec, Direction16, Direction4, Direction8, Direction9};
 
 #[test]
 fn test_spin() {
@@ -12,3 +12,86 @@ fn test_spin() {
     assert_eq!(dir8, dir8.rotate_by(8));
   }
 }
+
+#[test]
+fn test_radians() {
+  use std::f32::consts::{FRAC_PI_2, TAU};
+
+  assert_eq!(Direction4::East.radians(), 0.0);
+  assert_eq!(Direction4::South.radians(), FRAC_PI_2);
+  assert_eq!(Direction8::North.radians(), 3.0 * FRAC_PI_2);
+
+  for dir in Direction4::DIRECTIONS {
+    assert_eq!(Direction4::from_radians(dir.radians()), dir);
+    assert_eq!(Direction4::from_radians(dir.radians() - TAU), dir);
+  }
+  for dir in Direction8::DIRECTIONS {
+    assert_eq!(Direction8::from_radians(dir.radians()), dir);
+    assert_eq!(Direction16::from(dir).radians(), dir.radians());
+  }
+  for dir in Direction16::DIRECTIONS {
+    assert_eq!(Direction16::from_radians(dir.radians() + 0.1), dir);
+    assert_eq!(Direction16::from_radians(dir.radians() + TAU), dir);
+    let deltas = dir.deltas();
+    let angle = (deltas.y as f32).atan2(deltas.x as f32);
+    assert_eq!(Direction16::from_radians(angle), dir);
+  }
+
+  // Snapping to the nearest
+  assert_eq!(Direction8::from_radians(0.3), Direction8::East);
+  assert_eq!(Direction8::from_radians(-0.5), Direction8::NorthEast);
+  assert_eq!(Direction16::from_radians(-0.5), Direction16::EastNorthEast);
+}
+
+#[test]
+fn test_direction16() {
+  assert_eq!(Direction16::North.rotate_by(3), Direction16::EastNorthEast);
+  assert_eq!(
+    Direction16::North.rotate_by(-1),
+    Direction16::NorthNorthWest
+  );
+  assert_eq!(
+    Direction16::SouthSouthEast.flip(),
+    Direction16::NorthNorthWest
+  );
+  assert_eq!(Direction16::from(Direction4::West), Direction16::West);
+  for dir in Direction16::DIRECTIONS {
+    assert_eq!(dir.rotate_by(16), dir);
+    assert_eq!(dir.flip().deltas() + dir.deltas(), CoordVec::new(0, 0));
+  }
+}
+
+#[test]
+fn test_point9() {
+  // North is positive Y here
+  assert_eq!(CoordVec::new(1, 0).point9(), Direction9::East);
+  assert_eq!(CoordVec::new(-3, 0).point9(), Direction9::West);
+  assert_eq!(CoordVec::new(0, 2).point9(), Direction9::North);
+  assert_eq!(CoordVec::new(1, 1).point9(), Direction9::NorthEast);
+  assert_eq!(CoordVec::new(-5, -4).point9(), Direction9::SouthWest);
+  assert_eq!(CoordVec::new(0, 0).point9(), Direction9::Center);
+}
+
+#[test]
+fn test_set_display() {
+  use aglet::{Direction4Set, Direction8Set, DirectionSetExt};
+
+  let set = Direction4::North | Direction4::South | Direction4::East;
+  assert_eq!(set.display().to_string(), "N|E|S");
+  assert_eq!(Direction4Set::empty().display().to_string(), "-");
+  assert_eq!(Direction4Set::all().display().to_string(), "N|E|S|W");
+
+  let set: Direction8Set = Direction8::NorthWest | Direction8::SouthEast;
+  assert_eq!(set.display().to_string(), "SE|NW");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_set_names_deserialize() {
+  use aglet::Direction4Set;
+  use serde::de::value::{Error, SeqDeserializer};
+
+  let de = SeqDeserializer::<_, Error>::new(["North", "West"].into_iter());
+  let set: Direction4Set = aglet::direction_set_names::deserialize(de).unwrap();
+  assert_eq!(set, Direction4::North | Direction4::West);
+}
@@ -0,0 +1,43 @@
+use aglet::{Area, Coord, CoordVec, Direction4, LineIter};
+
+#[test]
+#[cfg(feature = "grid")]
+fn test_usize_coords() {
+  use aglet::Grid;
+
+  let mut grid = Grid::new(4, 4);
+  let coord: Coord<usize> = (2, 3).into();
+  grid.insert(coord, 'a');
+  assert_eq!(grid.get(Coord::new(2, 3)), Some(&'a'));
+  assert_eq!(grid.get(coord), Some(&'a'));
+  assert!(!grid.contains(Coord { x: usize::MAX, y: 0 }));
+
+  assert_eq!(coord.to_2d_idx(4), 14);
+  assert_eq!(coord.offset4(Direction4::North), Some(Coord { x: 2, y: 2 }));
+  assert_eq!(Coord { x: 0usize, y: 0 }.offset4(Direction4::West), None);
+}
+
+#[test]
+fn test_i64_coordvecs() {
+  let v = CoordVec { x: -3i64, y: 5 } + Direction4::East;
+  assert_eq!(v, CoordVec { x: -2, y: 5 });
+  assert_eq!(v.to_coord(), None);
+  assert_eq!(
+    (v + CoordVec { x: 2, y: 0 }).to_coord(),
+    Some(Coord { x: 0u64, y: 5 })
+  );
+  assert_eq!(Direction4::South.deltas_as::<i64>(), CoordVec { x: 0, y: 1 });
+}
+
+#[test]
+fn test_generic_iters() {
+  let area = Area::new(Coord { x: 0u8, y: 0 }, 3, 2);
+  assert_eq!(area.into_iter().count(), 6);
+
+  let line: Vec<Coord<u16>> =
+    LineIter::new(Coord { x: 0, y: 1 }, Coord { x: 6, y: 4 }).collect();
+  let expected: Vec<_> = LineIter::new(Coord::new(0, 1), Coord::new(6, 4))
+    .map(|c| Coord { x: c.x as u16, y: c.y as u16 })
+    .collect();
+  assert_eq!(line, expected);
+}